@@ -0,0 +1,114 @@
+use super::{util::prepare_delev_skip_patterns, DelevSkipPatterns};
+use anyhow::{anyhow, Context, Result};
+use confique::Config;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Config)]
+struct DelevRulesFile {
+    /// Rules are checked in order of appearance. First rule that matches both leveled list and subrecord is used.
+    #[config(default = [])]
+    rule: Vec<DelevRuleRaw>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DelevRuleRaw {
+    name: Option<String>,
+    #[serde(default)]
+    list: Vec<String>,
+    #[serde(default)]
+    subrecord: Vec<String>,
+    to: Option<u16>,
+    scale: Option<f64>,
+    segment: Option<u16>,
+    min: Option<u16>,
+}
+
+pub(crate) enum DelevTarget {
+    Default,
+    To(u16),
+    Scale(f64),
+    Segment(u16),
+}
+
+pub(crate) struct DelevRule {
+    pub(crate) name: String,
+    pub(crate) list: DelevSkipPatterns,
+    pub(crate) subrecord: DelevSkipPatterns,
+    pub(crate) target: DelevTarget,
+    pub(crate) min: u16,
+}
+
+#[derive(Default)]
+pub(crate) struct DelevRules(pub(crate) Vec<DelevRule>);
+
+impl DelevRules {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn matches_list(&self, id_lowercased: &str) -> bool {
+        self.0.iter().any(|rule| pattern_matches(id_lowercased, &rule.list))
+    }
+
+    pub(crate) fn get(&self, id_lowercased: &str, name_lowercased: &str) -> Option<&DelevRule> {
+        self.0
+            .iter()
+            .find(|rule| pattern_matches(id_lowercased, &rule.list) && pattern_matches(name_lowercased, &rule.subrecord))
+    }
+}
+
+// COMMENT: empty patterns match everything, so rule may be limited to lists or subrecords only
+fn pattern_matches(string_lowercased: &str, patterns: &DelevSkipPatterns) -> bool {
+    patterns.is_empty
+        || patterns.exact.iter().any(|exact| string_lowercased == exact)
+        || patterns.prefix.iter().any(|prefix| string_lowercased.starts_with(prefix))
+        || patterns.suffix.iter().any(|suffix| string_lowercased.ends_with(suffix))
+        || patterns.infix.iter().any(|infix| string_lowercased.contains(infix))
+}
+
+pub(super) fn get_delev_rules(path: &str, max_delev_to: u16) -> Result<DelevRules> {
+    if path.is_empty() {
+        return Ok(DelevRules::default());
+    }
+    if !Path::new(path).exists() {
+        return Err(anyhow!("Delev rules file \"{path}\" doesn't exist"));
+    }
+    let file = DelevRulesFile::builder()
+        .file(path)
+        .load()
+        .with_context(|| format!("Failed to load delev rules file \"{path}\""))?;
+    let mut rules = Vec::new();
+    for (index, raw) in file.rule.into_iter().enumerate() {
+        let name = raw.name.unwrap_or_else(|| format!("#{}", index + 1));
+        let target = match (raw.to, raw.scale, raw.segment) {
+            (None, None, None) => DelevTarget::Default,
+            (Some(to), None, None) => DelevTarget::To(to),
+            (None, Some(scale), None) => {
+                if scale <= 0.0 {
+                    return Err(anyhow!("Delev rule \"{name}\": scale({scale}) should be larger than 0"));
+                }
+                DelevTarget::Scale(scale)
+            }
+            (None, None, Some(segment)) => {
+                if segment < max_delev_to {
+                    return Err(anyhow!(
+                        "Delev rule \"{name}\": segment({segment}) should be larger or equal to level to delevel to({max_delev_to})"
+                    ));
+                }
+                DelevTarget::Segment(segment)
+            }
+            _ => return Err(anyhow!("Delev rule \"{name}\": only one of \"to\", \"scale\" and \"segment\" may be set")),
+        };
+        let lowercase = |patterns: Vec<String>| -> Vec<String> { patterns.iter().map(|pattern| pattern.to_lowercase()).collect() };
+        rules.push(DelevRule {
+            name,
+            list: prepare_delev_skip_patterns(lowercase(raw.list)),
+            subrecord: prepare_delev_skip_patterns(lowercase(raw.subrecord)),
+            target,
+            min: raw.min.unwrap_or_default(),
+        });
+    }
+    Ok(DelevRules(rules))
+}
@@ -10,21 +10,26 @@ use std::{
     io::stdin,
     path::PathBuf,
 };
+mod delev_rules;
 mod options;
 mod settings;
 mod util;
+use delev_rules::get_delev_rules;
+pub(crate) use delev_rules::{DelevRule, DelevRules, DelevTarget};
 use options::{get_options, Options};
 use settings::{get_settings, Settings};
 use util::{
-    append_default_to_skip, backup_settings_file, check_settings_version, check_verboseness, get_color, get_compare_only,
-    get_delev_to, get_exe_name_and_dir, get_fogbug_fixed_value, get_interior_grid_change, get_kind_delev_segment, get_kind_delev_to,
-    get_log_file, get_output_file, get_progress_frequency, get_settings_file, prepare_delev_skip_patterns,
-    prepare_plugin_extensions_to_ignore, show_configuration_add_header,
+    append_default_to_skip, backup_settings_file, check_settings_version, check_verboseness, get_color, get_compare_only, get_delev_to,
+    get_exe_name_and_dir, get_fogbug_fixed_value, get_interior_grid_change, get_kind_delev_segment, get_kind_delev_to, get_ll_replace,
+    get_log_file, get_merge_strategy, get_output_file, get_progress_frequency, get_relevel_transform, get_settings_file,
+    prepare_delev_skip_patterns, prepare_plugin_extensions_to_ignore, show_configuration_add_header,
 };
 
-const MERGE_TYPES: [&str; 30] = [
-    "GMST", "CLAS", "RACE", "SOUN", "SKIL", "MGEF", "BSGN", "SPEL", "STAT", "DOOR", "MISC", "WEAP", "CONT", "CREA", "BODY", "LIGH",
-    "ENCH", "NPC_", "ARMO", "CLOT", "REPA", "ACTI", "APPA", "LOCK", "PROB", "INGR", "BOOK", "ALCH", "CELL", "SNDG",
+// COMMENT: REGN and DIAL are supported, but not merged by default to keep output unchanged, add them to merge_types to opt in
+const MERGE_TYPES: [&str; 35] = [
+    "GMST", "CLAS", "FACT", "RACE", "SOUN", "SKIL", "MGEF", "BSGN", "LTEX", "SPEL", "STAT", "DOOR", "MISC", "WEAP", "CONT", "CREA",
+    "BODY", "LIGH", "ENCH", "NPC_", "ARMO", "CLOT", "REPA", "ACTI", "APPA", "LOCK", "PROB", "INGR", "BOOK", "ALCH", "CELL", "LAND",
+    "PGRD", "SNDG", "SCPT",
 ];
 const ALWAYS_DELETE: [&str; 4] = ["Morrowind.esm", "Tribunal.esm", "Bloodmoon.esm", "Tamriel_Data.esm"];
 const NEVER_DELETE: [&str; 3] = ["Wares-base.esm", "abotWaterLife.esm", "RepopulatedMorrowind.ESM"];
@@ -32,6 +37,10 @@ const NEVER_DELETE: [&str; 3] = ["Wares-base.esm", "abotWaterLife.esm", "Repopul
 #[derive(Default)]
 pub(crate) struct Cfg {
     pub(crate) config: String,
+    pub(crate) omw_local_dir: String,
+    pub(crate) mo2_profile: String,
+    pub(crate) plugins: Vec<String>,
+    pub(crate) load_order_file: String,
     pub(crate) output: OutputFile,
     pub(crate) dry_run: bool,
     pub(crate) log: Option<PathBuf>,
@@ -51,6 +60,12 @@ pub(crate) struct Cfg {
     pub(crate) always_delete: Vec<String>,
     pub(crate) never_delete: Vec<String>,
     pub(crate) no_threshold_warnings: bool,
+    pub(crate) strip_dangling: bool,
+    pub(crate) max_nesting_depth: u16,
+    pub(crate) ll_remove: Vec<String>,
+    pub(crate) ll_replace: Vec<(String, String)>,
+    pub(crate) chance_none_strategy: MergeStrategy,
+    pub(crate) list_flags_strategy: MergeStrategy,
     pub(crate) delev: bool,
     pub(crate) delev_distinct: bool,
     pub(crate) delev_output: OutputFile,
@@ -61,13 +76,22 @@ pub(crate) struct Cfg {
     pub(crate) delev_no_skip_list: DelevSkipPatterns,
     pub(crate) delev_skip_subrecord: DelevSkipPatterns,
     pub(crate) delev_no_skip_subrecord: DelevSkipPatterns,
+    pub(crate) delev_rules: DelevRules,
+    pub(crate) relevel: bool,
+    pub(crate) relevel_output: OutputFile,
+    pub(crate) relevel_transform: RelevelTransform,
     pub(crate) no_compare: bool,
     pub(crate) compare_only: bool,
     pub(crate) compare_only_name: String,
     pub(crate) compare_with: String,
     pub(crate) compare_delev_with: String,
     pub(crate) compare_common: bool,
+    pub(crate) simulate: u16,
+    pub(crate) graph_dot: String,
+    pub(crate) graph_json: String,
+    pub(crate) explain: String,
     pub(crate) multipatch: Multipatch,
+    pub(crate) groundcover: bool,
     pub(crate) merge: Merge,
     pub(crate) verbose: u8,
     pub(crate) quiet: bool,
@@ -120,6 +144,16 @@ impl Cfg {
         if !self.merge.cell && self.multipatch.cellnames {
             self.meta.multipatch_cellnames = true;
         }
+        // COMMENT: groundcover cells are only read by cellnames and fogbug multipatch, merged CELL records never include them
+        if self.groundcover && !self.merge.cell && (self.multipatch.cellnames || self.multipatch.fogbug) {
+            self.meta.read_groundcover = true;
+        }
+        if !self.ll_remove.is_empty() || !self.ll_replace.is_empty() {
+            self.meta.ll_edit = true;
+        }
+        if !self.graph_dot.is_empty() || !self.graph_json.is_empty() {
+            self.meta.graph = true;
+        }
     }
 
     #[cfg(test)]
@@ -159,11 +193,44 @@ pub(crate) struct ListKind {
     pub(crate) delev_segment_ceil: u16,
 }
 
+#[derive(Default)]
+pub(crate) struct RelevelTransform {
+    pub(crate) scale: f64,
+    pub(crate) add: i32,
+    pub(crate) min: u16,
+    pub(crate) max: u16,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum MergeStrategy {
+    #[default]
+    Novel,
+    Last,
+    First,
+    Min,
+    Max,
+    Changed,
+}
+
+impl MergeStrategy {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            MergeStrategy::Novel => "novel",
+            MergeStrategy::Last => "last",
+            MergeStrategy::First => "first",
+            MergeStrategy::Min => "min",
+            MergeStrategy::Max => "max",
+            MergeStrategy::Changed => "changed",
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) enum PluginKind {
     #[default]
     Merge,
     Delev,
+    Relevel,
 }
 
 #[derive(Default)]
@@ -226,6 +293,9 @@ pub(crate) struct Meta {
     pub(crate) skip_interior: bool,
     pub(crate) fix_fog: bool,
     pub(crate) multipatch_cellnames: bool,
+    pub(crate) read_groundcover: bool,
+    pub(crate) ll_edit: bool,
+    pub(crate) graph: bool,
 }
 
 #[derive(Default)]
@@ -241,7 +311,27 @@ pub(crate) struct Guts {
     pub(crate) mor_data_files_dir: String,
     pub(crate) omw_line_beginning_content: String,
     pub(crate) omw_line_beginning_data: String,
+    pub(crate) omw_line_beginning_data_local: String,
+    pub(crate) omw_line_beginning_config: String,
+    pub(crate) omw_line_beginning_replace: String,
+    pub(crate) omw_line_beginning_groundcover: String,
+    pub(crate) omw_config_file_name: String,
+    pub(crate) omw_global_dir_linux: String,
+    pub(crate) omw_global_dir_macos: String,
+    pub(crate) omw_global_dir_windows: String,
+    pub(crate) omw_userdata_suffix_linux_macos: String,
+    pub(crate) omw_userdata_suffix_windows: String,
+    pub(crate) omw_userconfig_suffix_linux_macos: String,
+    pub(crate) omw_userconfig_suffix_windows: String,
     pub(crate) omw_plugin_extensions: Vec<OsString>,
+    pub(crate) mo2_instance_dir: String,
+    pub(crate) mo2_ini: String,
+    pub(crate) mo2_line_beginning_game_path: String,
+    pub(crate) mo2_mods_dir: String,
+    pub(crate) mo2_overwrite_dir: String,
+    pub(crate) mo2_modlist: String,
+    pub(crate) mo2_plugins: String,
+    pub(crate) mo2_loadorder: String,
     pub(crate) plugin_extensions_to_ignore: Vec<String>,
     pub(crate) skip_default_reasons: Vec<Vec<String>>,
     pub(crate) skip_unexpected_tags_default: Vec<String>,
@@ -249,10 +339,12 @@ pub(crate) struct Guts {
     pub(crate) omw_cs_data_path_suffix_windows: String,
     pub(crate) omw_cs_data_paths_list: Vec<String>,
     pub(crate) no_skip_unknown_cell_flags: bool,
+    pub(crate) read_batch_size: usize,
     pub(crate) header_version: f32,
     pub(crate) header_author: String,
     pub(crate) header_description_merge: String,
     pub(crate) header_description_delev: String,
+    pub(crate) header_description_relevel: String,
     pub(crate) header_description_merge_and_delev: String,
     pub(crate) log_backup_suffix: String,
     pub(crate) progress_frequency: u8,
@@ -265,16 +357,23 @@ pub(crate) struct Guts {
     pub(crate) multipatch_fogbug_fixed_value: f32,
     pub(crate) auto_resolve_lower_limit: f64,
     pub(crate) verboseness_details_deleted_subrecords: u8,
+    pub(crate) verboseness_details_dangling_subrecords: u8,
+    pub(crate) verboseness_details_nesting_problems: u8,
+    pub(crate) verboseness_details_edited_subrecords: u8,
+    pub(crate) verboseness_details_merged_values: u8,
     pub(crate) verboseness_details_untouched_lists: u8,
     pub(crate) verboseness_details_threshold_resolved: u8,
     pub(crate) verboseness_details_threshold_skipped: u8,
     pub(crate) verboseness_details_threshold_warnings: u8,
     pub(crate) verboseness_details_deleveled_subrecords: u8,
+    pub(crate) verboseness_details_releveled_subrecords: u8,
     pub(crate) verboseness_details_merge_warnings: u8,
     pub(crate) verboseness_details_merge_record_merged: u8,
     pub(crate) verboseness_details_merge_record_multipatched: u8,
     pub(crate) verboseness_details_merge_field_changed: u8,
     pub(crate) verboseness_details_compare_plugins: u8,
+    pub(crate) verboseness_details_script_conflicts: u8,
+    pub(crate) verboseness_details_simulation: u8,
     pub(crate) verboseness_show_configuration: u8,
     pub(crate) debug_level_merge_list_all_plugins: u8,
     pub(crate) debug_level_merge_compare_to_the_last: u8,
@@ -470,11 +569,18 @@ impl Cfg {
             opt_or_set_some!(delev_items_segment, 0),
         )?;
         let no_skip_default = opt_or_set_bool!(no_skip_default);
+        let delev = opt_or_set_bool!(delev);
+        let relevel = opt_or_set_bool!(relevel);
         let (compare_only, compare_only_name) = get_compare_only(&opt.compare_only, &mut show_configuration)?;
         let mut cfg = Cfg {
             output: get_output_file(&opt, &set, PluginKind::Merge, &compare_only_name, &mut show_configuration)?,
             delev_output: get_output_file(&opt, &set, PluginKind::Delev, "", &mut show_configuration)?,
+            relevel_output: get_output_file(&opt, &set, PluginKind::Relevel, "", &mut show_configuration)?,
             config: opt_or_set_some!(config, ""),
+            omw_local_dir: opt_or_set_some!(omw_local_dir, ""),
+            mo2_profile: opt_or_set_some!(mo2_profile, ""),
+            plugins: opt_or_set_some!(plugins, Vec::<String>::new()),
+            load_order_file: opt_or_set_some!(load_order_file, ""),
             dry_run: opt_or_set_bool!(dry_run),
             no_log,
             log: get_log_file(no_log, opt_or_set_some!(log, ""), exe, dir)?,
@@ -513,7 +619,13 @@ impl Cfg {
             always_delete: opt_or_set_vec_lowercase!(always_delete, ALWAYS_DELETE),
             never_delete: opt_or_set_vec_lowercase!(never_delete, NEVER_DELETE),
             no_threshold_warnings: opt_or_set_bool!(no_threshold_warnings),
-            delev: opt_or_set_bool!(delev),
+            strip_dangling: opt_or_set_bool!(strip_dangling),
+            max_nesting_depth: opt_or_set_some!(max_nesting_depth, 5),
+            ll_remove: opt_or_set_vec_lowercase!(ll_remove),
+            ll_replace: get_ll_replace(opt_or_set_vec_lowercase!(ll_replace))?,
+            chance_none_strategy: get_merge_strategy(&opt_or_set_some!(chance_none_strategy, "novel"), "chance_none_strategy")?,
+            list_flags_strategy: get_merge_strategy(&opt_or_set_some!(list_flags_strategy, "novel"), "list_flags_strategy")?,
+            delev,
             delev_distinct: opt_or_set_bool!(delev_distinct),
             delev_random: opt_or_set_bool!(delev_random),
             delev_segment_progressive: opt_or_set_bool!(delev_segment_progressive),
@@ -522,12 +634,27 @@ impl Cfg {
             delev_no_skip_list: prepare_delev_skip_patterns(opt_or_set_vec_lowercase!(delev_no_skip_list)),
             delev_skip_subrecord: prepare_delev_skip_patterns(opt_or_set_vec_lowercase!(delev_skip_subrecord)),
             delev_no_skip_subrecord: prepare_delev_skip_patterns(opt_or_set_vec_lowercase!(delev_no_skip_subrecord)),
+            delev_rules: get_delev_rules(&opt_or_set_some!(delev_rules, ""), delev_creatures_to.max(delev_items_to))
+                .with_context(|| "Failed to get delev rules")?,
+            relevel,
+            relevel_transform: get_relevel_transform(
+                relevel,
+                delev,
+                opt_or_set_some!(relevel_scale, 1.0),
+                opt_or_set_some!(relevel_add, 0),
+                opt_or_set_some!(relevel_min, 0),
+                opt_or_set_some!(relevel_max, 0),
+            )?,
             no_compare: opt_or_set_bool!(no_compare),
             compare_only,
             compare_only_name,
             compare_with: opt_or_set_some!(compare_with, ""),
             compare_delev_with: opt_or_set_some!(compare_delev_with, ""),
             compare_common: opt_or_set_bool!(compare_common),
+            simulate: opt_or_set_some!(simulate, 0),
+            graph_dot: opt_or_set_some!(graph_dot, ""),
+            graph_json: opt_or_set_some!(graph_json, ""),
+            explain: opt_or_set_some!(explain, "").to_lowercase(),
             multipatch: Multipatch::new(
                 opt_or_set_bool!(no_multipatch),
                 opt_or_set_bool!(cellnames),
@@ -535,6 +662,7 @@ impl Cfg {
                 opt_or_set_bool!(summons),
                 opt_or_set_bool!(primitive),
             ),
+            groundcover: opt_or_set_bool!(groundcover),
             //opt_or_set_bool!(no_merge),
             merge: Merge::new(
                 true,
@@ -544,6 +672,7 @@ impl Cfg {
                 opt_or_set_bool!(plus_before_minus),
                 opt_or_set_bool!(verbose_atmosphere_data),
                 opt_or_set_threshold!(destination_similarity, f32, 1024, 8192),
+                opt_or_set_threshold!(pathgrid_similarity, f32, 64, 8192),
                 opt_or_set_vec_lowercase!(merge_types, MERGE_TYPES),
                 opt_or_set_vec_lowercase!(merge_skip_types),
             ),
@@ -568,7 +697,27 @@ impl Cfg {
                 mor_data_files_dir: set.guts.mor_data_files_dir,
                 omw_line_beginning_content: set.guts.omw_line_beginning_content,
                 omw_line_beginning_data: set.guts.omw_line_beginning_data,
+                omw_line_beginning_data_local: set.guts.omw_line_beginning_data_local,
+                omw_line_beginning_config: set.guts.omw_line_beginning_config,
+                omw_line_beginning_replace: set.guts.omw_line_beginning_replace,
+                omw_line_beginning_groundcover: set.guts.omw_line_beginning_groundcover,
+                omw_config_file_name: set.guts.omw_config_file_name,
+                omw_global_dir_linux: set.guts.omw_global_dir_linux,
+                omw_global_dir_macos: set.guts.omw_global_dir_macos,
+                omw_global_dir_windows: set.guts.omw_global_dir_windows,
+                omw_userdata_suffix_linux_macos: set.guts.omw_userdata_suffix_linux_macos,
+                omw_userdata_suffix_windows: set.guts.omw_userdata_suffix_windows,
+                omw_userconfig_suffix_linux_macos: set.guts.omw_userconfig_suffix_linux_macos,
+                omw_userconfig_suffix_windows: set.guts.omw_userconfig_suffix_windows,
                 omw_plugin_extensions: set_ext!(set.guts.omw_plugin_extensions),
+                mo2_instance_dir: set.guts.mo2_instance_dir,
+                mo2_ini: set.guts.mo2_ini,
+                mo2_line_beginning_game_path: set.guts.mo2_line_beginning_game_path,
+                mo2_mods_dir: set.guts.mo2_mods_dir,
+                mo2_overwrite_dir: set.guts.mo2_overwrite_dir,
+                mo2_modlist: set.guts.mo2_modlist,
+                mo2_plugins: set.guts.mo2_plugins,
+                mo2_loadorder: set.guts.mo2_loadorder,
                 plugin_extensions_to_ignore: prepare_plugin_extensions_to_ignore(set.guts.plugin_extensions_to_ignore),
                 skip_default_reasons: if no_skip_default {
                     Vec::new()
@@ -580,10 +729,12 @@ impl Cfg {
                 omw_cs_data_path_suffix_windows: set.guts.omw_cs_data_path_suffix_windows,
                 omw_cs_data_paths_list: set.guts.omw_cs_data_paths_list,
                 no_skip_unknown_cell_flags: set.guts.no_skip_unknown_cell_flags,
+                read_batch_size: set.guts.read_batch_size.max(1),
                 header_version: set.guts.header_version,
                 header_author: set.guts.header_author,
                 header_description_merge: set.guts.header_description_merge,
                 header_description_delev: set.guts.header_description_delev,
+                header_description_relevel: set.guts.header_description_relevel,
                 header_description_merge_and_delev: set.guts.header_description_merge_and_delev,
                 log_backup_suffix: set.guts.log_backup_suffix,
                 progress_frequency: get_progress_frequency(set.guts.progress_frequency)?,
@@ -596,16 +747,23 @@ impl Cfg {
                 multipatch_fogbug_fixed_value: get_fogbug_fixed_value(set.guts.multipatch_fogbug_fixed_value)?,
                 auto_resolve_lower_limit: set.guts.auto_resolve_lower_limit,
                 verboseness_details_deleted_subrecords: get_verbose!(set.guts.verboseness_details_deleted_subrecords),
+                verboseness_details_dangling_subrecords: get_verbose!(set.guts.verboseness_details_dangling_subrecords),
+                verboseness_details_nesting_problems: get_verbose!(set.guts.verboseness_details_nesting_problems),
+                verboseness_details_edited_subrecords: get_verbose!(set.guts.verboseness_details_edited_subrecords),
+                verboseness_details_merged_values: get_verbose!(set.guts.verboseness_details_merged_values),
                 verboseness_details_untouched_lists: get_verbose!(set.guts.verboseness_details_untouched_lists),
                 verboseness_details_threshold_resolved: get_verbose!(set.guts.verboseness_details_threshold_resolved),
                 verboseness_details_threshold_skipped: get_verbose!(set.guts.verboseness_details_threshold_skipped),
                 verboseness_details_threshold_warnings: get_verbose!(set.guts.verboseness_details_threshold_warnings),
                 verboseness_details_deleveled_subrecords: get_verbose!(set.guts.verboseness_details_deleveled_subrecords),
+                verboseness_details_releveled_subrecords: get_verbose!(set.guts.verboseness_details_releveled_subrecords),
                 verboseness_details_merge_record_merged: get_verbose!(set.guts.verboseness_details_merge_record_merged),
                 verboseness_details_merge_record_multipatched: get_verbose!(set.guts.verboseness_details_merge_record_multipatched),
                 verboseness_details_merge_field_changed: get_verbose!(set.guts.verboseness_details_merge_field_changed),
                 verboseness_details_merge_warnings: get_verbose!(set.guts.verboseness_details_merge_warnings),
                 verboseness_details_compare_plugins: get_verbose!(set.guts.verboseness_details_compare_plugins),
+                verboseness_details_script_conflicts: get_verbose!(set.guts.verboseness_details_script_conflicts),
+                verboseness_details_simulation: get_verbose!(set.guts.verboseness_details_simulation),
                 verboseness_show_configuration: get_verbose!(set.guts.verboseness_show_configuration),
                 debug_level_merge_list_all_plugins: get_verbose!(set.guts.debug_level_merge_list_all_plugins),
                 debug_level_merge_compare_to_the_last: get_verbose!(set.guts.debug_level_merge_compare_to_the_last),
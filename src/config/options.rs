@@ -29,6 +29,59 @@ pub(super) struct Options {
         help = "Path to the game configuration file"
     )]
     pub(super) config: Option<String>,
+    /// Path to the OpenMW installation directory, i.e. the directory with the OpenMW executable, e.g.: "C:\Games\OpenMW". It's what the "?local?" token of openmw.cfg stands for.
+    ///
+    /// Lines of openmw.cfg that start with "?local?" fail to resolve unless this option is set.
+    ///
+    /// Default value: "".
+    #[arg(
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["omw_local_dir", "openmw-dir", "openmw_dir"],
+        value_name = "PATH",
+        value_hint = clap::ValueHint::DirPath,
+        help = "Path to the OpenMW installation directory"
+    )]
+    pub(super) omw_local_dir: Option<String>,
+    /// Path to the Mod Organizer 2 profile directory, e.g.: "C:\Games\MO2\profiles\Default". Plugins are gathered from the profile instead of the game configuration file.
+    ///
+    /// Enabled mods are read from "modlist.txt" of the profile in priority order. Plugins are searched for in the game's "Data Files" directory, then in enabled mod directories, then in the "overwrite" directory. Later directories take precedence as in MO2's virtual file system. Plugins are ordered as in "loadorder.txt" of the profile, or "plugins.txt" if the former is absent. Only plugins enabled in "plugins.txt" are processed.
+    ///
+    /// Game directory is read from "ModOrganizer.ini" of the MO2 instance. Names and locations of MO2 files and directories are defined in settings guts.
+    #[arg(
+        conflicts_with_all = ["settings_write", "config"],
+        long,
+        aliases = ["mo2_profile", "mo2"],
+        value_name = "PATH",
+        value_hint = clap::ValueHint::DirPath,
+        help = "Path to the Mod Organizer 2 profile directory"
+    )]
+    pub(super) mo2_profile: Option<String>,
+    /// Paths to plugins to process instead of gathering them from the game configuration file, e.g.: "Morrowind.esm,Tribunal.esm,mods/Patch.esp". Plugins are processed in the order provided. Relative paths are relative to the current directory.
+    ///
+    /// May take either one or multiple comma-separated plugin paths. Filters(--skip, --skip-last and default skipped plugins) are still applied.
+    #[arg(
+        conflicts_with_all = ["settings_write", "config", "mo2_profile", "load_order_file"],
+        long,
+        value_name = "PATH(S)",
+        value_hint = clap::ValueHint::FilePath,
+        use_value_delimiter = true,
+        value_delimiter = ',',
+        help = "Paths to plugins to process in load order"
+    )]
+    pub(super) plugins: Option<Vec<String>>,
+    /// Path to the load order file to process plugins from instead of the game configuration file. Use "-" to read it from the standard input.
+    ///
+    /// Load order file contains one plugin path per line. Lines starting with "#" and empty lines are ignored. Relative paths are relative to the directory of the load order file(or current directory for the standard input). Filters(--skip, --skip-last and default skipped plugins) are still applied.
+    #[arg(
+        conflicts_with_all = ["settings_write", "config", "mo2_profile"],
+        long,
+        aliases = ["load_order_file", "load-order", "load_order"],
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        help = "Path to the load order file with plugin paths"
+    )]
+    pub(super) load_order_file: Option<String>,
     /// Name of the output plugin. May be provided as a path, e.g.: "C:\Morrowind\mods\LeveledLists.esp"(absolute), "mods/LeveledLists.esp"(relative). Non-existent directories will be created.
     ///
     /// Use --date to add date to the output plugin name, e.g. "MergedLeveledLists - YYYY-mm-dd.esp".
@@ -355,6 +408,99 @@ pub(super) struct Options {
         help = "Do not show threshold warnings"
     )]
     pub(super) no_threshold_warnings: bool,
+    /// Strip dangling subrecords from leveled lists.
+    ///
+    /// Subrecords that point to creatures or items not defined in any of the plugins are always reported. This option removes them from the lists, so the lists are placed into the output plugin even if they were not merged. Beware that records defined only in plugins excluded with --skip or --skip-last are unknown to the program.
+    #[arg(
+        help_heading = "Subrecord deletion",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["strip_dangling", "dangling-strip", "dangling_strip"],
+        help = "Strip dangling subrecords from leveled lists"
+    )]
+    pub(super) strip_dangling: bool,
+    /// Remove these subrecords from all leveled lists.
+    ///
+    /// Useful to get rid of a broken creature or item everywhere at once. Leveled lists that contained any of these subrecords are placed into the output plugin even if they were not merged. May take either one or multiple comma-separated ids, e.g. --ll-remove "broken_sword","bad_rat".
+    #[arg(
+        help_heading = "Subrecord deletion",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["ll_remove", "remove-subrecord", "remove_subrecord"],
+        value_name = "ID(S)",
+        use_value_delimiter = true,
+        value_delimiter = ',',
+        help = "Remove these subrecords from all leveled lists"
+    )]
+    pub(super) ll_remove: Option<Vec<String>>,
+    /// Replace subrecords in all leveled lists.
+    ///
+    /// Useful to swap creature or item for another one everywhere at once, e.g. for replacer mods. Level of subrecord is kept as is. Leveled lists that contained any of the replaced subrecords are placed into the output plugin even if they were not merged. May take either one or multiple comma-separated pairs of ids, e.g. --ll-replace "rat=rat_diseased","iron dagger=steel dagger".
+    ///
+    /// Removal with --ll-remove is done before replacement.
+    #[arg(
+        help_heading = "Subrecord deletion",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["ll_replace", "replace-subrecord", "replace_subrecord"],
+        value_name = "OLD=NEW",
+        use_value_delimiter = true,
+        value_delimiter = ',',
+        help = "Replace subrecords in all leveled lists"
+    )]
+    pub(super) ll_replace: Option<Vec<String>>,
+    /// Maximum depth of nested leveled lists.
+    ///
+    /// Leveled lists that contain themselves directly or through other lists are always reported. Chains of nested leveled lists deeper than this value are reported too, e.g. list that contains only creatures or items has depth 1, list that contains such list has depth 2 etc. Exit code 2 is returned when any of them is found.
+    ///
+    /// Default value: 5. Value of 0 disables depth check.
+    #[arg(
+        help_heading = "Leveled list checks",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["max_nesting_depth", "nesting-depth", "nesting_depth"],
+        help = "Report chains of nested leveled lists deeper than this",
+        value_name = "5"
+    )]
+    pub(super) max_nesting_depth: Option<u16>,
+    /// How to choose chance_none of merged leveled list when plugins disagree.
+    ///
+    /// Strategies:
+    ///   novel - the most recent value that no previous plugin had, e.g. 30 for values 50, 30, 50
+    ///   last - value from the last plugin
+    ///   first - value from the first plugin
+    ///   min - the lowest value
+    ///   max - the highest value
+    ///   changed - the most recent value that differs from the first plugin's value, so that plugin restating the original value doesn't revert changes made by previous plugins
+    ///
+    /// Chosen values are logged for every leveled list with disagreeing plugins.
+    ///
+    /// Default value: novel.
+    #[arg(
+        help_heading = "List merging",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["chance_none_strategy", "chance-none", "chance_none"],
+        help = "How to choose chance_none when plugins disagree",
+        value_name = "novel",
+        value_parser = ["novel", "last", "first", "min", "max", "changed"]
+    )]
+    pub(super) chance_none_strategy: Option<String>,
+    /// How to choose list flags of merged leveled list when plugins disagree.
+    ///
+    /// Strategies are the same as for --chance-none-strategy. The only difference is that min keeps only flags set by all plugins, while max keeps flags set by any plugin.
+    ///
+    /// Default value: novel.
+    #[arg(
+        help_heading = "List merging",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["list_flags_strategy", "list-flags", "list_flags"],
+        help = "How to choose list flags when plugins disagree",
+        value_name = "novel",
+        value_parser = ["novel", "last", "first", "min", "max", "changed"]
+    )]
+    pub(super) list_flags_strategy: Option<String>,
     /// Delevel subrecords mode.
     ///
     /// By default it delevels everything to level 1, deleveled lists are placed into the output plugin. Use --delev-to to set different level to delevel to. Use --delev-distinct to place deleveled lists into different output plugin.
@@ -695,6 +841,143 @@ pub(super) struct Options {
         verbatim_doc_comment
     )]
     pub(super) delev_no_skip_subrecord: Option<Vec<String>>,
+    /// Delevel by rules from the file.
+    ///
+    /// Rules file is a TOML file with a list of [[rule]] tables. Every rule may have following keys:
+    ///     name = "vampires"               Name to show in log, rule number is used if omitted
+    ///     list = ["infix:vampire"]        Leveled lists to apply the rule to, all lists if omitted
+    ///     subrecord = ["prefix:daedric"]  Subrecords to apply the rule to, all subrecords if omitted
+    ///     to = 5                          Level to delevel to
+    ///     scale = 0.5                     Multiply level by this value(rounded up)
+    ///     segment = 20                    Same as --delev-segment, but for this rule only
+    ///     min = 5                         Never delevel below this level
+    ///
+    /// Patterns work the same as in --delev-skip-list. Only one of "to", "scale" and "segment" may be set, usual delev rules are used if none of them is set. Rules are checked in order, first rule that matches both leveled list and subrecord is used. Subrecords are never raised to a higher level. Examples:
+    ///
+    ///     [[rule]]
+    ///     name = "vampire lists: level/2, minimum 5"
+    ///     list = ["infix:vampire"]
+    ///     scale = 0.5
+    ///     min = 5
+    ///
+    ///     [[rule]]
+    ///     name = "daedric items: never below 20"
+    ///     subrecord = ["prefix:daedric"]
+    ///     min = 20
+    ///
+    /// Requires --delev.
+    #[arg(
+        help_heading = "Delev filters",
+        requires = "delev",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["delev_rules", "delevel-rules", "delevel_rules", "delev-rules-file", "delev_rules_file"],
+        help = "Delevel by rules from the file",
+        value_name = "PATH",
+        verbatim_doc_comment
+    )]
+    pub(super) delev_rules: Option<String>,
+    /// Relevel subrecords mode.
+    ///
+    /// The opposite of --delev. Levels of subrecords are transformed with --relevel-scale, --relevel-add, --relevel-min and --relevel-max in that order, i.e. level is multiplied first(rounded), then offset is added, then the result is clamped. Examples:
+    ///     --relevel-scale 1.5 --relevel-max 100    Multiply every level by 1.5, but do not raise higher than 100
+    ///     --relevel-min 5                          Move every subrecord below level 5 to level 5
+    ///
+    /// Releveled lists are always placed into the additional output plugin. It has the same name as the output plugin with added infix " - Relevel" by default, e.g. "MergedLeveledLists - Relevel.esp". Use --relevel-output to set custom name.
+    ///
+    /// This option is required by all other --relevel-* options. Conflicts with --delev, --compare-only.
+    #[arg(
+        help_heading = "Relevel",
+        conflicts_with_all = ["settings_write", "delev", "compare_only"],
+        long,
+        alias = "relevel-mode",
+        help = "Relevel subrecords mode",
+        verbatim_doc_comment
+    )]
+    pub(super) relevel: bool,
+    /// Name of the relevel output plugin.
+    ///
+    /// Same as --output option, see --output for details. The only difference is that the default value is empty, so the file has the same name as the output plugin with added infix " - Relevel", e.g. "MergedLeveledLists - Relevel.esp".
+    ///
+    /// Default value: "".
+    ///
+    /// Requires --relevel.
+    #[arg(
+        help_heading = "Relevel",
+        requires = "relevel",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["relevel_output", "relevel-plugin", "relevel_plugin"],
+        value_name = "PATH",
+        value_hint = clap::ValueHint::Other,
+        help = "Name of the relevel output plugin"
+    )]
+    pub(super) relevel_output: Option<String>,
+    /// Multiply subrecords level by this value.
+    ///
+    /// Result is rounded to the nearest level.
+    ///
+    /// Default value: 1.0.
+    ///
+    /// Requires --relevel.
+    #[arg(
+        help_heading = "Relevel",
+        requires = "relevel",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["relevel_scale", "relevel-multiply", "relevel_multiply"],
+        help = "Multiply subrecords level by this value",
+        value_name = "1.0"
+    )]
+    pub(super) relevel_scale: Option<f64>,
+    /// Add this value to subrecords level.
+    ///
+    /// Negative values are allowed. It's applied after --relevel-scale.
+    ///
+    /// Default value: 0.
+    ///
+    /// Requires --relevel.
+    #[arg(
+        help_heading = "Relevel",
+        requires = "relevel",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["relevel_add", "relevel-offset", "relevel_offset"],
+        help = "Add this value to subrecords level",
+        value_name = "0",
+        allow_negative_numbers = true
+    )]
+    pub(super) relevel_add: Option<i32>,
+    /// Raise subrecords below this level to this level.
+    ///
+    /// Default value: 0(disabled).
+    ///
+    /// Requires --relevel.
+    #[arg(
+        help_heading = "Relevel",
+        requires = "relevel",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["relevel_min", "relevel-floor", "relevel_floor"],
+        help = "Raise subrecords below this level to this level",
+        value_name = "0"
+    )]
+    pub(super) relevel_min: Option<u16>,
+    /// Do not raise subrecords higher than this level.
+    ///
+    /// Subrecords that are already higher are lowered to this level. Default value: 0(disabled).
+    ///
+    /// Requires --relevel.
+    #[arg(
+        help_heading = "Relevel",
+        requires = "relevel",
+        conflicts_with = "settings_write",
+        long,
+        aliases = ["relevel_max", "relevel-cap", "relevel_cap"],
+        help = "Do not raise subrecords higher than this level",
+        value_name = "0"
+    )]
+    pub(super) relevel_max: Option<u16>,
     /// Do not compare plugins.
     ///
     /// By default output plugin is compared with previous version if there is one(same filename). It's not written if previous version is the same.
@@ -763,6 +1046,64 @@ pub(super) struct Options {
         aliases = ["compare_common", "common-compare", "common_compare"],
         help = "Compare common records only")]
     pub(super) compare_common: bool,
+    /// Simulate spawn probabilities of leveled lists at player level.
+    ///
+    /// Nested lists are expanded recursively to get exact chance of every creature or item to spawn with a single roll of the list. Chance none, "Calculate from all levels <= PC's level" and "Calculate for each item in count" flags are taken into account. The latter doesn't change the chance itself, though every item of the stack gets its own roll only with this flag. Results are shown for all leveled lists, or only for lists with changed probabilities in --delev mode to compare them before and after delev.
+    ///
+    /// Use with --dry-run to get results without writing the output plugin.
+    #[arg(
+        help_heading = "Compare",
+        conflicts_with_all = ["settings_write", "compare_only"],
+        long,
+        visible_alias = "sim",
+        aliases = ["simulate-level", "simulate_level", "simulation"],
+        help = "Simulate spawn probabilities of leveled lists at player level",
+        value_name = "LVL",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub(super) simulate: Option<u16>,
+    /// Export merged leveled lists hierarchy to Graphviz DOT file.
+    ///
+    /// Leveled lists and leaf creatures or items are nodes, subrecords are edges labeled with levels. Each leveled list is annotated with plugins that defined or modified it. Render it with e.g. "dot -Tsvg lists.dot -o lists.svg".
+    ///
+    /// File is written even with --dry-run.
+    #[arg(
+        help_heading = "Compare",
+        conflicts_with_all = ["settings_write", "compare_only"],
+        long,
+        aliases = ["graph_dot", "dot"],
+        help = "Export leveled lists graph to Graphviz DOT file",
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    pub(super) graph_dot: Option<String>,
+    /// Export merged leveled lists hierarchy to JSON file.
+    ///
+    /// Document contains array of nodes. Leveled list nodes contain plugins that defined or modified it and subrecords with levels and counts, i.e. adjacency list of the graph. Leaf nodes are creatures or items.
+    ///
+    /// File is written even with --dry-run.
+    #[arg(
+        help_heading = "Compare",
+        conflicts_with_all = ["settings_write", "compare_only"],
+        long,
+        aliases = ["graph_json", "json"],
+        help = "Export leveled lists graph to JSON file",
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    pub(super) graph_json: Option<String>,
+    /// Explain how the leveled list was merged.
+    ///
+    /// Prints version of the leveled list from every plugin in load order with subrecords added or removed by each of them. Then shows which deletion rule applied(--always-delete, --never-delete, thresholds), subrecords changed by deletion, --ll-remove, --ll-replace, delev or relevel and the final record with the reason it was or was not placed into the output plugin.
+    #[arg(
+        help_heading = "Compare",
+        conflicts_with_all = ["settings_write", "compare_only"],
+        long,
+        aliases = ["why"],
+        help = "Explain how the leveled list was merged",
+        value_name = "LIST_ID"
+    )]
+    pub(super) explain: Option<String>,
     /// Do not make multipatch.
     ///
     /// Multipatch is enabled by default. It consists of --cellnames, --fogbug and --summons.
@@ -836,6 +1177,16 @@ pub(super) struct Options {
         verbatim_doc_comment
     )]
     pub(super) summons: bool,
+    /// Read OpenMW groundcover plugins for cell multipatch.
+    ///
+    /// Groundcover plugins are listed with "groundcover=" lines of openmw.cfg. They are never used as a source of leveled lists and are not added as masters. By default they are not read at all. With this option their cells are used by --cellnames and --fogbug, while all other records and cell references are ignored. Groundcover cells are kept apart from other cells and are never merged, so they are not read when CELL records are merged.
+    #[arg(
+        help_heading = "Multipatch",
+        conflicts_with_all = ["settings_write", "no_multipatch"],
+        long,
+        help = "Read groundcover plugins for cell multipatch"
+    )]
+    pub(super) groundcover: bool,
     /// TODO
     #[arg(
         hide = true,
@@ -855,7 +1206,9 @@ pub(super) struct Options {
         help = "TODO"
     )]
     pub(super) no_merge: bool,
-    /// TODO
+    /// Record types to merge.
+    ///
+    /// REGN and DIAL are supported, but not merged by default to keep output unchanged. Add them to the list to merge them.
     ///
     /// Conflicts with --no-merge.
     #[arg(
@@ -866,10 +1219,10 @@ pub(super) struct Options {
         long,
         visible_alias = "merge",
         aliases = ["merge_types", "merge-type", "merge_type", "types-merge", "types_merge", "type-merge", "type_merge", "types", "type", "merge"],
-        value_name = "TODO",
+        value_name = "TYPE(S)",
         num_args = 1..,
         value_delimiter = ' ',
-        help = "TODO"
+        help = "Record types to merge, REGN and DIAL are not merged by default"
     )]
     pub(super) merge_types: Option<Vec<String>>,
     /// TODO
@@ -999,6 +1352,26 @@ pub(super) struct Options {
         value_parser = clap::value_parser!(u32).range(0..8192)
     )]
     pub(super) destination_similarity: Option<u32>,
+    /// Set path grid point similarity threshold.
+    ///
+    /// Path grid points of different plugins are assumed to be the same point when every coordinate differs by no more than this value. Edges of such points are joined in the merged path grid.
+    ///
+    /// Example:
+    ///   PGRD point(plugin 1): (1024,512,0)
+    ///   PGRD point(plugin 2): (1040,500,0)
+    ///
+    ///   By default merged path grid would have a single point. With this option set to 0 there would be both points.
+    #[arg(
+        help_heading = "Multipatch",
+        conflicts_with = "settings_write",
+        long,
+        visible_alias = "ps",
+        aliases = ["pathgrid_similarity", "similarity-pathgrid", "similarity_pathgrid", "pathgrid-similar", "pathgrid_similar", "similar-pathgrid", "similar_pathgrid"],
+        help = "Set path grid point similarity threshold",
+        value_name = "64",
+        value_parser = clap::value_parser!(u32).range(0..8192)
+    )]
+    pub(super) pathgrid_similarity: Option<u32>,
     /// Show more information. May be provided multiple times for extra effect.
     ///
     /// Conflicts with --quiet.
@@ -16,6 +16,14 @@ pub(super) struct Settings {
 pub(super) struct Options {
     #[config(default = "")]
     pub(super) config: String,
+    #[config(default = "")]
+    pub(super) omw_local_dir: String,
+    #[config(default = "")]
+    pub(super) mo2_profile: String,
+    #[config(default = [])]
+    pub(super) plugins: Vec<String>,
+    #[config(default = "")]
+    pub(super) load_order_file: String,
     #[config(default = "MergedLeveledLists.esp")]
     pub(super) output: String,
     #[config(default = "")]
@@ -66,6 +74,25 @@ pub(super) struct Options {
     pub(super) threshold_items: u64,
     #[config(default = false)]
     pub(super) no_threshold_warnings: bool,
+    #[config(default = false)]
+    pub(super) strip_dangling: bool,
+    #[config(default = [])]
+    pub(super) ll_remove: Vec<String>,
+    /// Each replacement is a string "OLD=NEW", e.g. ["rat=rat_diseased"].
+    #[config(default = [])]
+    pub(super) ll_replace: Vec<String>,
+    ///
+    /// [Leveled list checks]
+    /// Depth check is disabled with 0 value.
+    #[config(default = 5)]
+    pub(super) max_nesting_depth: u16,
+    ///
+    /// [List merging]
+    /// Strategies: "novel", "last", "first", "min", "max", "changed".
+    #[config(default = "novel")]
+    pub(super) chance_none_strategy: String,
+    #[config(default = "novel")]
+    pub(super) list_flags_strategy: String,
     ///
     /// [Delev]
     #[config(default = false)]
@@ -109,6 +136,25 @@ pub(super) struct Options {
     pub(super) delev_skip_subrecord: Vec<String>,
     #[config(default = [])]
     pub(super) delev_no_skip_subrecord: Vec<String>,
+    /// Path to delev rules file. Rules are disabled with empty value by default.
+    #[config(default = "")]
+    pub(super) delev_rules: String,
+    ///
+    /// [Relevel]
+    #[config(default = false)]
+    pub(super) relevel: bool,
+    /// By default the value is empty so that "guts.relevel_output_infix_default" is added to the output plugin name.
+    #[config(default = "")]
+    pub(super) relevel_output: String,
+    #[config(default = 1.0)]
+    pub(super) relevel_scale: f64,
+    #[config(default = 0)]
+    pub(super) relevel_add: i32,
+    /// Following 2 sections are effectively disabled with 0 values by default.
+    #[config(default = 0)]
+    pub(super) relevel_min: u16,
+    #[config(default = 0)]
+    pub(super) relevel_max: u16,
     ///
     /// [Compare]
     #[config(default = false)]
@@ -119,6 +165,16 @@ pub(super) struct Options {
     pub(super) compare_delev_with: String,
     #[config(default = false)]
     pub(super) compare_common: bool,
+    /// Simulation is disabled with 0 value by default.
+    #[config(default = 0)]
+    pub(super) simulate: u16,
+    /// Graph export is disabled with empty values by default.
+    #[config(default = "")]
+    pub(super) graph_dot: String,
+    #[config(default = "")]
+    pub(super) graph_json: String,
+    #[config(default = "")]
+    pub(super) explain: String,
     ///
     /// [Multipatch]
     #[config(default = false)]
@@ -130,12 +186,14 @@ pub(super) struct Options {
     #[config(default = false)]
     pub(super) summons: bool,
     #[config(default = false)]
+    pub(super) groundcover: bool,
+    #[config(default = false)]
     pub(super) primitive: bool,
     ///
     /// [Merge]
     // #[config(default = false)]
     // pub(super) no_merge: bool,
-    #[config(default = ["GMST","CLAS","RACE","SOUN","SKIL","MGEF","BSGN","SPEL","STAT","DOOR","MISC","WEAP","CONT","CREA","BODY","LIGH","ENCH","NPC_","ARMO","CLOT","REPA","ACTI","APPA","LOCK","PROB","INGR","BOOK","ALCH","CELL","SNDG"])]
+    #[config(default = ["GMST","CLAS","FACT","RACE","SOUN","SKIL","MGEF","BSGN","LTEX","SPEL","STAT","DOOR","MISC","WEAP","CONT","CREA","BODY","LIGH","ENCH","NPC_","ARMO","CLOT","REPA","ACTI","APPA","LOCK","PROB","INGR","BOOK","ALCH","CELL","LAND","PGRD","SNDG","SCPT"])]
     pub(super) merge_types: Vec<String>,
     #[config(default = [])]
     pub(super) merge_skip_types: Vec<String>,
@@ -151,6 +209,8 @@ pub(super) struct Options {
     pub(super) plus_before_minus: bool,
     #[config(default = 1024)]
     pub(super) destination_similarity: u32,
+    #[config(default = 64)]
+    pub(super) pathgrid_similarity: u32,
     ///
     /// [Display output]
     #[config(default = 0)]
@@ -221,8 +281,60 @@ pub(super) struct Guts {
     pub(super) omw_line_beginning_content: String,
     #[config(default = "data=")]
     pub(super) omw_line_beginning_data: String,
+    #[config(default = "data-local=")]
+    pub(super) omw_line_beginning_data_local: String,
+    #[config(default = "config=")]
+    pub(super) omw_line_beginning_config: String,
+    #[config(default = "replace=")]
+    pub(super) omw_line_beginning_replace: String,
+    #[config(default = "groundcover=")]
+    pub(super) omw_line_beginning_groundcover: String,
+    /// Name of the file that is loaded from directories provided with "config=" lines of openmw.cfg.
+    #[config(default = "openmw.cfg")]
+    pub(super) omw_config_file_name: String,
+    /// Directories that "?global?", "?userdata?" and "?userconfig?" tokens of openmw.cfg are replaced with. "?local?" is set with --omw-local-dir.
+    ///
+    /// Global directory on Linux, macOS and Windows respectively. Empty value means there is no global directory on the platform.
+    #[config(default = "/etc/openmw")]
+    pub(super) omw_global_dir_linux: String,
+    #[config(default = "/Library/Preferences/openmw")]
+    pub(super) omw_global_dir_macos: String,
+    #[config(default = "")]
+    pub(super) omw_global_dir_windows: String,
+    ///
+    /// Path that is appended to the "data_dir": "$HOME/.local/share|$HOME/Library/Application Support" + omw_userdata_suffix_linux_macos
+    #[config(default = "openmw")]
+    pub(super) omw_userdata_suffix_linux_macos: String,
+    /// Path that is appended to the "document_dir": "C:\Users\Username\Documents" + omw_userdata_suffix_windows
+    #[config(default = "My Games/OpenMW")]
+    pub(super) omw_userdata_suffix_windows: String,
+    /// Path that is appended to the "preference_dir": "$HOME/.config|$HOME/Library/Preferences" + omw_userconfig_suffix_linux_macos
+    #[config(default = "openmw")]
+    pub(super) omw_userconfig_suffix_linux_macos: String,
+    /// Path that is appended to the "document_dir": "C:\Users\Username\Documents" + omw_userconfig_suffix_windows
+    #[config(default = "My Games/OpenMW")]
+    pub(super) omw_userconfig_suffix_windows: String,
     #[config(default = ["esm", "esp", "omwaddon", "omwscripts"])]
     pub(super) omw_plugin_extensions: Vec<String>,
+    ///
+    /// [Mod Organizer 2 profile processing]
+    /// These are used with --mo2-profile. Instance directory is relative to the profile directory. Other directories and files are relative to the instance directory.
+    #[config(default = "../..")]
+    pub(super) mo2_instance_dir: String,
+    #[config(default = "ModOrganizer.ini")]
+    pub(super) mo2_ini: String,
+    #[config(default = "gamePath=")]
+    pub(super) mo2_line_beginning_game_path: String,
+    #[config(default = "mods")]
+    pub(super) mo2_mods_dir: String,
+    #[config(default = "overwrite")]
+    pub(super) mo2_overwrite_dir: String,
+    #[config(default = "modlist.txt")]
+    pub(super) mo2_modlist: String,
+    #[config(default = "plugins.txt")]
+    pub(super) mo2_plugins: String,
+    #[config(default = "loadorder.txt")]
+    pub(super) mo2_loadorder: String,
     /// Plugins with following extensions will not be processed. It's made to ignore .omwscripts, though may be used for anything else.
     #[config(default = ["omwscripts"])]
     pub(super) plugin_extensions_to_ignore: Vec<String>,
@@ -255,6 +367,9 @@ pub(super) struct Guts {
     /// Some cells contain undocumented and unknown 0x40 flag. There is no evident purpose of this flag. It looks like something half-baked by Bethesda and forgotten in CS. This flag is filtered out from cell flags after reading cells.
     #[config(default = false)]
     pub(super) no_skip_unknown_cell_flags: bool,
+    /// Number of plugins that are parsed in parallel at once. Next batch is parsed while records of the previous one are processed in load order. Larger values may be faster, though use more memory.
+    #[config(default = 32)]
+    pub(super) read_batch_size: usize,
     ///
     /// [Date]
     ///
@@ -273,6 +388,9 @@ pub(super) struct Guts {
     /// Delev output plugin default infix.
     #[config(default = "Delev")]
     pub(super) delev_output_infix_default: String,
+    /// Relevel output plugin default infix.
+    #[config(default = "Relevel")]
+    pub(super) relevel_output_infix_default: String,
     ///
     /// [Header]
     /// Output plugin will have these values placed into header.
@@ -284,6 +402,8 @@ pub(super) struct Guts {
     pub(super) header_description_merge: String,
     #[config(default = "Auto-generated deleveled leveled lists")]
     pub(super) header_description_delev: String,
+    #[config(default = "Auto-generated releveled leveled lists")]
+    pub(super) header_description_relevel: String,
     #[config(default = "Auto-generated merged and deleveled leveled lists")]
     pub(super) header_description_merge_and_delev: String,
     ///
@@ -327,6 +447,14 @@ pub(super) struct Guts {
     /// When log messages are displayed.
     #[config(default = 2)]
     pub(super) verboseness_details_deleted_subrecords: u8,
+    #[config(default = 1)]
+    pub(super) verboseness_details_dangling_subrecords: u8,
+    #[config(default = 0)]
+    pub(super) verboseness_details_nesting_problems: u8,
+    #[config(default = 1)]
+    pub(super) verboseness_details_edited_subrecords: u8,
+    #[config(default = 1)]
+    pub(super) verboseness_details_merged_values: u8,
     #[config(default = 2)]
     pub(super) verboseness_details_untouched_lists: u8,
     #[config(default = 1)]
@@ -337,6 +465,8 @@ pub(super) struct Guts {
     pub(super) verboseness_details_threshold_warnings: u8,
     #[config(default = 3)]
     pub(super) verboseness_details_deleveled_subrecords: u8,
+    #[config(default = 3)]
+    pub(super) verboseness_details_releveled_subrecords: u8,
     #[config(default = 1)]
     pub(super) verboseness_details_merge_warnings: u8,
     #[config(default = 1)]
@@ -347,6 +477,10 @@ pub(super) struct Guts {
     pub(super) verboseness_details_merge_field_changed: u8,
     #[config(default = 1)]
     pub(super) verboseness_details_compare_plugins: u8,
+    #[config(default = 1)]
+    pub(super) verboseness_details_script_conflicts: u8,
+    #[config(default = 1)]
+    pub(super) verboseness_details_simulation: u8,
     #[config(default = 4)]
     pub(super) verboseness_show_configuration: u8,
     ///
@@ -1,4 +1,6 @@
-use super::{DelevSkipPatterns, Options, OutputFile, PluginKind, Settings, SettingsFile, ShowConfiguration};
+use super::{
+    DelevSkipPatterns, MergeStrategy, Options, OutputFile, PluginKind, RelevelTransform, Settings, SettingsFile, ShowConfiguration,
+};
 use crate::{get_delev_segment_ceil, read_lines};
 use anyhow::{anyhow, Context, Result};
 use chrono::Local;
@@ -161,6 +163,7 @@ pub(super) fn get_output_file(
     let (opt_output, set_options_output, option_name) = match kind {
         PluginKind::Merge => (&opt.output, &set.options.output, "output"),
         PluginKind::Delev => (&opt.delev_output, &set.options.delev_output, "delev_output"),
+        PluginKind::Relevel => (&opt.relevel_output, &set.options.relevel_output, "relevel_output"),
     };
     let mut raw_path = match opt_output {
         Some(name) => {
@@ -169,7 +172,7 @@ pub(super) fn get_output_file(
         }
         None => {
             if (matches!(kind, PluginKind::Merge) && set_options_output != "MergedLeveledLists.esp")
-                || (matches!(kind, PluginKind::Delev) && !set_options_output.is_empty())
+                || (matches!(kind, PluginKind::Delev | PluginKind::Relevel) && !set_options_output.is_empty())
             {
                 show_configuration.add_some(false, option_name, format_args!("{:?}", &set_options_output))?;
             }
@@ -177,7 +180,7 @@ pub(super) fn get_output_file(
         }
     };
     let mut path = PathBuf::from(&raw_path);
-    if raw_path.is_empty() && matches!(kind, PluginKind::Delev) {
+    if raw_path.is_empty() && matches!(kind, PluginKind::Delev | PluginKind::Relevel) {
         raw_path = match &opt.output {
             Some(name) => name,
             None => &set.options.output,
@@ -187,10 +190,11 @@ pub(super) fn get_output_file(
             Some(stem) => stem.to_string_lossy(),
             None => name_parse_error!(raw_path, "file name without extension"),
         };
-        path.set_file_name(format!(
-            "{}{}{}",
-            stem, &set.guts.output_date_separators[0], &set.guts.delev_output_infix_default
-        ));
+        let infix = match kind {
+            PluginKind::Relevel => &set.guts.relevel_output_infix_default,
+            _ => &set.guts.delev_output_infix_default,
+        };
+        path.set_file_name(format!("{}{}{}", stem, &set.guts.output_date_separators[0], infix));
     };
     let dir_path = match &opt.output_dir {
         Some(path) => {
@@ -297,6 +301,54 @@ pub(super) fn get_kind_delev_segment(
     Ok((segment, ceil))
 }
 
+pub(super) fn get_relevel_transform(relevel: bool, delev: bool, scale: f64, add: i32, min: u16, max: u16) -> Result<RelevelTransform> {
+    if relevel {
+        // COMMENT: clap only rejects the combination on the command line, settings file may still enable both
+        if delev {
+            return Err(anyhow!("Relevel mode conflicts with delev mode, enable only one of them"));
+        }
+        if scale <= 0.0 {
+            return Err(anyhow!("Relevel scale({scale}) should be larger than 0"));
+        }
+        if scale == 1.0 && add == 0 && min == 0 && max == 0 {
+            return Err(anyhow!(
+                "Relevel mode requires at least one of --relevel-scale, --relevel-add, --relevel-min, --relevel-max"
+            ));
+        }
+        if max != 0 && min > max {
+            return Err(anyhow!("Relevel min({min}) should be lower or equal to relevel max({max})"));
+        }
+    }
+    Ok(RelevelTransform { scale, add, min, max })
+}
+
+pub(super) fn get_ll_replace(replacements: Vec<String>) -> Result<Vec<(String, String)>> {
+    let mut res = Vec::new();
+    for replacement in replacements {
+        match replacement.split_once('=') {
+            Some((old, new)) if !old.is_empty() && !new.is_empty() => {
+                res.push((old.to_owned(), new.to_owned()));
+            }
+            _ => return Err(anyhow!("Failed to parse replacement \"{replacement}\", it should look like \"OLD=NEW\"")),
+        }
+    }
+    Ok(res)
+}
+
+pub(super) fn get_merge_strategy(strategy: &str, name: &str) -> Result<MergeStrategy> {
+    match strategy.to_lowercase().as_str() {
+        "novel" => Ok(MergeStrategy::Novel),
+        "last" => Ok(MergeStrategy::Last),
+        "first" => Ok(MergeStrategy::First),
+        "min" => Ok(MergeStrategy::Min),
+        "max" => Ok(MergeStrategy::Max),
+        "changed" => Ok(MergeStrategy::Changed),
+        _ => Err(anyhow!(
+            "Value of {name} should be one of \"novel\", \"last\", \"first\", \"min\", \"max\", \"changed\", got \"{strategy}\""
+        )),
+    }
+}
+
 pub(super) fn get_fogbug_fixed_value(fog: f32) -> Result<f32> {
     if fog <= 0.0 {
         Err(anyhow!("Fog value to fix fogbug should be larger than 0"))
@@ -0,0 +1,136 @@
+use super::{finalize_plugins, get_all_plugins, get_listed_plugin, Helper, PluginInfo};
+use crate::{msg, read_lines, Cfg, Log, MsgTone};
+use anyhow::{anyhow, Context, Result};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) fn get_mo2_plugins(cfg: &Cfg, log: &mut Log) -> Result<Vec<PluginInfo>> {
+    let profile_dir = Path::new(&cfg.mo2_profile);
+    if !profile_dir.is_dir() {
+        return Err(anyhow!(
+            "Failed to find Mod Organizer 2 profile directory \"{}\"",
+            profile_dir.display()
+        ));
+    }
+    let text = format!("Gathering plugins from Mod Organizer 2 profile \"{}\"", profile_dir.display());
+    msg(text, MsgTone::Neutral, 1, cfg, log)?;
+    let instance_dir = profile_dir.join(&cfg.guts.mo2_instance_dir);
+    let mut data_dirs: Vec<PathBuf> = vec![get_game_data_dir(&instance_dir, cfg).with_context(|| "Failed to find game directory")?];
+    let modlist_path = profile_dir.join(&cfg.guts.mo2_modlist);
+    let modlist = read_text_file(&modlist_path).with_context(|| "Failed to read list of mods")?;
+    let mods_dir = instance_dir.join(&cfg.guts.mo2_mods_dir);
+    data_dirs.extend(parse_modlist(&modlist).iter().map(|mod_name| mods_dir.join(mod_name)));
+    let overwrite_dir = instance_dir.join(&cfg.guts.mo2_overwrite_dir);
+    if overwrite_dir.is_dir() {
+        data_dirs.push(overwrite_dir);
+    }
+    let text = format!(
+        "Mod Organizer 2 data directories in order of priority:\n{}",
+        data_dirs
+            .iter()
+            .map(|path| format!("\t{}", path.display()))
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    msg(text, MsgTone::Neutral, 2, cfg, log)?;
+    let all_plugins = get_all_plugins(&data_dirs.into_iter().enumerate().collect::<Vec<_>>(), cfg)
+        .with_context(|| "Failed to find all Mod Organizer 2 plugins")?;
+    let plugins_path = profile_dir.join(&cfg.guts.mo2_plugins);
+    let plugins = read_text_file(&plugins_path).with_context(|| "Failed to read list of enabled plugins")?;
+    let loadorder_path = profile_dir.join(&cfg.guts.mo2_loadorder);
+    let loadorder = if loadorder_path.exists() {
+        Some(read_text_file(&loadorder_path).with_context(|| "Failed to read load order")?)
+    } else {
+        let text = format!(
+            "Load order file \"{}\" not found, plugins will be ordered as in \"{}\"",
+            loadorder_path.display(),
+            plugins_path.display()
+        );
+        msg(text, MsgTone::Neutral, 1, cfg, log)?;
+        None
+    };
+    let mut helper: Helper = Helper::default();
+    let mut res: Vec<PluginInfo> = Vec::new();
+    for name in parse_load_order(&plugins, loadorder.as_deref()) {
+        get_listed_plugin(&name, &mut res, &all_plugins, false, &mut helper, cfg, log)
+            .with_context(|| "Failed to find Mod Organizer 2 plugin")?;
+    }
+    finalize_plugins(res, &helper, "Mod Organizer 2 profile", &plugins_path, cfg, log)
+}
+
+fn get_game_data_dir(instance_dir: &Path, cfg: &Cfg) -> Result<PathBuf> {
+    let ini_path = instance_dir.join(&cfg.guts.mo2_ini);
+    let ini = read_text_file(&ini_path)?;
+    match ini
+        .iter()
+        .find_map(|line| line.strip_prefix(&cfg.guts.mo2_line_beginning_game_path))
+    {
+        Some(raw_game_path) => Ok(parse_game_path(raw_game_path).join(&cfg.guts.mor_data_files_dir)),
+        None => Err(anyhow!(
+            "Failed to find line starting with \"{}\" in \"{}\"",
+            cfg.guts.mo2_line_beginning_game_path,
+            ini_path.display()
+        )),
+    }
+}
+
+fn read_text_file(path: &Path) -> Result<Vec<String>> {
+    Ok(read_lines(path)
+        .with_context(|| format!("Failed to read file \"{}\"", path.display()))?
+        .map_while(Result::ok)
+        .collect())
+}
+
+// COMMENT: "@ByteArray(C:\\Games\\Morrowind)" is how Qt stores paths in ModOrganizer.ini
+fn parse_game_path(raw_game_path: &str) -> PathBuf {
+    let raw_game_path = raw_game_path.trim();
+    let game_path = match raw_game_path.strip_prefix("@ByteArray(").and_then(|path| path.strip_suffix(')')) {
+        Some(path) => path.replace("\\\\", "\\"),
+        None => raw_game_path.to_owned(),
+    };
+    PathBuf::from(game_path)
+}
+
+// COMMENT: modlist.txt lists mods from highest to lowest priority: "+" is enabled, "-" is disabled, "*" is unmanaged(e.g. DLC)
+fn parse_modlist(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .rev()
+        .filter_map(|line| line.trim_end().strip_prefix('+'))
+        .map(str::to_owned)
+        .collect()
+}
+
+// COMMENT: plugins.txt either marks enabled plugins with "*" or lists enabled plugins only
+fn parse_load_order(plugins: &[String], loadorder: Option<&[String]>) -> Vec<String> {
+    let entries = |lines: &[String]| -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_owned)
+            .collect()
+    };
+    let plugins = entries(plugins);
+    let enabled: Vec<String> = if plugins.iter().any(|name| name.starts_with('*')) {
+        plugins.iter().filter_map(|name| name.strip_prefix('*')).map(str::to_owned).collect()
+    } else {
+        plugins
+    };
+    let Some(loadorder) = loadorder else {
+        return enabled;
+    };
+    let enabled_lowercased: HashSet<String> = enabled.iter().map(|name| name.to_lowercase()).collect();
+    let mut res: Vec<String> = entries(loadorder)
+        .into_iter()
+        .filter(|name| enabled_lowercased.contains(&name.to_lowercase()))
+        .collect();
+    let ordered_lowercased: HashSet<String> = res.iter().map(|name| name.to_lowercase()).collect();
+    res.extend(enabled.into_iter().filter(|name| !ordered_lowercased.contains(&name.to_lowercase())));
+    res
+}
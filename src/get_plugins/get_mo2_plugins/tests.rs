@@ -0,0 +1,43 @@
+use super::{parse_game_path, parse_load_order, parse_modlist};
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+fn lines(src: &[&str]) -> Vec<String> {
+    src.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn game_path() {
+    assert_eq!(parse_game_path("@ByteArray(C:\\\\Games\\\\Morrowind)"), PathBuf::from("C:\\Games\\Morrowind"));
+    assert_eq!(parse_game_path("/home/user/Games/Morrowind"), PathBuf::from("/home/user/Games/Morrowind"));
+}
+
+#[test]
+fn modlist_enabled_mods_by_ascending_priority() {
+    let modlist = lines(&[
+        "# This file was automatically generated by Mod Organizer.",
+        "+Patch for Purists",
+        "-Disabled Mod",
+        "*Unmanaged: Tribunal",
+        "+Morrowind Rebirth",
+    ]);
+    assert_eq!(parse_modlist(&modlist), ["Morrowind Rebirth", "Patch for Purists"]);
+}
+
+#[test]
+fn load_order_without_loadorder_file() {
+    let plugins = lines(&["# comment", "Morrowind.esm", "", "Tribunal.esm"]);
+    assert_eq!(parse_load_order(&plugins, None), ["Morrowind.esm", "Tribunal.esm"]);
+    let plugins = lines(&["*Morrowind.esm", "Disabled.esp", "*Tribunal.esm"]);
+    assert_eq!(parse_load_order(&plugins, None), ["Morrowind.esm", "Tribunal.esm"]);
+}
+
+#[test]
+fn load_order_with_loadorder_file() {
+    let plugins = lines(&["*Patch.esp", "*Morrowind.esm", "Disabled.esp", "*tribunal.esm", "*Unordered.esp"]);
+    let loadorder = lines(&["# comment", "Morrowind.esm", "Tribunal.esm", "Disabled.esp", "Patch.esp"]);
+    assert_eq!(
+        parse_load_order(&plugins, Some(&loadorder)),
+        ["Morrowind.esm", "Tribunal.esm", "Patch.esp", "Unordered.esp"]
+    );
+}
@@ -0,0 +1,187 @@
+use crate::{err_or_ignore, msg, read_lines, Cfg, Guts, Log, MsgTone};
+use anyhow::{Context, Result};
+use dirs::{data_dir, document_dir, preference_dir};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod tests;
+
+// COMMENT: values gathered from openmw.cfg and all the chained configuration files
+#[derive(Default)]
+pub(super) struct OmwConfig {
+    pub(super) data: Vec<PathBuf>,
+    pub(super) data_local: Option<PathBuf>,
+    pub(super) content: Vec<String>,
+    pub(super) groundcover: Vec<String>,
+}
+
+#[derive(Default)]
+struct OmwConfigFile {
+    replace: Vec<String>,
+    data: Vec<PathBuf>,
+    data_local: Option<PathBuf>,
+    content: Vec<String>,
+    groundcover: Vec<String>,
+    config: Vec<PathBuf>,
+    unresolved: Vec<String>,
+}
+
+struct OmwTokens {
+    local: Option<PathBuf>,
+    global: Option<PathBuf>,
+    userdata: Option<PathBuf>,
+    userconfig: Option<PathBuf>,
+}
+
+// COMMENT: configuration files are loaded in order of "config=" appearance, each one appends to or replaces values of previous ones
+pub(super) fn get_omw_config(config_path: &Path, cfg: &Cfg, log: &mut Log) -> Result<OmwConfig> {
+    let tokens = OmwTokens {
+        local: (!cfg.omw_local_dir.is_empty()).then(|| PathBuf::from(&cfg.omw_local_dir)),
+        global: get_global_dir(&cfg.guts),
+        userdata: get_user_dir(data_dir(), &cfg.guts.omw_userdata_suffix_linux_macos, &cfg.guts.omw_userdata_suffix_windows),
+        userconfig: get_user_dir(
+            preference_dir(),
+            &cfg.guts.omw_userconfig_suffix_linux_macos,
+            &cfg.guts.omw_userconfig_suffix_windows,
+        ),
+    };
+    let mut res = OmwConfig::default();
+    let mut loaded: Vec<PathBuf> = Vec::new();
+    let mut queue: VecDeque<PathBuf> = VecDeque::from([config_path.to_path_buf()]);
+    while let Some(path) = queue.pop_front() {
+        if loaded.is_empty() {
+            loaded.push(path.canonicalize().unwrap_or_else(|_| path.clone()));
+        } else {
+            if !path.is_file() {
+                let text = format!("Chained OpenMW configuration file \"{}\" not found", path.display());
+                msg(text, MsgTone::Neutral, 1, cfg, log)?;
+                continue;
+            }
+            let canonical_path = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if loaded.contains(&canonical_path) {
+                continue;
+            }
+            let text = format!("Gathering plugins from chained OpenMW configuration file \"{}\"", path.display());
+            msg(text, MsgTone::Neutral, 1, cfg, log)?;
+            loaded.push(canonical_path);
+        }
+        let lines = read_lines(&path)
+            .with_context(|| format!("Failed to read OpenMW configuration file \"{}\"", path.display()))?
+            .map_while(Result::ok)
+            .collect::<Vec<String>>();
+        let file = parse_omw_config_file(&lines, path.parent().unwrap_or(Path::new("")), &tokens, &cfg.guts);
+        for line in &file.unresolved {
+            let text = format!(
+                "Failed to resolve directory token of line \"{line}\" in OpenMW configuration file \"{}\": directory is unknown, use --omw-local-dir to set \"?local?\"",
+                path.display()
+            );
+            err_or_ignore(text, cfg, log)?;
+        }
+        for replaced in &file.replace {
+            match replaced.as_str() {
+                "data" => res.data.clear(),
+                "data-local" => res.data_local = None,
+                "content" => res.content.clear(),
+                "groundcover" => res.groundcover.clear(),
+                "config" => queue.clear(),
+                _ => {}
+            }
+        }
+        res.data.extend(file.data);
+        if file.data_local.is_some() {
+            res.data_local = file.data_local;
+        }
+        res.content.extend(file.content);
+        res.groundcover.extend(file.groundcover);
+        queue.extend(file.config.into_iter().map(|dir| dir.join(&cfg.guts.omw_config_file_name)));
+    }
+    Ok(res)
+}
+
+fn get_user_dir(linux_macos_dir: Option<PathBuf>, suffix_linux_macos: &str, suffix_windows: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        document_dir().map(|dir| dir.join(suffix_windows))
+    } else {
+        linux_macos_dir.map(|dir| dir.join(suffix_linux_macos))
+    }
+}
+
+// COMMENT: OpenMW has no global directory on Windows
+fn get_global_dir(guts: &Guts) -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        &guts.omw_global_dir_windows
+    } else if cfg!(target_os = "macos") {
+        &guts.omw_global_dir_macos
+    } else {
+        &guts.omw_global_dir_linux
+    };
+    (!dir.is_empty()).then(|| PathBuf::from(dir))
+}
+
+fn parse_omw_config_file(lines: &[String], config_dir: &Path, tokens: &OmwTokens, guts: &Guts) -> OmwConfigFile {
+    let mut res = OmwConfigFile::default();
+    for line in lines.iter().map(|line| line.trim()) {
+        if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_content) {
+            res.content.push(value.trim().to_owned());
+        } else if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_data) {
+            match get_path(value, config_dir, tokens) {
+                Some(path) => res.data.push(path),
+                None => res.unresolved.push(line.to_owned()),
+            }
+        } else if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_data_local) {
+            match get_path(value, config_dir, tokens) {
+                Some(path) => res.data_local = Some(path),
+                None => res.unresolved.push(line.to_owned()),
+            }
+        } else if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_config) {
+            match get_path(value, config_dir, tokens) {
+                Some(path) => res.config.push(path),
+                None => res.unresolved.push(line.to_owned()),
+            }
+        } else if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_groundcover) {
+            res.groundcover.push(value.trim().to_owned());
+        } else if let Some(value) = line.strip_prefix(&guts.omw_line_beginning_replace) {
+            res.replace.push(value.trim().to_owned());
+        }
+    }
+    res
+}
+
+// COMMENT: paths may be quoted with "&" escaping, start with a token and be relative to the directory of the configuration file
+fn get_path(value: &str, config_dir: &Path, tokens: &OmwTokens) -> Option<PathBuf> {
+    let path = unquote(value.trim());
+    for (token, token_dir) in [
+        ("?local?", tokens.local.as_ref()),
+        ("?global?", tokens.global.as_ref()),
+        ("?userdata?", tokens.userdata.as_ref()),
+        ("?userconfig?", tokens.userconfig.as_ref()),
+    ] {
+        if let Some(rest) = path.strip_prefix(token) {
+            return token_dir.map(|token_dir| token_dir.join(rest.trim_start_matches(['/', '\\'])));
+        }
+    }
+    Some(config_dir.join(path))
+}
+
+fn unquote(value: &str) -> String {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return value.to_owned();
+    };
+    let mut res = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => {
+                if let Some(escaped) = chars.next() {
+                    res.push(escaped);
+                }
+            }
+            '"' => break,
+            _ => res.push(c),
+        }
+    }
+    res
+}
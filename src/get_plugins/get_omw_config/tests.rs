@@ -0,0 +1,83 @@
+use super::{get_path, parse_omw_config_file, unquote, OmwTokens};
+use crate::Cfg;
+use pretty_assertions::assert_eq;
+use std::path::{Path, PathBuf};
+
+fn tokens() -> OmwTokens {
+    OmwTokens {
+        local: Some(PathBuf::from("/games/openmw")),
+        global: Some(PathBuf::from("/etc/openmw")),
+        userdata: Some(PathBuf::from("/home/user/.local/share/openmw")),
+        userconfig: None,
+    }
+}
+
+#[test]
+fn quoted_values() {
+    assert_eq!(unquote("\"/games/Data Files\""), "/games/Data Files");
+    assert_eq!(unquote("\"/mods/Tom && Jerry &\"Quoted&\"\""), "/mods/Tom & Jerry \"Quoted\"");
+    assert_eq!(unquote("/mods/Unquoted"), "/mods/Unquoted");
+}
+
+#[test]
+fn paths_with_tokens() {
+    let config_dir = Path::new("/home/user/.config/openmw");
+    assert_eq!(get_path("\"?local?data\"", config_dir, &tokens()), Some(PathBuf::from("/games/openmw/data")));
+    assert_eq!(get_path("\"?global?data\"", config_dir, &tokens()), Some(PathBuf::from("/etc/openmw/data")));
+    assert_eq!(
+        get_path("\"?userdata?/data\"", config_dir, &tokens()),
+        Some(PathBuf::from("/home/user/.local/share/openmw/data"))
+    );
+    assert_eq!(
+        get_path("\"mods/Patch\"", config_dir, &tokens()),
+        Some(PathBuf::from("/home/user/.config/openmw/mods/Patch"))
+    );
+    assert_eq!(get_path("\"/mods/Patch\"", config_dir, &tokens()), Some(PathBuf::from("/mods/Patch")));
+}
+
+#[test]
+fn paths_with_unknown_token_directories() {
+    let config_dir = Path::new("/home/user/.config/openmw");
+    let tokens = OmwTokens {
+        local: None,
+        global: None,
+        ..tokens()
+    };
+    assert_eq!(get_path("?local?data", config_dir, &tokens), None);
+    assert_eq!(get_path("?global?data", config_dir, &tokens), None);
+    assert_eq!(get_path("?userconfig?data", config_dir, &tokens), None);
+}
+
+#[test]
+fn config_file() {
+    let mut cfg = Cfg::default();
+    cfg.guts.omw_line_beginning_content = String::from("content=");
+    cfg.guts.omw_line_beginning_data = String::from("data=");
+    cfg.guts.omw_line_beginning_data_local = String::from("data-local=");
+    cfg.guts.omw_line_beginning_config = String::from("config=");
+    cfg.guts.omw_line_beginning_replace = String::from("replace=");
+    cfg.guts.omw_line_beginning_groundcover = String::from("groundcover=");
+    let lines = [
+        "# comment",
+        "replace=content",
+        "data=\"/games/Data Files\"",
+        "data-local=\"?userdata?data\"",
+        "data=?userconfig?data",
+        "fallback=LevelUpMsg_2,content=not a plugin",
+        "content=Morrowind.esm",
+        "  content=Patch.esp  ",
+        "groundcover=Grass.esp",
+        "config=\"user\"",
+    ]
+    .iter()
+    .map(|line| line.to_string())
+    .collect::<Vec<String>>();
+    let file = parse_omw_config_file(&lines, Path::new("/games/openmw"), &tokens(), &cfg.guts);
+    assert_eq!(file.replace, ["content"]);
+    assert_eq!(file.data, [PathBuf::from("/games/Data Files")]);
+    assert_eq!(file.data_local, Some(PathBuf::from("/home/user/.local/share/openmw/data")));
+    assert_eq!(file.content, ["Morrowind.esm", "Patch.esp"]);
+    assert_eq!(file.groundcover, ["Grass.esp"]);
+    assert_eq!(file.config, [PathBuf::from("/games/openmw/user")]);
+    assert_eq!(file.unresolved, ["data=?userconfig?data"]);
+}
@@ -0,0 +1,80 @@
+use super::{finalize_plugins, is_extension_ignored, skip_filtered_plugins, Helper, PluginInfo};
+use crate::{err_or_ignore, msg, read_lines, Cfg, Log, MsgTone};
+use anyhow::{Context, Result};
+use std::{
+    io::{stdin, BufRead},
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) fn get_plugins_from_list(cfg: &Cfg, log: &mut Log) -> Result<Vec<PluginInfo>> {
+    let (paths, source, source_path): (Vec<PathBuf>, &str, PathBuf) = if !cfg.plugins.is_empty() {
+        (
+            cfg.plugins.iter().map(PathBuf::from).collect(),
+            "--plugins",
+            PathBuf::from(cfg.plugins.join(",")),
+        )
+    } else if cfg.load_order_file == "-" {
+        msg("Gathering plugins from standard input", MsgTone::Neutral, 1, cfg, log)?;
+        let lines = stdin()
+            .lock()
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+            .with_context(|| "Failed to read load order from standard input")?;
+        (parse_load_order_file(&lines, Path::new("")), "standard input", PathBuf::from("-"))
+    } else {
+        let load_order_path = PathBuf::from(&cfg.load_order_file);
+        let text = format!("Gathering plugins from load order file \"{}\"", load_order_path.display());
+        msg(text, MsgTone::Neutral, 1, cfg, log)?;
+        let lines = read_lines(&load_order_path)
+            .with_context(|| format!("Failed to read load order file \"{}\"", load_order_path.display()))?
+            .map_while(Result::ok)
+            .collect::<Vec<String>>();
+        let base_dir = load_order_path.parent().unwrap_or(Path::new(""));
+        (parse_load_order_file(&lines, base_dir), "load order file", load_order_path)
+    };
+    let mut helper: Helper = Helper::default();
+    let mut res: Vec<PluginInfo> = Vec::new();
+    for path in paths {
+        get_plugin_by_path(path, &mut res, &mut helper, cfg, log).with_context(|| "Failed to find plugin")?;
+    }
+    finalize_plugins(res, &helper, source, &source_path, cfg, log)
+}
+
+fn get_plugin_by_path(path: PathBuf, res: &mut Vec<PluginInfo>, helper: &mut Helper, cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let raw_name = match path.file_name() {
+        Some(file_name) => file_name.to_string_lossy().into_owned(),
+        None => {
+            let text = format!("Failed to get plugin name from path \"{}\"", path.display());
+            return err_or_ignore(text, cfg, log);
+        }
+    };
+    if let Some((name, name_lowercased)) = skip_filtered_plugins(&raw_name, helper, cfg, log)? {
+        if !is_extension_ignored(&name_lowercased, cfg) {
+            if path.is_file() {
+                res.push(PluginInfo {
+                    name,
+                    name_lowercased,
+                    path,
+                    groundcover: false,
+                });
+            } else {
+                let text = format!("Plugin \"{}\" not found at expected location \"{}\"", name, path.display());
+                err_or_ignore(text, cfg, log)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// COMMENT: one plugin path per line, relative paths are resolved against the directory of the load order file
+fn parse_load_order_file(lines: &[String], base_dir: &Path) -> Vec<PathBuf> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base_dir.join(line))
+        .collect()
+}
@@ -0,0 +1,21 @@
+use super::parse_load_order_file;
+use pretty_assertions::assert_eq;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn load_order_file() {
+    let lines = ["# Base game", "Morrowind.esm", "", "  Tribunal.esm  ", "mods/Patch.esp", "/absolute/Other.esp"]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        parse_load_order_file(&lines, Path::new("lists")),
+        [
+            PathBuf::from("lists/Morrowind.esm"),
+            PathBuf::from("lists/Tribunal.esm"),
+            PathBuf::from("lists/mods/Patch.esp"),
+            PathBuf::from("/absolute/Other.esp")
+        ]
+    );
+    assert_eq!(parse_load_order_file(&lines[..2], Path::new("")), [PathBuf::from("Morrowind.esm")]);
+}
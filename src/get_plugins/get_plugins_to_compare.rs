@@ -40,6 +40,10 @@ pub(crate) fn get_plugins_to_compare(cfg: &Cfg, log: &mut Log) -> Result<Compare
             (path, comp, purpose) = (&cfg.delev_output.path, &mut compare_plugins.delev_previous, "previous delev");
             get_plugin_to_compare(path, comp, purpose, true, cfg, log)?;
         }
+        if cfg.relevel {
+            (path, comp, purpose) = (&cfg.relevel_output.path, &mut compare_plugins.relevel_previous, "previous relevel");
+            get_plugin_to_compare(path, comp, purpose, true, cfg, log)?;
+        }
     }
     Ok(compare_plugins)
 }
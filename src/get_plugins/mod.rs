@@ -1,4 +1,4 @@
-use crate::{err_or_ignore, err_or_ignore_thread_safe, msg, read_lines, Cfg, Log, MsgTone};
+use crate::{err_or_ignore, err_or_ignore_thread_safe, msg, plural, read_lines, Cfg, Log, MsgTone};
 use anyhow::{anyhow, Context, Result};
 use dirs::{data_dir, document_dir};
 use fs_err::read_dir;
@@ -10,6 +10,12 @@ use std::{
 };
 mod get_game_config;
 use get_game_config::get_game_config;
+mod get_mo2_plugins;
+use get_mo2_plugins::get_mo2_plugins;
+mod get_omw_config;
+use get_omw_config::get_omw_config;
+mod get_plugins_from_list;
+use get_plugins_from_list::get_plugins_from_list;
 mod get_plugins_to_compare;
 pub(crate) use get_plugins_to_compare::get_plugins_to_compare;
 
@@ -18,17 +24,14 @@ pub(crate) struct PluginInfo {
     pub(crate) name: String,
     pub(crate) name_lowercased: String,
     pub(crate) path: PathBuf,
+    pub(crate) groundcover: bool,
 }
 
 #[derive(Default)]
 struct Helper {
-    mor_found: bool,
-    omw_found: bool,
-    omw_data_ended: bool,
     omw_data_counter: usize,
     mor_data_files_dir: PathBuf,
     mor_data_files_dir_found: bool,
-    omw_all_plugins_found: bool,
     // Make plugins defined in both plugins_skip and plugins_skip_last be skipped once
     preskipped_plugins_number: usize,
     skipped_plugin_numbers: Vec<usize>,
@@ -41,53 +44,75 @@ pub(super) fn get_plugins(cfg: &Cfg, log: &mut Log) -> Result<Vec<PluginInfo>> {
     if cfg.compare_only {
         return Ok(res);
     }
+    if !cfg.mo2_profile.is_empty() {
+        return get_mo2_plugins(cfg, log).with_context(|| "Failed to get plugins from Mod Organizer 2 profile");
+    }
+    if !cfg.plugins.is_empty() || !cfg.load_order_file.is_empty() {
+        return get_plugins_from_list(cfg, log).with_context(|| "Failed to get plugins from list");
+    }
     let config_path = get_game_config(cfg, log).with_context(|| "Failed to get game configuration file")?;
     let text = format!("Gathering plugins from game configuration file \"{}\"", &config_path.display());
     msg(text, MsgTone::Neutral, 1, cfg, log)?;
-    let config_lines =
-        read_lines(&config_path).with_context(|| format!("Failed to read game configuration file \"{}\"", &config_path.display()))?;
+    let config_lines = read_lines(&config_path)
+        .with_context(|| format!("Failed to read game configuration file \"{}\"", &config_path.display()))?
+        .map_while(Result::ok)
+        .collect::<Vec<String>>();
     let mut helper: Helper = Helper::default();
-    let mut omw_data_dirs: Vec<(usize, PathBuf)> = Vec::new();
-    let mut omw_all_plugins: HashMap<String, PathBuf> = HashMap::new();
-    for line in config_lines.map_while(Result::ok) {
-        if !helper.omw_found && line.starts_with(&cfg.guts.mor_line_beginning_content) {
+    let mut omw_groundcover: Option<(Vec<String>, HashMap<String, PathBuf>)> = None;
+    if config_lines.iter().any(|line| line.starts_with(&cfg.guts.mor_line_beginning_content)) {
+        for line in config_lines.iter().filter(|line| line.starts_with(&cfg.guts.mor_line_beginning_content)) {
             if !helper.mor_data_files_dir_found {
                 mor_get_data_files_dir(&config_path, &mut helper, cfg)
                     .with_context(|| "Failed to find Morrowind's \"Data Files\" directory")?;
             }
-            mor_get_plugin(&line, &mut res, &mut helper, cfg, log).with_context(|| "Failed to find Morrowind's plugin")?;
+            mor_get_plugin(line, &mut res, &mut helper, cfg, log).with_context(|| "Failed to find Morrowind's plugin")?;
         }
-        if !helper.mor_found {
-            if !helper.omw_data_ended && line.starts_with(&cfg.guts.omw_line_beginning_data) {
-                omw_get_data_dir(&line, &mut omw_data_dirs, &mut helper, cfg, log)
-                    .with_context(|| "Failed to get OpenMW's data directory")?;
-            }
-            if line.starts_with(&cfg.guts.omw_line_beginning_content) {
-                if !helper.omw_all_plugins_found {
-                    omw_get_cs_data_dir(&mut omw_data_dirs, &mut helper, cfg, log)
-                        .with_context(|| "Failed to find \"hidden\" OpenMW-CS data directory path")?;
-                    omw_all_plugins =
-                        get_all_plugins(&omw_data_dirs, &mut helper, cfg).with_context(|| "Failed to find all OpenMW's plugins")?;
-                };
-                omw_get_plugin(&line, &mut res, &omw_all_plugins, &mut helper, cfg, log)
-                    .with_context(|| "Failed to find OpenMW's plugin")?;
-            }
+    } else {
+        let omw_config = get_omw_config(&config_path, cfg, log).with_context(|| "Failed to resolve OpenMW's configuration files")?;
+        let mut omw_data_dirs: Vec<(usize, PathBuf)> = omw_config.data.into_iter().enumerate().collect();
+        helper.omw_data_counter = omw_data_dirs.len();
+        match omw_config.data_local {
+            Some(data_local) => omw_data_dirs.push((helper.omw_data_counter, data_local)),
+            None => omw_get_cs_data_dir(&mut omw_data_dirs, &mut helper, cfg, log)
+                .with_context(|| "Failed to find \"hidden\" OpenMW-CS data directory path")?,
+        }
+        let omw_all_plugins = get_all_plugins(&omw_data_dirs, cfg).with_context(|| "Failed to find all OpenMW's plugins")?;
+        for name in &omw_config.content {
+            get_listed_plugin(name, &mut res, &omw_all_plugins, false, &mut helper, cfg, log)
+                .with_context(|| "Failed to find OpenMW's plugin")?;
         }
+        omw_groundcover = Some((omw_config.groundcover, omw_all_plugins));
     }
+    let mut res = finalize_plugins(res, &helper, "game configuration file", &config_path, cfg, log)?;
+    if let Some((groundcover, omw_all_plugins)) = omw_groundcover {
+        omw_get_groundcover_plugins(&groundcover, &mut res, &omw_all_plugins, &mut helper, cfg, log)
+            .with_context(|| "Failed to find OpenMW's groundcover plugins")?;
+    }
+    Ok(res)
+}
+
+fn finalize_plugins(
+    mut res: Vec<PluginInfo>,
+    helper: &Helper,
+    source: &str,
+    source_path: &Path,
+    cfg: &Cfg,
+    log: &mut Log,
+) -> Result<Vec<PluginInfo>> {
     if res.is_empty() {
-        error_none_listed(&config_path)?;
+        error_none_listed(source, source_path)?;
     }
     if cfg.skip_last > 0 {
-        skip_last_plugins(&mut res, &helper, cfg, log).with_context(|| format!("Failed to skip last {} plugins", cfg.skip_last))?;
+        skip_last_plugins(&mut res, helper, cfg, log).with_context(|| format!("Failed to skip last {} plugins", cfg.skip_last))?;
     }
     if res.is_empty() {
-        error_none_listed(&config_path)
+        error_none_listed(source, source_path)
     } else {
         Ok(res)
     }
 }
 
-fn get_all_plugins(omw_data_dirs: &[(usize, PathBuf)], helper: &mut Helper, cfg: &Cfg) -> Result<HashMap<String, PathBuf>> {
+fn get_all_plugins(omw_data_dirs: &[(usize, PathBuf)], cfg: &Cfg) -> Result<HashMap<String, PathBuf>> {
     let mut found_plugins: Vec<(usize, String, PathBuf)> = omw_data_dirs
         .par_iter()
         .map(|(id, dir_path)| -> Result<Vec<(usize, String, PathBuf)>, _> {
@@ -123,9 +148,6 @@ fn get_all_plugins(omw_data_dirs: &[(usize, PathBuf)], helper: &mut Helper, cfg:
             v.insert(path);
         }
     });
-    if !helper.omw_all_plugins_found {
-        helper.omw_all_plugins_found = true;
-    }
     Ok(all_plugins)
 }
 
@@ -154,6 +176,7 @@ fn mor_get_plugin(line: &str, res: &mut Vec<PluginInfo>, helper: &mut Helper, cf
                     name,
                     name_lowercased,
                     path,
+                    groundcover: false,
                 });
             } else {
                 let text = format!(
@@ -168,66 +191,81 @@ fn mor_get_plugin(line: &str, res: &mut Vec<PluginInfo>, helper: &mut Helper, cf
         let text = format!("Failed to parse line \"{line}\"");
         err_or_ignore(text, cfg, log)?;
     }
-    if !helper.mor_found {
-        helper.mor_found = true;
-    }
     Ok(())
 }
 
-fn omw_get_data_dir(
-    line: &str,
-    omw_data_dirs: &mut Vec<(usize, PathBuf)>,
+fn get_listed_plugin(
+    raw_name: &str,
+    res: &mut Vec<PluginInfo>,
+    all_plugins: &HashMap<String, PathBuf>,
+    groundcover: bool,
     helper: &mut Helper,
     cfg: &Cfg,
     log: &mut Log,
 ) -> Result<()> {
-    if let Some(raw_data) = line.split('=').nth(1) {
-        let data = PathBuf::from(&raw_data[1..raw_data.len() - 1]);
-        omw_data_dirs.push((helper.omw_data_counter, data));
-        helper.omw_data_counter += 1;
-    } else {
-        let text = format!("Failed to parse line \"{line}\"");
-        err_or_ignore(text, cfg, log)?;
-    }
-    if !helper.omw_found {
-        helper.omw_found = true;
+    if let Some((name, name_lowercased)) = skip_filtered_plugins(raw_name, helper, cfg, log)? {
+        if !is_extension_ignored(&name_lowercased, cfg) {
+            if let Some(path) = all_plugins.get(&name) {
+                res.push(PluginInfo {
+                    name,
+                    name_lowercased,
+                    path: path.clone(),
+                    groundcover,
+                });
+            } else {
+                let text = format!("Failed to find plugin \"{name}\"");
+                err_or_ignore(text, cfg, log)?;
+            }
+        }
     }
     Ok(())
 }
 
-fn omw_get_plugin(
-    line: &str,
+// COMMENT: groundcover plugins are placed after all the other plugins like OpenMW does, so --skip-last doesn't count them
+fn omw_get_groundcover_plugins(
+    groundcover: &[String],
     res: &mut Vec<PluginInfo>,
     omw_all_plugins: &HashMap<String, PathBuf>,
     helper: &mut Helper,
     cfg: &Cfg,
     log: &mut Log,
 ) -> Result<()> {
-    if let Some(raw_name) = line.split('=').nth(1) {
-        if let Some((name, name_lowercased)) = skip_filtered_plugins(raw_name, helper, cfg, log)? {
-            if !cfg
-                .guts
-                .plugin_extensions_to_ignore
-                .iter()
-                .any(|ext| name_lowercased.ends_with(ext))
-            {
-                if let Some(path) = omw_all_plugins.get(&name) {
-                    res.push(PluginInfo {
-                        name,
-                        name_lowercased,
-                        path: path.clone(),
-                    });
-                } else {
-                    let text = format!("Failed to find plugin \"{name}\"");
-                    err_or_ignore(text, cfg, log)?;
-                }
+    if groundcover.is_empty() {
+        return Ok(());
+    }
+    if !cfg.meta.read_groundcover {
+        let text = format!(
+            "{} groundcover plugin{} will be skipped. {}",
+            groundcover.len(),
+            plural("s", groundcover.len())?,
+            if !cfg.groundcover {
+                format!("Add --groundcover to read {} for cell multipatch", if groundcover.len() == 1 { "it" } else { "them" })
+            } else {
+                String::from("Groundcover is only read for --cellnames or --fogbug multipatch while CELL records are not merged")
             }
-        }
+        );
+        return msg(text, MsgTone::Neutral, 0, cfg, log);
     }
-    if !helper.omw_data_ended {
-        helper.omw_data_ended = true;
+    let plugins_len = res.len();
+    for name in groundcover {
+        get_listed_plugin(name, res, omw_all_plugins, true, helper, cfg, log)?;
     }
-    Ok(())
+    let text = format!(
+        "Groundcover plugins will be read for cell multipatch only:\n{}",
+        res[plugins_len..]
+            .iter()
+            .map(|plugin| format!("\t{}", plugin.name))
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    msg(text, MsgTone::Neutral, 1, cfg, log)
+}
+
+fn is_extension_ignored(name_lowercased: &str, cfg: &Cfg) -> bool {
+    cfg.guts
+        .plugin_extensions_to_ignore
+        .iter()
+        .any(|ext| name_lowercased.ends_with(ext))
 }
 
 fn skip_filtered_plugins(raw_name: &str, helper: &mut Helper, cfg: &Cfg, log: &mut Log) -> Result<Option<(String, String)>> {
@@ -333,11 +371,8 @@ fn skip_last_plugins(res: &mut Vec<PluginInfo>, helper: &Helper, cfg: &Cfg, log:
     }
 }
 
-fn error_none_listed(config_path: &Path) -> Result<Vec<PluginInfo>> {
-    Err(anyhow!(
-        "None plugins listed in game configuration file: \"{}\"",
-        config_path.display()
-    ))
+fn error_none_listed(source: &str, source_path: &Path) -> Result<Vec<PluginInfo>> {
+    Err(anyhow!("None plugins listed in {}: \"{}\"", source, source_path.display()))
 }
 
 fn omw_get_cs_data_dir(omw_data_dirs: &mut Vec<(usize, PathBuf)>, helper: &mut Helper, cfg: &Cfg, log: &mut Log) -> Result<()> {
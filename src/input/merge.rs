@@ -2,11 +2,11 @@ use crate::PluginInfo;
 use anyhow::{Context, Result};
 use hashbrown::{hash_map::Entry, HashMap};
 use paste::paste;
-use std::fmt;
+use std::{fmt, sync::Arc};
 use tes3::esp::{
-    Activator, Alchemy, Apparatus, Armor, Birthsign, Bodypart, Book, Cell, CellFlags, Class, Clothing, Container, Creature, Door,
-    EffectId, Enchanting, GameSetting, Ingredient, Light, Lockpick, MagicEffect, MiscItem, Npc, Probe, Race, RepairItem, Skill,
-    SkillId, Sound, SoundGen, Spell, Static, Weapon,
+    Activator, Alchemy, Apparatus, Armor, Birthsign, Bodypart, Book, Cell, CellFlags, Class, Clothing, Container, Creature, Dialogue,
+    DialogueInfo, Door, EffectId, Enchanting, GameSetting, Ingredient, Landscape, LandscapeTexture, Light, Lockpick, MagicEffect,
+    MiscItem, Npc, PathGrid, Probe, Race, RepairItem, Script, Skill, SkillId, Sound, SoundGen, Spell, Static, Weapon,
 };
 
 pub(crate) trait RecordMap<'a> {
@@ -21,6 +21,20 @@ pub(crate) trait RecordMap<'a> {
     fn record_id_display(&'a self) -> anyhow::Result<impl 'a + std::fmt::Display>;
 }
 
+// COMMENT: INFO records belong to the preceding DIAL record, so they are kept together
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Topic {
+    pub(crate) dialogue: Dialogue,
+    pub(crate) infos: Vec<DialogueInfo>,
+}
+
+// COMMENT: texture indices of LAND are local to the plugin, so LTEX records of the plugin are kept along, keyed by texture index + 1
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Land {
+    pub(crate) landscape: Landscape,
+    pub(crate) textures: Arc<HashMap<u16, LandscapeTexture>>,
+}
+
 #[derive(Eq, Hash, PartialEq)]
 pub(crate) enum CellKey {
     Interior(String),
@@ -52,6 +66,15 @@ macro_rules! select_id_debug {
             Ok(&$self.$name)
         }
     };
+    ($self:ident, $record:ident, $short:ident, dial) => {
+        Ok(&$record.$short.dialogue.id)
+    };
+    ($self:ident, $record:ident, $short:ident, land) => {
+        Ok(&$record.$short.landscape.grid)
+    };
+    ($self:ident, $record:ident, $short:ident, pgrd) => {
+        Ok(($record.$short.cell.as_str(), $record.$short.data.grid))
+    };
     ($self:ident, $record:ident, $short:ident, $key:ident) => {
         Ok(&$record.$short.$key)
     };
@@ -145,6 +168,9 @@ impl<'a> RecordMap<'a> for &[<$long RecordMap>]<'a> {
         let record = self.record(0)?;
         macro_rules! select_id_display {
             (id) => { &record.$short.id };
+            (dial) => { &record.$short.dialogue.id };
+            (land) => { format!("{:?}", record.$short.landscape.grid) };
+            (pgrd) => { format!("{} {:?}", record.$short.cell, record.$short.data.grid) };
             (cell, $name:ident) => {
             if record.$short.data.flags.contains(CellFlags::IS_INTERIOR) {
                 &record.$short.name
@@ -162,6 +188,7 @@ impl<'a> RecordMap<'a> for &[<$long RecordMap>]<'a> {
 pub(crate) struct IntermediateRecords<'a> {
     counter: usize,
     $(pub(crate) $short: HashMap<$key_type, [<$long RecordMap>]<'a>>,)+
+    pub(crate) groundcover_cell: HashMap<CellKey, CellRecordMap<'a>>,
 }
 
 impl<'a> IntermediateRecords<'a> {
@@ -170,6 +197,16 @@ impl<'a> IntermediateRecords<'a> {
         self.counter += 1;
         macro_rules! select_hashmap_entry {
             (id) => { $short.id.to_lowercase() };
+            (dial) => { $short.dialogue.id.to_lowercase() };
+            (land) => { CellKey::Exterior($short.landscape.grid) };
+            // COMMENT: PGRD has no flags to tell interior, though interior path grids are always at (0, 0) and have the cell name
+            (pgrd) => {
+                if $short.data.grid == (0, 0) && !$short.cell.is_empty() {
+                    CellKey::Interior($short.cell.to_lowercase())
+                } else {
+                    CellKey::Exterior($short.data.grid)
+                }
+            };
             (cell) => {
                 if $short.data.flags.contains(CellFlags::IS_INTERIOR) {
                     CellKey::Interior($short.name.to_lowercase())
@@ -200,6 +237,7 @@ pub(crate) struct Merge {
     pub(crate) plus_before_minus: bool,
     pub(crate) verbose_atmosphere_data: bool,
     pub(crate) destination_similarity: f32,
+    pub(crate) pathgrid_similarity: f32,
     pub(crate) references: bool,
     $(pub(crate) $short: bool,)+
 }
@@ -228,6 +266,7 @@ impl Merge {
         plus_before_minus: bool,
         verbose_atmosphere_data: bool,
         destination_similarity: f32,
+        pathgrid_similarity: f32,
         merge_types: Vec<String>,
         merge_skip_types: Vec<String>,
     ) -> Self {
@@ -239,6 +278,7 @@ impl Merge {
             plus_before_minus,
             verbose_atmosphere_data,
             destination_similarity,
+            pathgrid_similarity,
             ..Default::default()
         };
         if !no_merge {
@@ -255,11 +295,14 @@ impl Merge {
 make_intermediate_records!(
     gmst:id:String:GameSetting,
     clas:id:String:Class,
+    fact:id:String:Faction,
     race:id:String:Race,
     soun:id:String:Sound,
     skil:skill_id:SkillId:Skill,
     mgef:effect_id:EffectId:MagicEffect,
+    regn:id:String:Region,
     bsgn:id:String:Birthsign,
+    ltex:id:String:LandscapeTexture,
     spel:id:String:Spell,
     stat:id:String:Static,
     door:id:String:Door,
@@ -282,5 +325,43 @@ make_intermediate_records!(
     book:id:String:Book,
     alch:id:String:Alchemy,
     cell:cell:CellKey:Cell:name,
-    sndg:id:String:SoundGen
+    land:land:CellKey:Land,
+    pgrd:pgrd:CellKey:PathGrid,
+    sndg:id:String:SoundGen,
+    dial:dial:String:Topic,
+    scpt:id:String:Script
 );
+
+impl<'a> IntermediateRecords<'a> {
+    // COMMENT: groundcover cells are kept apart from other cells to not be merged, see Cfg::meta()
+    pub(crate) fn get_groundcover_cell(&mut self, cell: Cell, plugin_info: &'a PluginInfo) {
+        self.counter += 1;
+        let key = if cell.data.flags.contains(CellFlags::IS_INTERIOR) {
+            CellKey::Interior(cell.name.to_lowercase())
+        } else {
+            CellKey::Exterior(cell.data.grid)
+        };
+        match self.groundcover_cell.entry(key) {
+            Entry::Vacant(v) => {
+                v.insert(CellRecordMap::new(self.counter, cell, plugin_info));
+            }
+            Entry::Occupied(mut o) => {
+                o.get_mut().push(cell, plugin_info);
+            }
+        };
+    }
+
+    // COMMENT: groundcover plugins are loaded after all the other plugins, so their cells go last
+    pub(crate) fn append_groundcover_cells(&mut self) {
+        for (key, groundcover_map) in self.groundcover_cell.drain() {
+            match self.cell.entry(key) {
+                Entry::Vacant(v) => {
+                    v.insert(groundcover_map);
+                }
+                Entry::Occupied(mut o) => {
+                    o.get_mut().records.extend(groundcover_map.records);
+                }
+            };
+        }
+    }
+}
@@ -1,25 +1,33 @@
-use crate::{err_or_ignore, msg, CellRecordMap, Cfg, LlCreatureRecords, LlItemRecords, Log, MsgTone, PluginInfo, Progress};
+use crate::{
+    err_or_ignore, msg, CellRecordMap, Cfg, Land, LlCreatureRecords, LlItemRecords, Log, MsgTone, PluginInfo, Progress, Topic,
+};
 use anyhow::{anyhow, Context, Result};
 use paste::paste;
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
-use std::{io::ErrorKind, sync::mpsc, thread};
-use tes3::esp::{Plugin, TES3Object};
+use rayon::iter::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator};
+use std::{
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread,
+};
+use tes3::esp::{Cell, CellFlags, LandscapeTexture, Plugin, TES3Object};
 pub(crate) mod merge;
 pub(crate) mod structs;
 use merge::{CellKey, IntermediateRecords};
-use structs::{InputHelper, ReadStats};
+use structs::{InputHelper, KnownIds, ReadStats};
 
 pub(super) fn get_records<'a>(
     plugins: &'a [PluginInfo],
     cfg: &'a Cfg,
     log: &mut Log,
-) -> Result<(LlCreatureRecords<'a>, LlItemRecords<'a>, IntermediateRecords<'a>, ReadStats)> {
+) -> Result<(LlCreatureRecords<'a>, LlItemRecords<'a>, IntermediateRecords<'a>, KnownIds, ReadStats)> {
     let mut ll_creatures = LlCreatureRecords::default();
     let mut ll_items = LlItemRecords::default();
     let mut intermediate_records = IntermediateRecords::default();
+    let mut known_ids = KnownIds::default();
     let mut stats = ReadStats::default();
     if cfg.compare_only {
-        return Ok((ll_creatures, ll_items, intermediate_records, stats));
+        return Ok((ll_creatures, ll_items, intermediate_records, known_ids, stats));
     }
     let mut helper = InputHelper::new(cfg, &plugins[0]);
     let mut progress = Progress::new(plugins.len(), cfg);
@@ -40,32 +48,52 @@ pub(super) fn get_records<'a>(
         }
     });
 
-    for (plugin_info, count) in plugins.iter().zip(1u64..) {
+    // COMMENT: plugins are parsed in parallel by batches in a separate thread, so the next batch is parsed while records of the
+    // COMMENT: previous one are processed strictly in load order; the channel holds at most one batch to bound memory usage
+    let (tx_plugin, rx_plugin) = mpsc::sync_channel(cfg.guts.read_batch_size);
+    let plugin_paths: Vec<PathBuf> = plugins.iter().map(|plugin_info| plugin_info.path.clone()).collect();
+    let read_batch_size = cfg.guts.read_batch_size;
+    let reader = thread::spawn(move || {
+        for batch in plugin_paths.chunks(read_batch_size) {
+            for loaded_plugin in batch.par_iter().map(|path| load_plugin(path)).collect::<Vec<_>>() {
+                // COMMENT: receiver is only dropped when processing fails, so there is no one to read the rest of plugins for
+                if tx_plugin.send(loaded_plugin).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    let mut read_count = 0;
+    for ((plugin_info, loaded_plugin), count) in plugins.iter().zip(rx_plugin.iter()).zip(1u64..) {
+        read_count += 1;
         if !progress.off {
             progress.tick(count);
         }
         helper.set_plugin(count, plugin_info);
-        let mut plugin = Plugin::new();
-        if let Err(error) = plugin.load_path(&plugin_info.path) {
-            if matches!(error.kind(), ErrorKind::InvalidData) {
-                if let Some(tag) = error.to_string().strip_prefix("Unexpected Tag: ") {
-                    if cfg.skip_unexpected_tags
-                        || (!cfg.no_skip_unexpected_tags_default
-                            && cfg.guts.skip_unexpected_tags_default.contains(&tag.to_lowercase()))
-                    {
-                        skipped_plugins.push(format!(
-                            "Plugin \"{}\" will be skipped, because it contains known unexpected record type: {}",
-                            &plugin_info.name, tag
-                        ));
-                        continue;
-                    } else {
-                        return Err(anyhow!("Failed to read plugin \"{}\"\n{}\nUse either --skip \"{0}\" to skip this plugin or --skip-unexpected-tags to skip all similar plugins\nConsider reporting the error to add this tag to the list of unexpected tags to skip by default", &plugin_info.name, error));
+        let plugin = match loaded_plugin {
+            Ok(plugin) => plugin,
+            Err(error) => {
+                if matches!(error.kind(), ErrorKind::InvalidData) {
+                    if let Some(tag) = error.to_string().strip_prefix("Unexpected Tag: ") {
+                        if cfg.skip_unexpected_tags
+                            || (!cfg.no_skip_unexpected_tags_default
+                                && cfg.guts.skip_unexpected_tags_default.contains(&tag.to_lowercase()))
+                        {
+                            skipped_plugins.push(format!(
+                                "Plugin \"{}\" will be skipped, because it contains known unexpected record type: {}",
+                                &plugin_info.name, tag
+                            ));
+                            continue;
+                        } else {
+                            return Err(anyhow!("Failed to read plugin \"{}\"\n{}\nUse either --skip \"{0}\" to skip this plugin or --skip-unexpected-tags to skip all similar plugins\nConsider reporting the error to add this tag to the list of unexpected tags to skip by default", &plugin_info.name, error));
+                        }
                     }
-                }
-            };
-            let text = format!("Failed to read plugin \"{}\"\n{}", &plugin_info.name, error);
-            err_or_ignore(text, cfg, log)?;
-            continue;
+                };
+                let text = format!("Failed to read plugin \"{}\"\n{}", &plugin_info.name, error);
+                err_or_ignore(text, cfg, log)?;
+                continue;
+            }
         };
         let Some(header) = plugin.objects.first() else {
             let text = format!("Failed to read plugin \"{}\"\nPlugin is empty", &plugin_info.name);
@@ -73,8 +101,37 @@ pub(super) fn get_records<'a>(
             continue;
         };
         stats.get_records(header);
+        // COMMENT: groundcover plugins only provide cells for multipatch, their references are never merged to not become masters
+        if plugin_info.groundcover {
+            stats.groundcover_plugins += 1;
+            for object in plugin.objects.into_iter() {
+                match object {
+                    TES3Object::Cell(mut cell) => {
+                        if !cell.references.is_empty() {
+                            tx_reference
+                                .send(cell.references)
+                                .with_context(|| "Bug: failed to send references to tx_reference channel")?;
+                            cell.references = hashbrown::HashMap::new();
+                        }
+                        intermediate_records.get_groundcover_cell(cell, helper.plugin_info);
+                    }
+                    _ => {
+                        tx_object.send(object).with_context(|| "Bug: failed to send object to tx_object channel")?;
+                    }
+                }
+            }
+            continue;
+        }
+        let reference_masters = if cfg.merge.references {
+            get_reference_masters(header, plugins, helper.plugin_index)
+        } else {
+            Vec::new()
+        };
+        let mut topic: Option<Topic> = None;
+        let mut land_textures = Arc::default();
 
         for object in plugin.objects.into_iter() {
+            known_ids.push(&object);
             macro_rules! match_object {
                 ($($type:ident:$obj:ident),+) => {
                     paste! {
@@ -90,8 +147,35 @@ pub(super) fn get_records<'a>(
                                     tx_reference.send(cell.references).with_context(|| "Bug: failed to send references to tx_reference channel")?;
                                     cell.references = hashbrown::HashMap::new();
                                 }
+                                if cfg.merge.references {
+                                    rebase_references(&mut cell, &reference_masters, helper.plugin_info, cfg, log)?;
+                                }
                                 intermediate_records.get_cell(cell, helper.plugin_info);
                             }
+                            TES3Object::Dialogue(dialogue) if cfg.merge.dial => {
+                                if let Some(topic) = topic.take() {
+                                    intermediate_records.get_dial(topic, helper.plugin_info);
+                                }
+                                topic = Some(Topic { dialogue, infos: Vec::new() });
+                            }
+                            TES3Object::DialogueInfo(info) if cfg.merge.dial => match topic {
+                                Some(ref mut topic) => topic.infos.push(info),
+                                None => {
+                                    tx_object.send(TES3Object::DialogueInfo(info)).with_context(|| "Bug: failed to send object to tx_object channel")?;
+                                }
+                            },
+                            TES3Object::LandscapeTexture(ltex) if cfg.merge.ltex || cfg.merge.land => {
+                                if cfg.merge.land {
+                                    Arc::make_mut(&mut land_textures).insert(ltex.index as u16 + 1, ltex.clone());
+                                }
+                                // COMMENT: local index is dropped, so that LTEX records of plugins with different indices are equal
+                                if cfg.merge.ltex {
+                                    intermediate_records.get_ltex(LandscapeTexture { index: 0, ..ltex }, helper.plugin_info);
+                                }
+                            }
+                            TES3Object::Landscape(landscape) if cfg.merge.land => {
+                                intermediate_records.get_land(Land { landscape, textures: Arc::clone(&land_textures) }, helper.plugin_info);
+                            }
                             TES3Object::Creature(mut crea) if get_crea => {
                                 if crea.scale == Some(1.0) {
                                     crea.scale = None;
@@ -111,10 +195,12 @@ pub(super) fn get_records<'a>(
             match_object!(
                 gmst:GameSetting,
                 clas:Class,
+                fact:Faction,
                 race:Race,
                 soun:Sound,
                 skil:Skill,
                 mgef:MagicEffect,
+                regn:Region,
                 bsgn:Birthsign,
                 spel:Spell,
                 stat:Static,
@@ -136,16 +222,29 @@ pub(super) fn get_records<'a>(
                 ingr:Ingredient,
                 book:Book,
                 alch:Alchemy,
-                sndg:SoundGen
+                pgrd:PathGrid,
+                sndg:SoundGen,
+                scpt:Script
             );
         }
+        if let Some(topic) = topic {
+            intermediate_records.get_dial(topic, helper.plugin_info);
+        }
     }
 
+    // COMMENT: the loop above silently stops when the reader stops sending, so the list of plugins may be truncated
+    if reader.join().is_err() {
+        return Err(anyhow!("Bug: plugins reading thread panicked"));
+    }
+    if read_count != plugins.len() {
+        return Err(anyhow!("Bug: only {read_count} of {} plugins were read", plugins.len()));
+    }
     if get_cell {
         let skip_0x40 = !cfg.guts.no_skip_unknown_cell_flags;
         intermediate_records
             .cell
             .par_iter_mut()
+            .chain(intermediate_records.groundcover_cell.par_iter_mut())
             .try_for_each(|(key, map)| preprocess_cell(key, map, skip_0x40))
             .with_context(|| "Bug: failed to preprocess cells")?;
     }
@@ -154,7 +253,67 @@ pub(super) fn get_records<'a>(
     if !skipped_plugins.is_empty() {
         msg(skipped_plugins.join("\n"), MsgTone::Neutral, 0, cfg, log)?;
     }
-    Ok((ll_creatures, ll_items, intermediate_records, stats))
+    Ok((ll_creatures, ll_items, intermediate_records, known_ids, stats))
+}
+
+fn load_plugin(path: &Path) -> io::Result<Plugin> {
+    let mut plugin = Plugin::new();
+    plugin.load_path(path).map(|_| plugin)
+}
+
+// COMMENT: master index of a reference is local to the plugin, so it's replaced with the index of the plugin in the load order
+fn get_reference_masters(header: &TES3Object, plugins: &[PluginInfo], plugin_index: usize) -> Vec<Option<u32>> {
+    let mut res = vec![Some(plugin_index as u32)];
+    if let TES3Object::Header(header) = header {
+        res.extend(header.masters.iter().map(|(name, _)| {
+            let name_lowercased = name.to_lowercase();
+            plugins[..plugin_index]
+                .iter()
+                .rposition(|plugin| plugin.name_lowercased == name_lowercased)
+                .map(|index| index as u32)
+        }));
+    }
+    res
+}
+
+// COMMENT: references of masters that are not in the load order can't be merged, so they are dropped
+fn rebase_references(
+    cell: &mut Cell,
+    reference_masters: &[Option<u32>],
+    plugin_info: &PluginInfo,
+    cfg: &Cfg,
+    log: &mut Log,
+) -> Result<()> {
+    let mut dropped = Vec::new();
+    cell.references = std::mem::take(&mut cell.references)
+        .into_values()
+        .filter_map(|mut reference| match reference_masters.get(reference.mast_index as usize).copied().flatten() {
+            Some(mast_index) => {
+                reference.mast_index = mast_index;
+                Some(((mast_index, reference.refr_index), reference))
+            }
+            None => {
+                dropped.push(format!("\"{}\"({}:{})", reference.id, reference.mast_index, reference.refr_index));
+                None
+            }
+        })
+        .collect();
+    if !dropped.is_empty() {
+        dropped.sort();
+        let text = format!(
+            "Dropped {} reference(s) of cell {:?} from plugin \"{}\" because their masters are not in the load order: {}",
+            dropped.len(),
+            if cell.data.flags.contains(CellFlags::IS_INTERIOR) {
+                cell.name.clone()
+            } else {
+                format!("{:?}", cell.data.grid)
+            },
+            plugin_info.name,
+            dropped.join(", ")
+        );
+        msg(text, MsgTone::Bad, 0, cfg, log)?;
+    }
+    Ok(())
 }
 
 fn preprocess_cell(key: &CellKey, map: &mut CellRecordMap<'_>, skip_0x40: bool) -> Result<()> {
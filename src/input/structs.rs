@@ -1,6 +1,6 @@
 use crate::{Cfg, PluginInfo};
-use hashbrown::HashMap;
-use tes3::esp::TES3Object;
+use hashbrown::{HashMap, HashSet};
+use tes3::esp::{ObjectFlags, TES3Object};
 
 pub(crate) type PluginName<'a> = &'a String;
 pub(crate) type ResponsiblePlugins<'a> = Vec<PluginName<'a>>;
@@ -38,6 +38,42 @@ pub(crate) struct HelperCreatures {
 
 pub(crate) type HelperItems = HelperCreatures;
 
+// COMMENT: ids of records that may be placed into leveled lists, collected from every plugin to find dangling subrecords
+#[derive(Default)]
+pub(crate) struct KnownIds {
+    pub(crate) creatures: HashSet<String>,
+    pub(crate) items: HashSet<String>,
+    pub(crate) leveled_creatures: HashSet<String>,
+}
+
+impl KnownIds {
+    pub(super) fn push(&mut self, tes3object: &TES3Object) {
+        // COMMENT: only the last loaded version of a leveled creature list decides whether it survives
+        if let TES3Object::LeveledCreature(levc) = tes3object {
+            if levc.flags.contains(ObjectFlags::DELETED) {
+                self.leveled_creatures.remove(&levc.id.to_lowercase());
+            } else {
+                self.leveled_creatures.insert(levc.id.to_lowercase());
+            }
+        }
+        macro_rules! push {
+            ($($kind:ident: $($obj:ident),+);+) => {
+                match tes3object {
+                    $($(TES3Object::$obj(record) => {
+                        self.$kind.insert(record.id.to_lowercase());
+                    })+)+
+                    _ => {}
+                }
+            };
+        }
+        push!(
+            creatures: Creature, Npc, LeveledCreature;
+            items: Weapon, Armor, Clothing, MiscItem, Book, Alchemy, Ingredient, Light, Lockpick, Probe, RepairItem, Apparatus,
+                LeveledItem
+        );
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct PluginReadStats {
     pub(crate) total: u32,
@@ -50,6 +86,7 @@ pub(crate) type RecordReadStats = PluginReadStats;
 pub(crate) struct ReadStats {
     pub(crate) plugins: PluginReadStats,
     pub(crate) records: RecordReadStats,
+    pub(crate) groundcover_plugins: u32,
 }
 
 impl ReadStats {
@@ -1,2 +1,8 @@
+pub(super) mod explain;
+pub(super) mod graph;
 pub(super) mod messages;
+pub(super) mod nesting;
 pub(super) mod records;
+pub(super) mod simulation;
+pub(super) mod strategy;
+pub(super) mod weights;
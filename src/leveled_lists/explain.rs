@@ -0,0 +1,174 @@
+use super::weights::{get_weights, Weights};
+use crate::{msg, Cfg, LlElement, Log, MsgTone, PluginName};
+use anyhow::Result;
+use hashbrown::HashSet;
+use std::fmt::Write as _;
+
+#[cfg(test)]
+mod tests;
+
+// COMMENT: version of the explained leveled list in a single plugin
+pub(crate) struct ExplainStep<'a> {
+    pub(crate) plugin_name: PluginName<'a>,
+    pub(crate) count: usize,
+    pub(crate) added: Vec<LlElement>,
+    pub(crate) removed: Vec<(LlElement, usize)>,
+    pub(crate) removal_ignored: Option<&'static str>,
+}
+
+impl<'a> ExplainStep<'a> {
+    pub(crate) fn new(
+        plugin_name: PluginName<'a>,
+        list: &[LlElement],
+        added: &[LlElement],
+        first: &[(LlElement, LlElement)],
+        first_weights: &Weights,
+        object_weights: &Weights,
+        removal_ignored: Option<&'static str>,
+    ) -> ExplainStep<'a> {
+        let mut seen = HashSet::new();
+        let removed = first
+            .iter()
+            .filter(|(subrecord_lowercased, _)| seen.insert(subrecord_lowercased))
+            .filter_map(|(subrecord_lowercased, subrecord)| {
+                let object_weight = object_weights.get(subrecord_lowercased).copied().unwrap_or(0);
+                first_weights[subrecord_lowercased]
+                    .checked_sub(object_weight)
+                    .filter(|removed| *removed > 0)
+                    .map(|removed| (subrecord.clone(), removed))
+            })
+            .collect();
+        ExplainStep {
+            plugin_name,
+            count: list.len(),
+            added: added.to_vec(),
+            removed,
+            removal_ignored,
+        }
+    }
+}
+
+pub(crate) enum DeleteOutcome {
+    NotAllowed,
+    Resolved(f64, f64),
+    ThresholdSkipped(f64, f64),
+    ThresholdWarning(f64, f64),
+    Deleted,
+}
+
+// COMMENT: accumulates provenance of the explained leveled list, shown when the list is emitted
+pub(crate) struct Explanation {
+    text: String,
+    list: Vec<LlElement>,
+}
+
+impl Explanation {
+    pub(crate) fn new(id: &str, log_t: &str, steps: &[ExplainStep], list: &[LlElement]) -> Result<Option<Explanation>> {
+        if steps.is_empty() {
+            return Ok(None);
+        }
+        let mut text = String::new();
+        write!(text, "Explanation of leveled list \"{id}\"({log_t}) in load order:")?;
+        for (index, step) in steps.iter().enumerate() {
+            write!(text, "\n\t{}. \"{}\": {} subrecords", index + 1, step.plugin_name, step.count)?;
+            if index == 0 {
+                write!(text, ", initial list")?;
+                continue;
+            }
+            if !step.added.is_empty() {
+                write!(text, "\n\t\tadded: {}", join_subrecords(step.added.iter().map(|subrecord| (subrecord, 1))))?;
+            }
+            if !step.removed.is_empty() {
+                write!(text, "\n\t\tremoved: {}", join_subrecords(step.removed.iter().map(|(subrecord, count)| (subrecord, *count))))?;
+                if let Some(reason) = step.removal_ignored {
+                    write!(text, " (ignored due to {reason})")?;
+                }
+            }
+            if step.added.is_empty() && step.removed.is_empty() {
+                write!(text, ", nothing added or removed")?;
+            }
+        }
+        Ok(Some(Explanation { text, list: list.to_vec() }))
+    }
+
+    pub(crate) fn delete(&mut self, outcome: Option<DeleteOutcome>, list: &[LlElement], cfg: &Cfg) -> Result<()> {
+        let text = match outcome {
+            None if cfg.no_delete => String::from("subrecord deletion is disabled with --no-delete"),
+            None => return Ok(()),
+            Some(DeleteOutcome::NotAllowed) => String::from(
+                "removals were not applied because initial plugin is not in --always-delete and --extended-delete is off",
+            ),
+            Some(DeleteOutcome::Resolved(ratio, threshold)) => format!(
+                "removals were not applied because {ratio:.0}% of initial subrecords exceeds threshold {threshold:.0}%"
+            ),
+            Some(DeleteOutcome::ThresholdSkipped(ratio, threshold)) => format!(
+                "removals were applied despite {ratio:.0}% exceeding threshold {threshold:.0}% due to --always-delete"
+            ),
+            Some(DeleteOutcome::ThresholdWarning(ratio, threshold)) => {
+                format!("removals were applied with warning: {ratio:.0}% exceeds threshold {threshold:.0}%")
+            }
+            Some(DeleteOutcome::Deleted) => String::from("removals were applied"),
+        };
+        write!(self.text, "\n\tDeletion: {text}")?;
+        self.update("Deletion", list)
+    }
+
+    pub(crate) fn update(&mut self, stage: &str, list: &[LlElement]) -> Result<()> {
+        self.changes(stage, list)?;
+        self.list = list.to_vec();
+        Ok(())
+    }
+
+    // COMMENT: writes subrecords that were added or removed since the previous stage
+    pub(crate) fn changes(&mut self, stage: &str, list: &[LlElement]) -> Result<()> {
+        let (added, removed) = diff(&self.list, list);
+        if !added.is_empty() || !removed.is_empty() {
+            write!(self.text, "\n\t{stage}:")?;
+            if !removed.is_empty() {
+                write!(self.text, "\n\t\t-{}", join_subrecords(removed.into_iter()))?;
+            }
+            if !added.is_empty() {
+                write!(self.text, "\n\t\t+{}", join_subrecords(added.into_iter()))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn show(mut self, flags: String, chance_none: u8, placement: &str, cfg: &Cfg, log: &mut Log) -> Result<()> {
+        write!(
+            self.text,
+            "\n\tResult: {placement}, list flags {flags}(strategy: {}), chance_none {chance_none}(strategy: {}), {} subrecords:",
+            cfg.list_flags_strategy.name(),
+            cfg.chance_none_strategy.name(),
+            self.list.len()
+        )?;
+        for (name, level) in self.list.iter() {
+            write!(self.text, "\n\t\t{level:>3} {name}")?;
+        }
+        msg(&self.text, MsgTone::Neutral, 0, cfg, log)
+    }
+}
+
+fn diff<'b>(old: &'b [LlElement], new: &'b [LlElement]) -> (Vec<(&'b LlElement, usize)>, Vec<(&'b LlElement, usize)>) {
+    let lowercased = |list: &[LlElement]| list.iter().map(|(name, level)| (name.to_lowercase(), *level)).collect::<Vec<_>>();
+    let (old_weights, new_weights) = (get_weights(&lowercased(old)), get_weights(&lowercased(new)));
+    let changes = |from: &'b [LlElement], from_weights: &Weights, to_weights: &Weights| {
+        let mut seen = HashSet::new();
+        from.iter()
+            .filter_map(|subrecord| {
+                let subrecord_lowercased = (subrecord.0.to_lowercase(), subrecord.1);
+                let from_weight = from_weights[&subrecord_lowercased];
+                let to_weight = to_weights.get(&subrecord_lowercased).copied().unwrap_or(0);
+                (from_weight > to_weight && seen.insert(subrecord_lowercased)).then_some((subrecord, from_weight - to_weight))
+            })
+            .collect::<Vec<_>>()
+    };
+    (changes(new, &new_weights, &old_weights), changes(old, &old_weights, &new_weights))
+}
+
+fn join_subrecords<'b>(subrecords: impl Iterator<Item = (&'b LlElement, usize)>) -> String {
+    subrecords
+        .map(|((name, level), count)| if count > 1 { format!("{name}({level}) x{count}") } else { format!("{name}({level})") })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
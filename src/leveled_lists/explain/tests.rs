@@ -0,0 +1,47 @@
+use super::{ExplainStep, Explanation};
+use crate::{leveled_lists::weights::get_weights, LlElement};
+use pretty_assertions::assert_eq;
+
+fn list(src: &[(&str, u16)]) -> Vec<LlElement> {
+    src.iter().map(|(name, level)| (name.to_string(), *level)).collect()
+}
+
+fn lowercased(list: &[LlElement]) -> Vec<LlElement> {
+    list.iter().map(|(name, level)| (name.to_lowercase(), *level)).collect()
+}
+
+#[test]
+fn step_removed_subrecords() {
+    let plugin_name = String::from("Patch.esp");
+    let first_list = list(&[("Rat", 1), ("rat", 1), ("mudcrab", 2)]);
+    let first = lowercased(&first_list).into_iter().zip(first_list.iter().cloned()).collect::<Vec<_>>();
+    let first_weights = get_weights(&lowercased(&first_list));
+    let object = list(&[("rat", 1), ("kagouti", 5)]);
+    let object_weights = get_weights(&lowercased(&object));
+    let step = ExplainStep::new(&plugin_name, &object, &object[1..], &first, &first_weights, &object_weights, None);
+    assert_eq!(step.count, 2);
+    assert_eq!(step.added, list(&[("kagouti", 5)]));
+    assert_eq!(step.removed, [(("Rat".to_string(), 1), 1), (("mudcrab".to_string(), 2), 1)]);
+}
+
+#[test]
+fn explanation_changes() {
+    let plugin_name = String::from("Morrowind.esm");
+    let merged = list(&[("rat", 1), ("rat", 1), ("mudcrab", 5)]);
+    let step = ExplainStep::new(&plugin_name, &merged, &merged, &[], &get_weights(&[]), &get_weights(&[]), None);
+    let mut explanation = Explanation::new("l_vermin", "C", &[step], &merged).unwrap().unwrap();
+    explanation.update("Deleveled", &list(&[("rat", 1), ("rat", 1), ("mudcrab", 1)])).unwrap();
+    explanation.changes("Releveled into relevel output plugin", &list(&[("rat", 2), ("rat", 2), ("mudcrab", 2)])).unwrap();
+    assert_eq!(
+        explanation.text,
+        "Explanation of leveled list \"l_vermin\"(C) in load order:
+\t1. \"Morrowind.esm\": 3 subrecords, initial list
+\tDeleveled:
+\t\t-mudcrab(5)
+\t\t+mudcrab(1)
+\tReleveled into relevel output plugin:
+\t\t-rat(1) x2, mudcrab(1)
+\t\t+rat(2) x2, mudcrab(2)"
+    );
+    assert_eq!(explanation.list, list(&[("rat", 1), ("rat", 1), ("mudcrab", 1)]));
+}
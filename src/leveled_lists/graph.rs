@@ -0,0 +1,155 @@
+use crate::{create_dir_early, msg, Cfg, LlElement, Log, MsgTone};
+use anyhow::{Context, Result};
+use fs_err::write;
+use hashbrown::HashSet;
+use std::{fmt::Write as _, path::Path};
+
+#[cfg(test)]
+mod tests;
+
+struct GraphList<'a> {
+    id: String,
+    record_type: &'static str,
+    plugins: Vec<&'a str>,
+    list: Vec<LlElement>,
+}
+
+// COMMENT: merged leveled lists in order of processing, nested lists and leaf ids are told apart on export
+#[derive(Default)]
+pub(crate) struct LlGraph<'a>(Vec<GraphList<'a>>);
+
+impl<'a> LlGraph<'a> {
+    pub(crate) fn push(&mut self, id: &str, record_type: &'static str, plugins: Vec<&'a str>, list: &[LlElement]) {
+        self.0.push(GraphList {
+            id: id.to_owned(),
+            record_type,
+            plugins,
+            list: list.to_vec(),
+        });
+    }
+
+    fn list_ids(&self) -> HashSet<String> {
+        self.0.iter().map(|list| list.id.to_lowercase()).collect()
+    }
+
+    // COMMENT: (id, level, count) with duplicate subrecords collapsed into a single edge
+    fn edges(list: &[LlElement]) -> Vec<(&str, u16, usize)> {
+        let mut edges: Vec<(&str, u16, usize)> = Vec::new();
+        for (name, level) in list {
+            match edges.iter_mut().find(|(id, lvl, _)| *lvl == *level && id.eq_ignore_ascii_case(name)) {
+                Some(edge) => edge.2 += 1,
+                None => edges.push((name, *level, 1)),
+            }
+        }
+        edges
+    }
+
+    fn leaves(&self, list_ids: &HashSet<String>) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        let mut leaves = Vec::new();
+        for (name, _) in self.0.iter().flat_map(|list| list.list.iter()) {
+            let name_low = name.to_lowercase();
+            if !list_ids.contains(&name_low) && seen.insert(name_low) {
+                leaves.push(name.as_str());
+            }
+        }
+        leaves
+    }
+
+    fn to_dot(&self) -> Result<String> {
+        let list_ids = self.list_ids();
+        let mut text = String::from("digraph leveled_lists {\n    rankdir=LR;\n");
+        for list in self.0.iter() {
+            writeln!(
+                text,
+                "    \"{}\" [shape=box, label=\"{} ({})\\n{}\"];",
+                dot_escape(&list.id.to_lowercase()),
+                dot_escape(&list.id),
+                list.record_type.to_uppercase(),
+                dot_escape(&list.plugins.join(", "))
+            )?;
+        }
+        for leaf in self.leaves(&list_ids) {
+            writeln!(text, "    \"{}\" [shape=ellipse, label=\"{}\"];", dot_escape(&leaf.to_lowercase()), dot_escape(leaf))?;
+        }
+        for list in self.0.iter() {
+            for (name, level, count) in LlGraph::edges(&list.list) {
+                let label = if count > 1 { format!("{level} x{count}") } else { level.to_string() };
+                writeln!(
+                    text,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    dot_escape(&list.id.to_lowercase()),
+                    dot_escape(&name.to_lowercase()),
+                    label
+                )?;
+            }
+        }
+        text.push_str("}\n");
+        Ok(text)
+    }
+
+    fn to_json(&self) -> Result<String> {
+        let list_ids = self.list_ids();
+        let mut nodes = Vec::new();
+        for list in self.0.iter() {
+            let plugins = list.plugins.iter().map(|plugin| json_string(plugin)).collect::<Vec<String>>();
+            let subrecords = LlGraph::edges(&list.list)
+                .into_iter()
+                .map(|(name, level, count)| format!("{{\"id\": {}, \"level\": {level}, \"count\": {count}}}", json_string(name)))
+                .collect::<Vec<String>>();
+            nodes.push(format!(
+                "    {{\"id\": {}, \"kind\": \"list\", \"type\": \"{}\", \"plugins\": [{}], \"subrecords\": [{}]}}",
+                json_string(&list.id),
+                list.record_type.to_uppercase(),
+                plugins.join(", "),
+                subrecords.join(", ")
+            ));
+        }
+        for leaf in self.leaves(&list_ids) {
+            nodes.push(format!("    {{\"id\": {}, \"kind\": \"leaf\"}}", json_string(leaf)));
+        }
+        let mut text = String::new();
+        write!(text, "{{\n  \"nodes\": [\n{}\n  ]\n}}\n", nodes.join(",\n"))?;
+        Ok(text)
+    }
+
+    pub(crate) fn write(&self, cfg: &Cfg, log: &mut Log) -> Result<()> {
+        if !cfg.graph_dot.is_empty() {
+            write_graph(&cfg.graph_dot, "DOT", &self.to_dot()?, cfg, log)?;
+        }
+        if !cfg.graph_json.is_empty() {
+            write_graph(&cfg.graph_json, "JSON", &self.to_json()?, cfg, log)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_graph(path: &str, format: &str, text: &str, cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let path = Path::new(path);
+    create_dir_early(path, "graph")?;
+    write(path, text).with_context(|| format!("Failed to write leveled lists graph to \"{}\"", path.display()))?;
+    let text = format!("Leveled lists graph in {format} format was written to \"{}\"", path.display());
+    msg(text, MsgTone::Good, 0, cfg, log)
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn json_string(text: &str) -> String {
+    let mut res = String::with_capacity(text.len() + 2);
+    res.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
@@ -0,0 +1,48 @@
+use super::LlGraph;
+use crate::LlElement;
+use pretty_assertions::assert_eq;
+
+fn graph() -> LlGraph<'static> {
+    let list = |src: &[(&str, u16)]| src.iter().map(|(name, level)| (name.to_string(), *level)).collect::<Vec<LlElement>>();
+    let mut graph = LlGraph::default();
+    graph.push("L_Loot", "levi", vec!["Morrowind.esm", "Tamriel_Data.esm"], &list(&[("gold_001", 1), ("l_daggers", 5)]));
+    graph.push("l_daggers", "levi", vec!["Morrowind.esm"], &list(&[("iron dagger", 1), ("Iron Dagger", 1), ("glass \"dagger\"", 10)]));
+    graph
+}
+
+#[test]
+fn dot() {
+    assert_eq!(
+        graph().to_dot().unwrap(),
+        r#"digraph leveled_lists {
+    rankdir=LR;
+    "l_loot" [shape=box, label="L_Loot (LEVI)\nMorrowind.esm, Tamriel_Data.esm"];
+    "l_daggers" [shape=box, label="l_daggers (LEVI)\nMorrowind.esm"];
+    "gold_001" [shape=ellipse, label="gold_001"];
+    "iron dagger" [shape=ellipse, label="iron dagger"];
+    "glass \"dagger\"" [shape=ellipse, label="glass \"dagger\""];
+    "l_loot" -> "gold_001" [label="1"];
+    "l_loot" -> "l_daggers" [label="5"];
+    "l_daggers" -> "iron dagger" [label="1 x2"];
+    "l_daggers" -> "glass \"dagger\"" [label="10"];
+}
+"#
+    );
+}
+
+#[test]
+fn json() {
+    assert_eq!(
+        graph().to_json().unwrap(),
+        r#"{
+  "nodes": [
+    {"id": "L_Loot", "kind": "list", "type": "LEVI", "plugins": ["Morrowind.esm", "Tamriel_Data.esm"], "subrecords": [{"id": "gold_001", "level": 1, "count": 1}, {"id": "l_daggers", "level": 5, "count": 1}]},
+    {"id": "l_daggers", "kind": "list", "type": "LEVI", "plugins": ["Morrowind.esm"], "subrecords": [{"id": "iron dagger", "level": 1, "count": 2}, {"id": "glass \"dagger\"", "level": 10, "count": 1}]},
+    {"id": "gold_001", "kind": "leaf"},
+    {"id": "iron dagger", "kind": "leaf"},
+    {"id": "glass \"dagger\"", "kind": "leaf"}
+  ]
+}
+"#
+    );
+}
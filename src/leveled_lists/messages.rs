@@ -1,4 +1,5 @@
-use crate::{append_for_details_or_check_log, msg, plural, Cfg, ListCounts, LlElement, Log, MsgTone, PluginName, ResponsiblePlugins};
+use super::weights::WeightDelta;
+use crate::{append_for_details_or_check_log, msg, plural, Cfg, ListCounts, LlElement, Log, MsgTone, PluginName};
 use anyhow::{anyhow, Result};
 use std::{cmp::max, fmt::Write as _};
 
@@ -7,8 +8,13 @@ pub(crate) struct LlMessages<'a> {
     pub(crate) threshold_skipped: ThresholdMessages<'a>,
     pub(crate) threshold_warnings: ThresholdMessages<'a>,
     pub(crate) untouched_lists: Vec<UntouchedList<'a>>,
+    pub(crate) merged_values: Vec<MergedValues<'a>>,
     pub(crate) deleted_subrecords: Vec<DeletedSubrecords<'a>>,
+    pub(crate) edited_subrecords: Vec<EditedSubrecords<'a>>,
     pub(crate) deleveled_subrecords: Vec<DeleveledSubrecords<'a>>,
+    pub(crate) releveled_subrecords: Vec<ReleveledSubrecords<'a>>,
+    pub(crate) dangling_subrecords: Vec<DanglingSubrecords<'a>>,
+    pub(crate) nesting_problems: Vec<NestingProblem<'a>>,
 }
 
 impl<'a> LlMessages<'a> {
@@ -18,13 +24,18 @@ impl<'a> LlMessages<'a> {
             threshold_skipped: ThresholdMessages::new(ThresholdMessageKind::Skipped),
             threshold_warnings: ThresholdMessages::new(ThresholdMessageKind::Warning),
             untouched_lists: Vec::new(),
+            merged_values: Vec::new(),
             deleted_subrecords: Vec::new(),
+            edited_subrecords: Vec::new(),
             deleveled_subrecords: Vec::new(),
+            releveled_subrecords: Vec::new(),
+            dangling_subrecords: Vec::new(),
+            nesting_problems: Vec::new(),
         }
     }
 
     pub(crate) fn exit_code(&self) -> i32 {
-        if self.threshold_warnings.is_empty() {
+        if self.threshold_warnings.is_empty() && self.nesting_problems.is_empty() {
             0
         } else {
             2
@@ -37,9 +48,21 @@ impl<'a> LlMessages<'a> {
             Ok(None) => return Ok(()),
             Err(_) => return Err(anyhow!("Bug: messages list contains nothing")),
         };
+        if !self.dangling_subrecords.is_empty() {
+            show_dangling_subrecords(&mut text, &self.dangling_subrecords, cfg, log)?;
+        }
+        if !self.nesting_problems.is_empty() {
+            show_nesting_problems(&mut text, &self.nesting_problems, cfg, log)?;
+        }
         if !self.deleted_subrecords.is_empty() {
             show_deleted_subrecords(&mut text, &self.deleted_subrecords, counts.merge.deleted_subrecord, cfg, log)?;
         }
+        if !self.edited_subrecords.is_empty() {
+            show_edited_subrecords(&mut text, &self.edited_subrecords, counts.merge.edited_subrecord, cfg, log)?;
+        }
+        if !self.merged_values.is_empty() {
+            show_merged_values(&mut text, &self.merged_values, cfg, log)?;
+        }
         if !self.untouched_lists.is_empty() {
             show_untouched_lists(&mut text, &self.untouched_lists, cfg, log)?;
         }
@@ -53,7 +76,12 @@ impl<'a> LlMessages<'a> {
             show_threshold_messages(&mut text, &self.threshold_warnings, cfg, log)?;
         }
         if !self.deleveled_subrecords.is_empty() {
-            show_deleveled_subrecords(&mut text, &self.deleveled_subrecords, counts.delev.deleveled_subrecord, cfg, log)?;
+            let (count, details) = (counts.delev.deleveled_subrecord, cfg.guts.verboseness_details_deleveled_subrecords);
+            show_changed_level_subrecords(&mut text, &self.deleveled_subrecords, count, "deleveled", details, cfg, log)?;
+        }
+        if !self.releveled_subrecords.is_empty() {
+            let (count, details) = (counts.relevel.releveled_subrecord, cfg.guts.verboseness_details_releveled_subrecords);
+            show_changed_level_subrecords(&mut text, &self.releveled_subrecords, count, "releveled", details, cfg, log)?;
         }
         Ok(())
     }
@@ -61,11 +89,16 @@ impl<'a> LlMessages<'a> {
     fn create_text_with_enough_capacity(&self, counts: &ListCounts, cfg: &Cfg) -> Result<Option<String>> {
         match [
             counts.merge.deleted_subrecord,
+            counts.merge.edited_subrecord,
             self.untouched_lists.len(),
+            self.merged_values.len(),
             self.threshold_resolved.messages.len(),
             self.threshold_skipped.messages.len(),
             self.threshold_warnings.messages.len(),
             counts.delev.deleveled_subrecord,
+            counts.relevel.releveled_subrecord,
+            self.dangling_subrecords.iter().map(|list| list.subrecords.len()).sum(),
+            self.nesting_problems.len(),
         ]
         .iter()
         .max()
@@ -97,11 +130,11 @@ impl<'a> ThresholdMessages<'a> {
         log_t: &'a str,
         id: String,
         initial_plugin: PluginName<'a>,
-        delete: &[(LlElement, LlElement, ResponsiblePlugins<'a>)],
+        delete: &[WeightDelta<'a>],
     ) {
         let mut plugins = Vec::new();
-        for (_, _, responsible_plugins) in delete {
-            for responsible_plugin in responsible_plugins.iter() {
+        for delta in delete {
+            for responsible_plugin in delta.responsible_plugins.iter() {
                 if !plugins.contains(&responsible_plugin) {
                     plugins.push(responsible_plugin);
                 }
@@ -143,7 +176,7 @@ pub(crate) struct DeletedSubrecords<'a> {
     pub(crate) log_t: &'a str,
     pub(crate) id: String,
     pub(crate) initial_plugin: PluginName<'a>,
-    pub(crate) subrecords: Vec<(LlElement, ResponsiblePluginsStr<'a>)>,
+    pub(crate) subrecords: Vec<(LlElement, (usize, usize), ResponsiblePluginsStr<'a>)>,
 }
 
 pub(crate) struct UntouchedList<'a> {
@@ -153,16 +186,53 @@ pub(crate) struct UntouchedList<'a> {
     pub(crate) last_plugin: &'a str,
 }
 
+pub(crate) struct MergedValues<'a> {
+    pub(crate) log_t: &'a str,
+    pub(crate) id: String,
+    pub(crate) field: &'static str,
+    pub(crate) strategy: &'static str,
+    pub(crate) merged: String,
+    pub(crate) values: Vec<(String, PluginName<'a>)>,
+}
+
+pub(crate) struct EditedSubrecords<'a> {
+    pub(crate) log_t: &'a str,
+    pub(crate) id: String,
+    pub(crate) initial_plugin: PluginName<'a>,
+    pub(crate) subrecords: Vec<(LlElement, Option<String>)>,
+}
+
+pub(crate) struct DanglingSubrecords<'a> {
+    pub(crate) log_t: &'a str,
+    pub(crate) id: String,
+    pub(crate) subrecords: Vec<(LlElement, PluginName<'a>)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum NestingProblemKind {
+    Cycle,
+    TooDeep,
+}
+
+pub(crate) struct NestingProblem<'a> {
+    pub(crate) log_t: &'a str,
+    pub(crate) kind: NestingProblemKind,
+    pub(crate) chain: Vec<String>,
+}
+
 type NewLevel = u16;
+type RuleName<'a> = &'a str;
 
 #[derive(Debug)]
 pub(crate) struct DeleveledSubrecords<'a> {
     pub(crate) log_t: &'a str,
     pub(crate) id: String,
     pub(crate) initial_plugin: PluginName<'a>,
-    pub(crate) subrecords: Vec<(LlElement, NewLevel)>,
+    pub(crate) subrecords: Vec<(LlElement, NewLevel, Option<RuleName<'a>>)>,
 }
 
+pub(crate) type ReleveledSubrecords<'a> = DeleveledSubrecords<'a>;
+
 fn show_deleted_subrecords(
     text: &mut String,
     list: &[DeletedSubrecords],
@@ -184,20 +254,65 @@ fn show_deleted_subrecords(
     if !(cfg.no_log && cfg.verbose < details) {
         writeln!(
             text,
-            "\n\t{:>2} {:<32} {:<1} {:<32} {:<16} RESPONSIBLE PLUGINS",
-            "LV", "DELETED SUBRECORD", "T", "LEVELED LIST", "INITIAL PLUGIN"
+            "\n\t{:>2} {:<32} {:<7} {:<1} {:<32} {:<16} RESPONSIBLE PLUGINS",
+            "LV", "DELETED SUBRECORD", "WEIGHT", "T", "LEVELED LIST", "INITIAL PLUGIN"
         )?;
         for list_item in list.iter() {
             for subrecord in list_item.subrecords.iter() {
                 writeln!(
                     text,
-                    "\t{:>2} {:<32} {:<1} {:<32} {:<16} {}",
+                    "\t{:>2} {:<32} {:<7} {:<1} {:<32} {:<16} {}",
                     subrecord.0 .1,
                     subrecord.0 .0,
+                    format!("{} → {}", subrecord.1 .0, subrecord.1 .1),
                     list_item.log_t,
                     list_item.id,
                     list_item.initial_plugin,
-                    subrecord.1.join(", "),
+                    subrecord.2.join(", "),
+                )?;
+            }
+        }
+        msg_and_clear(text, MsgTone::Neutral, details, cfg, log)?;
+    }
+    Ok(())
+}
+
+fn show_edited_subrecords(
+    text: &mut String,
+    list: &[EditedSubrecords],
+    subrecords_count: usize,
+    cfg: &Cfg,
+    log: &mut Log,
+) -> Result<()> {
+    let details = cfg.guts.verboseness_details_edited_subrecords;
+    text.clear();
+    write!(
+        text,
+        "{} subrecord{} from {} leveled list{} {} removed or replaced",
+        subrecords_count,
+        plural("s", subrecords_count)?,
+        list.len(),
+        plural("s", list.len())?,
+        plural("were", subrecords_count)?,
+    )?;
+    msg_with_details_suggestion(text, MsgTone::Good, 0, details, cfg, log)?;
+    if !(cfg.no_log && cfg.verbose < details) {
+        writeln!(
+            text,
+            "\n\t{:>2} {:<32} {:<32} {:<1} {:<32} INITIAL PLUGIN",
+            "LV", "OLD SUBRECORD", "NEW SUBRECORD", "T", "LEVELED LIST"
+        )?;
+        for list_item in list.iter() {
+            for ((name, level), new_name) in list_item.subrecords.iter() {
+                writeln!(
+                    text,
+                    "\t{:>2} {:<32} {:<32} {:<1} {:<32} {}",
+                    level,
+                    name,
+                    new_name.as_deref().unwrap_or("-"),
+                    list_item.log_t,
+                    list_item.id,
+                    list_item.initial_plugin
                 )?;
             }
         }
@@ -206,6 +321,121 @@ fn show_deleted_subrecords(
     Ok(())
 }
 
+fn show_dangling_subrecords(text: &mut String, list: &[DanglingSubrecords], cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let details = cfg.guts.verboseness_details_dangling_subrecords;
+    let subrecords_count = list.iter().map(|list_item| list_item.subrecords.len()).sum();
+    text.clear();
+    write!(
+        text,
+        "{} subrecord{} from {} leveled list{} {} to undefined records",
+        subrecords_count,
+        plural("s", subrecords_count)?,
+        list.len(),
+        plural("s", list.len())?,
+        plural("point", subrecords_count)?,
+    )?;
+    if cfg.strip_dangling {
+        write!(text, " and {} stripped", plural("were", subrecords_count)?)?;
+    }
+    let tone = if cfg.strip_dangling { MsgTone::Good } else { MsgTone::Ugly };
+    msg_with_details_suggestion(text, tone, 0, details, cfg, log)?;
+    if !(cfg.no_log && cfg.verbose < details) {
+        writeln!(
+            text,
+            "\n\t{:>2} {:<32} {:<1} {:<32} RESPONSIBLE PLUGIN",
+            "LV", "DANGLING SUBRECORD", "T", "LEVELED LIST"
+        )?;
+        for list_item in list.iter() {
+            for ((name, level), plugin_name) in list_item.subrecords.iter() {
+                writeln!(
+                    text,
+                    "\t{:>2} {:<32} {:<1} {:<32} {}",
+                    level, name, list_item.log_t, list_item.id, plugin_name
+                )?;
+            }
+        }
+        msg_and_clear(text, MsgTone::Neutral, details, cfg, log)?;
+    }
+    Ok(())
+}
+
+fn show_nesting_problems(text: &mut String, list: &[NestingProblem], cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let details = cfg.guts.verboseness_details_nesting_problems;
+    let cycles = list.iter().filter(|problem| problem.kind == NestingProblemKind::Cycle).count();
+    let too_deep = list.len() - cycles;
+    text.clear();
+    write!(
+        text,
+        "{} cycle{} and {} chain{} of nested leveled lists deeper than {} {} found",
+        cycles,
+        plural("s", cycles)?,
+        too_deep,
+        plural("s", too_deep)?,
+        cfg.max_nesting_depth,
+        plural("were", list.len())?,
+    )?;
+    msg_with_details_suggestion(text, MsgTone::Bad, 0, details, cfg, log)?;
+    if !(cfg.no_log && cfg.verbose < details) {
+        writeln!(text, "\n\t{:<1} {:<5} {:>5} NESTED LEVELED LISTS", "T", "ISSUE", "DEPTH")?;
+        for problem in list.iter() {
+            writeln!(
+                text,
+                "\t{:<1} {:<5} {:>5} {}",
+                problem.log_t,
+                match problem.kind {
+                    NestingProblemKind::Cycle => "CYCLE",
+                    NestingProblemKind::TooDeep => "DEPTH",
+                },
+                match problem.kind {
+                    NestingProblemKind::Cycle => String::from("-"),
+                    NestingProblemKind::TooDeep => problem.chain.len().to_string(),
+                },
+                problem.chain.join(" -> ")
+            )?;
+        }
+        msg_and_clear(text, MsgTone::Neutral, details, cfg, log)?;
+    }
+    Ok(())
+}
+
+fn show_merged_values(text: &mut String, list: &[MergedValues], cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let details = cfg.guts.verboseness_details_merged_values;
+    text.clear();
+    write!(
+        text,
+        "{} chance_none or list flags value{} of merged leveled lists differed between plugins",
+        list.len(),
+        plural("s", list.len())?,
+    )?;
+    msg_with_details_suggestion(text, MsgTone::Neutral, 0, details, cfg, log)?;
+    if !(cfg.no_log && cfg.verbose < details) {
+        writeln!(
+            text,
+            "\n\t{:<1} {:<32} {:<11} {:<8} {:>6} VALUES IN LOAD ORDER",
+            "T", "LEVELED LIST", "FIELD", "STRATEGY", "RESULT"
+        )?;
+        for list_item in list.iter() {
+            writeln!(
+                text,
+                "\t{:<1} {:<32} {:<11} {:<8} {:>6} {}",
+                list_item.log_t,
+                list_item.id,
+                list_item.field,
+                list_item.strategy,
+                list_item.merged,
+                list_item
+                    .values
+                    .iter()
+                    .map(|(value, plugin_name)| format!("{value}({plugin_name})"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )?;
+        }
+        msg_and_clear(text, MsgTone::Neutral, details, cfg, log)?;
+    }
+    Ok(())
+}
+
 fn show_untouched_lists(text: &mut String, list: &[UntouchedList], cfg: &Cfg, log: &mut Log) -> Result<()> {
     let details = cfg.guts.verboseness_details_untouched_lists;
     text.clear();
@@ -321,36 +551,50 @@ Consider performing any of these actions:
     Ok(())
 }
 
-fn show_deleveled_subrecords(
+fn show_changed_level_subrecords(
     text: &mut String,
     list: &[DeleveledSubrecords],
     subrecords_count: usize,
+    action: &str,
+    details: u8,
     cfg: &Cfg,
     log: &mut Log,
 ) -> Result<()> {
-    let details = cfg.guts.verboseness_details_deleveled_subrecords;
     write!(
         text,
-        "{} subrecord{} from {} leveled list{} {} deleveled",
+        "{} subrecord{} from {} leveled list{} {} {}",
         subrecords_count,
         plural("s", subrecords_count)?,
         list.len(),
         plural("s", list.len())?,
         plural("were", subrecords_count)?,
+        action,
     )?;
     msg_with_details_suggestion(text, MsgTone::Good, 0, details, cfg, log)?;
     if !(cfg.no_log && cfg.verbose < details) {
+        let show_rules = list.iter().flat_map(|list_item| list_item.subrecords.iter()).any(|subrecord| subrecord.2.is_some());
         writeln!(
             text,
-            "\n\t{:>3} {:>3} {:<32} {:<1} {:<32} {:<16}",
-            "NEW", "OLD", "DELEVELED SUBRECORD", "T", "LEVELED LIST", "INITIAL PLUGIN"
+            "\n\t{:>3} {:>3} {:<32} {:<1} {:<32} {:<16}{}",
+            "NEW",
+            "OLD",
+            format!("{} SUBRECORD", action.to_uppercase()),
+            "T",
+            "LEVELED LIST",
+            "INITIAL PLUGIN",
+            if show_rules { " RULE" } else { "" }
         )?;
         for list_item in list.iter() {
             for subrecord in list_item.subrecords.iter() {
+                let rule = if show_rules {
+                    format!(" {}", subrecord.2.unwrap_or("-"))
+                } else {
+                    String::new()
+                };
                 writeln!(
                     text,
-                    "\t{:>3} {:>3} {:<32} {:<1} {:<32} {:<16}",
-                    subrecord.1, subrecord.0 .1, subrecord.0 .0, list_item.log_t, list_item.id, list_item.initial_plugin,
+                    "\t{:>3} {:>3} {:<32} {:<1} {:<32} {:<16}{}",
+                    subrecord.1, subrecord.0 .1, subrecord.0 .0, list_item.log_t, list_item.id, list_item.initial_plugin, rule,
                 )?;
             }
         }
@@ -359,12 +603,12 @@ fn show_deleveled_subrecords(
     Ok(())
 }
 
-fn msg_with_details_suggestion(text: &mut String, tone: MsgTone, verbose: u8, details: u8, cfg: &Cfg, log: &mut Log) -> Result<()> {
+pub(super) fn msg_with_details_suggestion(text: &mut String, tone: MsgTone, verbose: u8, details: u8, cfg: &Cfg, log: &mut Log) -> Result<()> {
     append_for_details_or_check_log(text, details, cfg)?;
     msg_and_clear(text, tone, verbose, cfg, log)
 }
 
-fn msg_and_clear(text: &mut String, tone: MsgTone, verbose: u8, cfg: &Cfg, log: &mut Log) -> Result<()> {
+pub(super) fn msg_and_clear(text: &mut String, tone: MsgTone, verbose: u8, cfg: &Cfg, log: &mut Log) -> Result<()> {
     msg(&text, tone, verbose, cfg, log)?;
     text.clear();
     Ok(())
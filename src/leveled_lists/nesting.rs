@@ -0,0 +1,129 @@
+use super::messages::{LlMessages, NestingProblem, NestingProblemKind};
+use crate::LlElement;
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(test)]
+mod tests;
+
+enum Visit<'b> {
+    InProgress,
+    // COMMENT: depth of the deepest chain starting from the list, next list of that chain
+    Done(usize, Option<&'b str>),
+}
+
+struct NestingGraph<'b> {
+    // COMMENT: id_low -> (id, ids_low of nested lists)
+    lists: HashMap<String, (&'b str, Vec<String>)>,
+}
+
+impl<'b> NestingGraph<'b> {
+    fn new(lists: impl Iterator<Item = (&'b str, &'b [LlElement])>) -> NestingGraph<'b> {
+        let lists = lists
+            .map(|(id, list)| {
+                let mut nested = list.iter().map(|(name, _)| name.to_lowercase()).collect::<Vec<String>>();
+                nested.sort();
+                nested.dedup();
+                (id.to_lowercase(), (id, nested))
+            })
+            .collect::<HashMap<String, (&str, Vec<String>)>>();
+        let known = lists.keys().cloned().collect::<HashSet<String>>();
+        let lists = lists
+            .into_iter()
+            .map(|(id_low, (id, mut nested))| {
+                nested.retain(|name_low| known.contains(name_low));
+                (id_low, (id, nested))
+            })
+            .collect();
+        NestingGraph { lists }
+    }
+
+    fn visit<'s>(
+        &'s self,
+        id_low: &'s str,
+        state: &mut HashMap<&'s str, Visit<'s>>,
+        stack: &mut Vec<&'s str>,
+        cycles: &mut Vec<Vec<&'s str>>,
+    ) -> usize {
+        state.insert(id_low, Visit::InProgress);
+        stack.push(id_low);
+        let mut deepest = (0, None);
+        for nested in self.lists[id_low].1.iter() {
+            let depth = match state.get(nested.as_str()) {
+                Some(Visit::InProgress) => {
+                    let start = stack.iter().position(|x| *x == nested.as_str()).unwrap_or_default();
+                    cycles.push(stack[start..].to_vec());
+                    continue;
+                }
+                Some(Visit::Done(depth, _)) => *depth,
+                None => self.visit(nested, state, stack, cycles),
+            };
+            if depth > deepest.0 {
+                deepest = (depth, Some(nested.as_str()));
+            }
+        }
+        stack.pop();
+        state.insert(id_low, Visit::Done(deepest.0 + 1, deepest.1));
+        deepest.0 + 1
+    }
+
+    fn id(&self, id_low: &str) -> String {
+        self.lists[id_low].0.to_owned()
+    }
+}
+
+// COMMENT: only lists that are not nested anywhere are reported for depth, because every list above a deep chain is deep too
+pub(super) fn find_nesting_problems<'b>(
+    lists: impl Iterator<Item = (&'b str, &'b [LlElement])>,
+    max_depth: usize,
+) -> Vec<(NestingProblemKind, Vec<String>)> {
+    let graph = NestingGraph::new(lists);
+    let mut ids_low = graph.lists.keys().map(String::as_str).collect::<Vec<&str>>();
+    ids_low.sort();
+    let mut state = HashMap::new();
+    let mut cycles = Vec::new();
+    for id_low in ids_low.iter() {
+        if !state.contains_key(id_low) {
+            graph.visit(id_low, &mut state, &mut Vec::new(), &mut cycles);
+        }
+    }
+    let mut problems = Vec::new();
+    let mut seen_cycles = HashSet::new();
+    for mut cycle in cycles {
+        let min = cycle.iter().enumerate().min_by_key(|(_, id_low)| **id_low).map_or(0, |(index, _)| index);
+        cycle.rotate_left(min);
+        if seen_cycles.insert(cycle.clone()) {
+            let mut chain = cycle.iter().map(|id_low| graph.id(id_low)).collect::<Vec<String>>();
+            chain.push(graph.id(cycle[0]));
+            problems.push((NestingProblemKind::Cycle, chain));
+        }
+    }
+    problems.sort_by(|a, b| a.1.cmp(&b.1));
+    if max_depth > 0 {
+        let nested = graph.lists.values().flat_map(|(_, nested)| nested.iter().map(String::as_str)).collect::<HashSet<&str>>();
+        for id_low in ids_low.into_iter().filter(|id_low| !nested.contains(id_low)) {
+            if let Some(Visit::Done(depth, _)) = state.get(id_low) {
+                if *depth > max_depth {
+                    let mut chain = vec![graph.id(id_low)];
+                    let mut next = id_low;
+                    while let Some(Visit::Done(_, Some(nested))) = state.get(next) {
+                        chain.push(graph.id(nested));
+                        next = *nested;
+                    }
+                    problems.push((NestingProblemKind::TooDeep, chain));
+                }
+            }
+        }
+    }
+    problems
+}
+
+pub(crate) fn check_nesting<'a, 'b>(
+    lists: impl Iterator<Item = (&'b str, &'b [LlElement])>,
+    max_depth: usize,
+    log_t: &'a str,
+    messages: &mut LlMessages<'a>,
+) {
+    for (kind, chain) in find_nesting_problems(lists, max_depth) {
+        messages.nesting_problems.push(NestingProblem { log_t, kind, chain });
+    }
+}
@@ -0,0 +1,54 @@
+use super::{find_nesting_problems, NestingProblemKind};
+use crate::LlElement;
+use pretty_assertions::assert_eq;
+
+fn lists(src: &[(&str, &[&str])]) -> Vec<(String, Vec<LlElement>)> {
+    src.iter()
+        .map(|(id, list)| (id.to_string(), list.iter().map(|name| (name.to_string(), 1)).collect()))
+        .collect()
+}
+
+fn problems(src: &[(String, Vec<LlElement>)], max_depth: usize) -> Vec<(NestingProblemKind, Vec<String>)> {
+    find_nesting_problems(src.iter().map(|(id, list)| (id.as_str(), list.as_slice())), max_depth)
+}
+
+fn chain(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[test]
+fn no_problems() {
+    let src = lists(&[("l_loot", &["gold_001", "L_Daggers"]), ("l_daggers", &["iron dagger"])]);
+    assert_eq!(problems(&src, 5), []);
+}
+
+#[test]
+fn self_and_indirect_cycles() {
+    let src = lists(&[
+        ("l_self", &["L_Self", "rat"]),
+        ("c", &["a"]),
+        ("a", &["b", "mudcrab"]),
+        ("b", &["C"]),
+        ("root", &["a"]),
+    ]);
+    assert_eq!(
+        problems(&src, 0),
+        [
+            (NestingProblemKind::Cycle, chain(&["a", "b", "c", "a"])),
+            (NestingProblemKind::Cycle, chain(&["l_self", "l_self"]))
+        ]
+    );
+}
+
+#[test]
+fn too_deep_reported_for_top_lists_only() {
+    let src = lists(&[
+        ("top", &["middle", "short"]),
+        ("middle", &["bottom"]),
+        ("bottom", &["rat"]),
+        ("short", &["rat"]),
+        ("other", &["short"]),
+    ]);
+    assert_eq!(problems(&src, 2), [(NestingProblemKind::TooDeep, chain(&["top", "middle", "bottom"]))]);
+    assert_eq!(problems(&src, 3), []);
+}
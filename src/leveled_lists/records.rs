@@ -1,12 +1,27 @@
-use super::messages::{DeletedSubrecords, DeleveledSubrecords, LlMessages, UntouchedList};
+use super::{
+    explain::{DeleteOutcome, ExplainStep, Explanation},
+    graph::LlGraph,
+    messages::{
+        DanglingSubrecords, DeletedSubrecords, DeleveledSubrecords, EditedSubrecords, LlMessages, ReleveledSubrecords, UntouchedList,
+    },
+    nesting::check_nesting,
+    simulation::LlSimulation,
+    strategy::{log_merged_values, merge_values},
+    weights::{get_weights, merge_additions, merge_removals, WeightDelta, Weights},
+};
 use crate::{
-    get_delev_segment_ceil, get_plugin_size, Cfg, DelevSkipPatterns, InputHelper, ListCounts, ListKind, Log, PluginInfo, PluginName,
-    RawPlugins, ResponsiblePlugins,
+    get_delev_segment_ceil, get_plugin_size, Cfg, DelevRule, DelevSkipPatterns, DelevTarget, InputHelper, ListCounts, ListKind, Log,
+    PluginInfo, PluginName, RawPlugins, RelevelTransform,
 };
 use anyhow::{anyhow, Result};
-use hashbrown::{hash_map::Entry, HashMap};
+use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use paste::paste;
 use rand::{rngs::ThreadRng, Rng};
+use std::{
+    cmp::{max, min},
+    mem::take,
+    ops::{BitAnd, BitOr},
+};
 use tes3::esp::{LeveledCreature, LeveledCreatureFlags, LeveledItem, LeveledItemFlags, ObjectFlags, TES3Object};
 mod self_macro;
 use self_macro::ll_record_methods;
@@ -56,15 +71,21 @@ pub(crate) struct LlCreatureRecord<'a> {
     pub(crate) id: String,
     pub(crate) leveled_creature_flags: Vec<LeveledCreatureFlags>,
     pub(crate) chance_nones: Vec<u8>,
+    pub(crate) list_flags_history: Vec<(LeveledCreatureFlags, PluginName<'a>)>,
+    pub(crate) chance_none_history: Vec<(u8, PluginName<'a>)>,
     pub(crate) list: Vec<LlElement>,
+    pub(crate) list_plugins: Vec<PluginName<'a>>,
     pub(crate) list_lowercased: Vec<LlElement>,
     pub(crate) first: Vec<(LlElement, LlElement)>,
-    pub(crate) delete: Vec<(LlElement, LlElement, ResponsiblePlugins<'a>)>,
+    pub(crate) first_weights: Weights,
+    pub(crate) delete: Vec<WeightDelta<'a>>,
     pub(crate) count: usize,
     pub(crate) plugin_name_lowercased: PluginName<'a>,
     pub(crate) masters: Vec<&'a PluginInfo>,
     pub(crate) last: LlCreatureLast,
     pub(crate) last_plugin_name: Option<PluginName<'a>>,
+    pub(crate) stripped: bool,
+    pub(crate) explain: Vec<ExplainStep<'a>>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -73,15 +94,21 @@ pub(crate) struct LlItemRecord<'a> {
     pub(crate) id: String,
     pub(crate) leveled_item_flags: Vec<LeveledItemFlags>,
     pub(crate) chance_nones: Vec<u8>,
+    pub(crate) list_flags_history: Vec<(LeveledItemFlags, PluginName<'a>)>,
+    pub(crate) chance_none_history: Vec<(u8, PluginName<'a>)>,
     pub(crate) list: Vec<LlElement>,
+    pub(crate) list_plugins: Vec<PluginName<'a>>,
     pub(crate) list_lowercased: Vec<LlElement>,
     pub(crate) first: Vec<(LlElement, LlElement)>,
-    pub(crate) delete: Vec<(LlElement, LlElement, ResponsiblePlugins<'a>)>,
+    pub(crate) first_weights: Weights,
+    pub(crate) delete: Vec<WeightDelta<'a>>,
     pub(crate) count: usize,
     pub(crate) plugin_name_lowercased: PluginName<'a>,
     pub(crate) masters: Vec<&'a PluginInfo>,
     pub(crate) last: LlItemLast,
     pub(crate) last_plugin_name: Option<PluginName<'a>>,
+    pub(crate) stripped: bool,
+    pub(crate) explain: Vec<ExplainStep<'a>>,
 }
 
 ll_record_methods!(
@@ -112,18 +139,19 @@ fn delete_subrecords<'a>(
     list_lowercased: &mut Vec<LlElement>,
     id: &String,
     first: Vec<(LlElement, LlElement)>,
-    delete: Vec<(LlElement, LlElement, ResponsiblePlugins<'a>)>,
+    delete: Vec<WeightDelta<'a>>,
     threshold: f64,
     log_t: &'a str,
     plugin_info: &'a PluginInfo,
     counts: &mut ListCounts,
     messages: &mut LlMessages<'a>,
     cfg: &Cfg,
-) -> Result<()> {
+) -> Result<DeleteOutcome> {
     if !cfg.extended_delete && !cfg.always_delete.contains(&plugin_info.name_lowercased) {
-        return Ok(());
+        return Ok(DeleteOutcome::NotAllowed);
     };
-    let ratio = 100.0 * delete.len() as f64 / first.len() as f64;
+    let removed: usize = delete.iter().map(|delta| delta.removed).sum();
+    let ratio = 100.0 * removed as f64 / first.len() as f64;
     if cfg.extended_delete
         && ratio > threshold
         && ratio >= cfg.guts.auto_resolve_lower_limit
@@ -132,31 +160,42 @@ fn delete_subrecords<'a>(
         messages
             .threshold_resolved
             .push(ratio, threshold, log_t, id.to_owned(), &plugin_info.name, &delete);
+        Ok(DeleteOutcome::Resolved(ratio, threshold))
     } else {
+        let mut outcome = DeleteOutcome::Deleted;
         if cfg.extended_delete && ratio > threshold {
             if cfg.always_delete.contains(&plugin_info.name_lowercased) {
                 messages
                     .threshold_skipped
                     .push(ratio, threshold, log_t, id.to_owned(), &plugin_info.name, &delete);
+                outcome = DeleteOutcome::ThresholdSkipped(ratio, threshold);
             } else {
                 messages
                     .threshold_warnings
                     .push(ratio, threshold, log_t, id.to_owned(), &plugin_info.name, &delete);
+                outcome = DeleteOutcome::ThresholdWarning(ratio, threshold);
             }
         }
         let mut subrecords = Vec::new();
-        for (subrecord_lowercased, subrecord, responsible_plugins) in delete.into_iter() {
-            let index = match list_lowercased.iter().position(|x| x == &subrecord_lowercased) {
-                Some(index) => index,
-                None => {
-                    return Err(anyhow!("Failed to delete subrecord. This error should've never happened. List id: {}, subrecord id: {}, initial plugin: {}, responsible plugin: {}", id, subrecord.0, &plugin_info.name, responsible_plugins.into_iter().map(|x| x.as_str()).collect::<Vec<_>>().join(", ")));
-                }
-            };
+        for delta in delete.into_iter() {
+            let weight = list_lowercased.iter().filter(|x| x == &&delta.subrecord_lowercased).count();
+            for _ in 0..delta.removed {
+                let index = match list_lowercased.iter().position(|x| x == &delta.subrecord_lowercased) {
+                    Some(index) => index,
+                    None => {
+                        return Err(anyhow!("Failed to delete subrecord. This error should've never happened. List id: {}, subrecord id: {}, initial plugin: {}, responsible plugin: {}", id, delta.subrecord.0, &plugin_info.name, delta.responsible_plugins.iter().map(|x| x.as_str()).collect::<Vec<_>>().join(", ")));
+                    }
+                };
 
-            list_lowercased.swap_remove(index);
-            list.swap_remove(index);
-            subrecords.push((subrecord, responsible_plugins.into_iter().map(|x| x.as_str()).collect()));
-            counts.merge.deleted_subrecord += 1;
+                list_lowercased.swap_remove(index);
+                list.swap_remove(index);
+                counts.merge.deleted_subrecord += 1;
+            }
+            subrecords.push((
+                delta.subrecord,
+                (weight, weight - delta.removed),
+                delta.responsible_plugins.into_iter().map(|x| x.as_str()).collect(),
+            ));
         }
         messages.deleted_subrecords.push(DeletedSubrecords {
             log_t,
@@ -164,11 +203,56 @@ fn delete_subrecords<'a>(
             initial_plugin: &plugin_info.name,
             subrecords,
         });
+        Ok(outcome)
     }
-    Ok(())
 }
 
-fn append_masters<'a>(
+fn graph_plugins<'a>(masters: &[&'a PluginInfo], last_plugin_name: Option<PluginName<'a>>) -> Vec<&'a str> {
+    let mut plugins = masters.iter().map(|plugin_info| plugin_info.name.as_str()).collect::<Vec<&str>>();
+    if let Some(last_plugin_name) = last_plugin_name {
+        if !plugins.contains(&last_plugin_name.as_str()) {
+            plugins.push(last_plugin_name);
+        }
+    }
+    plugins
+}
+
+fn edit_subrecords<'a>(
+    list: &mut Vec<LlElement>,
+    id: &str,
+    log_t: &'a str,
+    plugin_info: &'a PluginInfo,
+    counts: &mut ListCounts,
+    messages: &mut LlMessages<'a>,
+    cfg: &Cfg,
+) -> bool {
+    let mut subrecords = Vec::new();
+    list.retain_mut(|(name, level)| {
+        let name_lowercased = name.to_lowercase();
+        if cfg.ll_remove.contains(&name_lowercased) {
+            subrecords.push(((name.clone(), *level), None));
+            return false;
+        }
+        if let Some((_, new_name)) = cfg.ll_replace.iter().find(|(old_name, _)| old_name == &name_lowercased) {
+            subrecords.push(((name.clone(), *level), Some(new_name.clone())));
+            *name = new_name.clone();
+        }
+        true
+    });
+    if subrecords.is_empty() {
+        return false;
+    }
+    counts.merge.edited_subrecord += subrecords.len();
+    messages.edited_subrecords.push(EditedSubrecords {
+        log_t,
+        id: id.to_owned(),
+        initial_plugin: &plugin_info.name,
+        subrecords,
+    });
+    true
+}
+
+pub(crate) fn append_masters<'a>(
     masters_src: Vec<&'a PluginInfo>,
     masters_dst: &mut HashMap<PluginName<'a>, (usize, PluginName<'a>, u64)>,
     count: &mut usize,
@@ -187,15 +271,12 @@ fn append_masters<'a>(
 
 fn creature_differs(
     flags: &[ObjectFlags],
-    list_flags: &[LeveledCreatureFlags],
-    chance_none: &[u8],
+    list_flags: LeveledCreatureFlags,
+    chance_none: u8,
     mut merged: Vec<LlElement>,
     last: &mut LlCreatureLast,
 ) -> bool {
-    if (flags.len() > 1 && flags.last().unwrap() != &last.flag)
-        || (list_flags.len() > 1 && list_flags.last().unwrap() != &last.list_flag)
-        || (chance_none.len() > 1 && chance_none.last().unwrap() != &last.chance_none)
-    {
+    if (flags.len() > 1 && flags.last().unwrap() != &last.flag) || list_flags != last.list_flag || chance_none != last.chance_none {
         true
     } else {
         merged.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
@@ -213,15 +294,12 @@ fn creature_differs(
 
 fn item_differs(
     flags: &[ObjectFlags],
-    list_flags: &[LeveledItemFlags],
-    chance_none: &[u8],
+    list_flags: LeveledItemFlags,
+    chance_none: u8,
     mut merged: Vec<LlElement>,
     last: &mut LlItemLast,
 ) -> bool {
-    if (flags.len() > 1 && flags.last().unwrap() != &last.flag)
-        || (list_flags.len() > 1 && list_flags.last().unwrap() != &last.list_flag)
-        || (chance_none.len() > 1 && chance_none.last().unwrap() != &last.chance_none)
-    {
+    if (flags.len() > 1 && flags.last().unwrap() != &last.flag) || list_flags != last.list_flag || chance_none != last.chance_none {
         true
     } else {
         merged.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
@@ -244,32 +322,35 @@ fn delevel_list<'a>(
     kind: &'a ListKind,
     plugin_info: &'a PluginInfo,
     rng: &mut ThreadRng,
-    cfg: &Cfg,
+    cfg: &'a Cfg,
     counts: &mut ListCounts,
     messages: &mut LlMessages<'a>,
 ) -> Vec<LlElement> {
     let mut res = Vec::new();
-    if list.iter().any(|(_, level)| level > &kind.delev_to) {
+    let id_lowercased = id.to_lowercase();
+    if list.iter().any(|(_, level)| level > &kind.delev_to) || cfg.delev_rules.matches_list(&id_lowercased) {
         if delev_skip(id, &cfg.delev_skip_list, &cfg.delev_no_skip_list) {
             return res;
         }
         let mut subrecords = Vec::new();
         for (name, level) in list.iter() {
-            if level > &kind.delev_to && !delev_skip(name, &cfg.delev_skip_subrecord, &cfg.delev_no_skip_subrecord) {
-                let mut new_level = if kind.delev_segment > 0 && level >= &kind.delev_segment {
-                    if !cfg.delev_segment_progressive {
-                        kind.delev_segment_ceil
-                    } else {
-                        get_delev_segment_ceil(level, kind.delev_segment, kind.delev_to, cfg.delev_segment_ratio)
-                    }
+            let name_lowercased = name.to_lowercase();
+            let rule = cfg.delev_rules.get(&id_lowercased, &name_lowercased);
+            let (new_level, is_delev) = match rule {
+                Some(rule) => {
+                    let new_level = get_rule_delev_level(level, rule, kind, cfg);
+                    (new_level, new_level < *level)
+                }
+                None => (get_delev_level(level, kind.delev_segment, kind, cfg), level > &kind.delev_to),
+            };
+            if is_delev && !delev_skip(name, &cfg.delev_skip_subrecord, &cfg.delev_no_skip_subrecord) {
+                let new_level = if cfg.delev_random {
+                    rng.gen_range(new_level..=*level)
                 } else {
-                    kind.delev_to
-                };
-                if cfg.delev_random {
-                    new_level = rng.gen_range(new_level..=*level);
+                    new_level
                 };
                 res.push((name.clone(), new_level));
-                subrecords.push(((name.to_lowercase(), *level), new_level));
+                subrecords.push(((name_lowercased, *level), new_level, rule.map(|rule| rule.name.as_str())));
                 counts.delev.deleveled_subrecord += 1;
             } else {
                 res.push((name.clone(), *level));
@@ -285,6 +366,78 @@ fn delevel_list<'a>(
     res
 }
 
+fn relevel_list<'a>(
+    list: &[LlElement],
+    id: &str,
+    kind: &'a ListKind,
+    plugin_info: &'a PluginInfo,
+    cfg: &Cfg,
+    counts: &mut ListCounts,
+    messages: &mut LlMessages<'a>,
+) -> Vec<LlElement> {
+    let mut res = Vec::new();
+    let mut subrecords = Vec::new();
+    for (name, level) in list.iter() {
+        let new_level = get_relevel_level(level, &cfg.relevel_transform);
+        if new_level != *level {
+            subrecords.push(((name.to_lowercase(), *level), new_level, None));
+        }
+        res.push((name.clone(), new_level));
+    }
+    if subrecords.is_empty() {
+        return Vec::new();
+    }
+    counts.relevel.releveled_subrecord += subrecords.len();
+    messages.releveled_subrecords.push(ReleveledSubrecords {
+        log_t: &kind.log_t,
+        id: id.to_owned(),
+        initial_plugin: &plugin_info.name,
+        subrecords,
+    });
+    res
+}
+
+// COMMENT: scale, then add, then clamp; moved subrecords never end up at level 0 because such subrecords never spawn
+fn get_relevel_level(level: &u16, transform: &RelevelTransform) -> u16 {
+    let mut new_level = (*level as f64 * transform.scale).round() as i64 + transform.add as i64;
+    if transform.min > 0 {
+        new_level = new_level.max(transform.min as i64);
+    }
+    if transform.max > 0 {
+        new_level = new_level.min(transform.max as i64);
+    }
+    if new_level == *level as i64 {
+        *level
+    } else {
+        new_level.clamp(1, u16::MAX as i64) as u16
+    }
+}
+
+fn get_delev_level(level: &u16, delev_segment: u16, kind: &ListKind, cfg: &Cfg) -> u16 {
+    if level <= &kind.delev_to {
+        *level
+    } else if delev_segment > 0 && level >= &delev_segment {
+        if !cfg.delev_segment_progressive {
+            get_delev_segment_ceil(&delev_segment, delev_segment, kind.delev_to, cfg.delev_segment_ratio)
+        } else {
+            get_delev_segment_ceil(level, delev_segment, kind.delev_to, cfg.delev_segment_ratio)
+        }
+    } else {
+        kind.delev_to
+    }
+}
+
+// COMMENT: rules may only lower the level, "min" is applied after the target level is calculated
+fn get_rule_delev_level(level: &u16, rule: &DelevRule, kind: &ListKind, cfg: &Cfg) -> u16 {
+    let new_level = match rule.target {
+        DelevTarget::Default => get_delev_level(level, kind.delev_segment, kind, cfg),
+        DelevTarget::To(to) => to,
+        DelevTarget::Scale(scale) => (*level as f64 * scale).ceil() as u16,
+        DelevTarget::Segment(segment) => get_delev_level(level, segment, kind, cfg),
+    };
+    new_level.max(rule.min).min(*level)
+}
+
 fn delev_skip(string: &str, patterns: &DelevSkipPatterns, no_patterns: &DelevSkipPatterns) -> bool {
     if !patterns.is_empty {
         let string_lowercased = string.to_lowercase();
@@ -6,10 +6,13 @@ macro_rules! ll_record_methods {
         pub(crate) struct [<$ll_long s>]<'a>(pub(crate) Vec<$ll_long<'a>>);
 
         impl<'a> [<$ll_long s>]<'a> {
+            #[allow(clippy::too_many_arguments)]
             pub(crate) fn [<make_ $short>](
                 self,
                 raw: &mut RawPlugins<'a>,
                 messages: &mut LlMessages<'a>,
+                simulation: &mut LlSimulation,
+                graph: &mut LlGraph<'a>,
                 counts: &mut ListCounts,
                 rng: &mut ThreadRng,
                 cfg: &'a Cfg,
@@ -18,51 +21,100 @@ macro_rules! ll_record_methods {
                 for mut o in self.0.into_iter() {
                     counts.total.total += o.count;
                     counts.total.unique += 1;
-                    if o.count > 1 || cfg.all_lists || cfg.delev {
+                    if o.count > 1 || cfg.all_lists || cfg.delev || cfg.relevel || o.stripped || cfg.meta.ll_edit {
                         let mut $name = o.list;
+                        let mut explanation = Explanation::new(&o.id, &cfg.$name.log_t, &o.explain, &$name)?;
+                        let list_flags = merge_values(&o.list_flags_history, cfg.list_flags_strategy, BitAnd::bitand, BitOr::bitor);
+                        let chance_none = merge_values(&o.chance_none_history, cfg.chance_none_strategy, min, max);
+                        log_merged_values(
+                            &o.list_flags_history,
+                            list_flags,
+                            |value| value.bits().to_string(),
+                            "list flags",
+                            cfg.list_flags_strategy,
+                            &o.id,
+                            &cfg.$name.log_t,
+                            messages,
+                        );
+                        log_merged_values(
+                            &o.chance_none_history,
+                            chance_none,
+                            |value| value.to_string(),
+                            "chance_none",
+                            cfg.chance_none_strategy,
+                            &o.id,
+                            &cfg.$name.log_t,
+                            messages,
+                        );
 
+                        let delete_outcome = if o.count > 1 && !cfg.no_delete && !o.delete.is_empty() {
+                            Some(delete_subrecords(
+                                &mut $name,
+                                &mut o.list_lowercased,
+                                &o.id,
+                                o.first,
+                                o.delete,
+                                cfg.$name.threshold,
+                                &cfg.$name.log_t,
+                                &o.masters[0],
+                                counts,
+                                messages,
+                                cfg,
+                            )?)
+                        } else {
+                            None
+                        };
+                        let is_edited = cfg.meta.ll_edit
+                            && edit_subrecords(&mut $name, &o.id, &cfg.$name.log_t, &o.masters[0], counts, messages, cfg);
+                        if let Some(explanation) = explanation.as_mut() {
+                            explanation.delete(delete_outcome, &$name, cfg)?;
+                            explanation.update("Removed or replaced with --ll-remove or --ll-replace", &$name)?;
+                        }
                         if o.count > 1 {
-                            if !cfg.no_delete && !o.delete.is_empty() {
-                                delete_subrecords(
-                                    &mut $name,
-                                    &mut o.list_lowercased,
-                                    &o.id,
-                                    o.first,
-                                    o.delete,
-                                    cfg.$name.threshold,
-                                    &cfg.$name.log_t,
-                                    &o.masters[0],
-                                    counts,
-                                    messages,
-                                    cfg,
-                                )?;
-                            }
                             $name.sort_by(|(name1, _), (name2, _)| name1.to_lowercase().cmp(&name2.to_lowercase()));
                             $name.sort_by_key(|level| level.1);
                         }
 
-                        let is_merge = if o.count > 1
+                        let is_merge = if is_edited {
+                            true
+                        } else if o.count > 1
                             && !cfg.all_lists
-                            && $kind_differs(&o.flags, &o.$flags_kind, &o.chance_nones, o.list_lowercased, &mut o.last)
+                            && $kind_differs(&o.flags, list_flags, chance_none, o.list_lowercased, &mut o.last)
                         {
                             true
                         } else {
-                            false
+                            o.stripped
                         };
 
+                        if cfg.meta.graph {
+                            graph.push(&o.id, stringify!($short), graph_plugins(&o.masters, o.last_plugin_name), &$name);
+                        }
+
                         let mut is_delev = false;
+                        let mut is_simulated = false;
                         if cfg.delev && !cfg.$name.skip_delev {
                             let delev_list = delevel_list(&$name, &o.id, &cfg.$name, &o.masters[0], rng, cfg, counts, messages);
                             if !delev_list.is_empty() {
+                                if cfg.simulate > 0 {
+                                    simulation.$name.push(&o.id, &list_flags, chance_none, &$name, Some(&delev_list));
+                                    is_simulated = true;
+                                }
                                 counts.delev.deleveled += 1;
+                                if let Some(explanation) = explanation.as_mut() {
+                                    if cfg.delev_distinct {
+                                        explanation.changes("Deleveled into delev output plugin", &delev_list)?;
+                                    } else {
+                                        explanation.update("Deleveled", &delev_list)?;
+                                    }
+                                }
                                 if cfg.delev_distinct {
                                     let x = o.masters.clone();
                                     append_masters(x, &mut raw.delev.masters, &mut counts.delev.master, cfg, log)?;
                                     raw.delev.plugin.objects.push(TES3Object::$long($long {
                                         flags: o.flags.last().unwrap().clone(),
                                         id: o.id.clone(),
-                                        $flags_kind: *o.$flags_kind.last().unwrap(),
-                                        chance_none: *o.chance_nones.last().unwrap(),
+                                        $flags_kind: list_flags,
+                                        chance_none,
                                         $name: delev_list,
                                     }));
                                     counts.delev.placed += 1;
@@ -72,6 +124,39 @@ macro_rules! ll_record_methods {
                                 }
                             }
                         };
+                        if cfg.relevel {
+                            let relevel_list = relevel_list(&$name, &o.id, &cfg.$name, &o.masters[0], cfg, counts, messages);
+                            if !relevel_list.is_empty() {
+                                counts.relevel.releveled += 1;
+                                if let Some(explanation) = explanation.as_mut() {
+                                    explanation.changes("Releveled into relevel output plugin", &relevel_list)?;
+                                }
+                                let x = o.masters.clone();
+                                append_masters(x, &mut raw.relevel.masters, &mut counts.relevel.master, cfg, log)?;
+                                raw.relevel.plugin.objects.push(TES3Object::$long($long {
+                                    flags: o.flags.last().unwrap().clone(),
+                                    id: o.id.clone(),
+                                    $flags_kind: list_flags,
+                                    chance_none,
+                                    $name: relevel_list,
+                                }));
+                                counts.relevel.placed += 1;
+                            }
+                        }
+                        if cfg.simulate > 0 && !is_simulated {
+                            simulation.$name.push(&o.id, &list_flags, chance_none, &$name, None);
+                        }
+
+                        if let Some(explanation) = explanation {
+                            let placement = if cfg.all_lists || is_delev || is_merge {
+                                "placed into the output plugin"
+                            } else if o.count > 1 {
+                                "not placed, identical to the last loaded list"
+                            } else {
+                                "not placed, defined by a single plugin"
+                            };
+                            explanation.show(list_flags.bits().to_string(), chance_none, placement, cfg, log)?;
+                        }
 
                         if cfg.all_lists || is_delev || is_merge {
                             append_masters(
@@ -88,8 +173,8 @@ macro_rules! ll_record_methods {
                             raw.merge.plugin.objects.push(TES3Object::$long($long {
                                 flags: o.flags.last().unwrap().clone(),
                                 id: o.id,
-                                $flags_kind: *o.$flags_kind.last().unwrap(),
-                                chance_none: *o.chance_nones.last().unwrap(),
+                                $flags_kind: list_flags,
+                                chance_none,
                                 $name,
                             }));
                             if o.count > 1 {
@@ -102,7 +187,7 @@ macro_rules! ll_record_methods {
                                 } else {
                                     counts.merge.untouched += 1;
                                 }
-                            } else if cfg.all_lists {
+                            } else if cfg.all_lists || o.stripped || is_edited {
                                 counts.merge.placed += 1;
                                 counts.total.placed += 1;
                             }
@@ -124,28 +209,96 @@ macro_rules! ll_record_methods {
                             }
                             counts.merge.merged += 1;
                         }
+                    } else {
+                        if cfg.simulate > 0 {
+                            let (flags, chance_none) = (o.$flags_kind.last().unwrap(), *o.chance_nones.last().unwrap());
+                            simulation.$name.push(&o.id, flags, chance_none, &o.list, None);
+                        }
+                        if cfg.meta.graph {
+                            graph.push(&o.id, stringify!($short), graph_plugins(&o.masters, o.last_plugin_name), &o.list);
+                        }
+                        if let Some(explanation) = Explanation::new(&o.id, &cfg.$name.log_t, &o.explain, &o.list)? {
+                            let (flags, chance_none) = (o.$flags_kind.last().unwrap(), *o.chance_nones.last().unwrap());
+                            let placement = "not placed, defined by a single plugin";
+                            explanation.show(flags.bits().to_string(), chance_none, placement, cfg, log)?;
+                        }
                     }
                 }
                 Ok(())
             }
 
+            pub(crate) fn [<check_dangling_ $short>](
+                &mut self,
+                known_ids: &HashSet<String>,
+                messages: &mut LlMessages<'a>,
+                cfg: &'a Cfg
+            ) {
+                for o in self.0.iter_mut() {
+                    let subrecords = o
+                        .list
+                        .iter()
+                        .zip(take(&mut o.list_plugins))
+                        .filter(|((name, _), _)| !known_ids.contains(&name.to_lowercase()))
+                        .map(|(subrecord, plugin_name)| (subrecord.clone(), plugin_name))
+                        .collect::<Vec<(LlElement, PluginName<'a>)>>();
+                    if subrecords.is_empty() {
+                        continue;
+                    }
+                    if cfg.strip_dangling {
+                        let dangling = subrecords.iter().map(|((name, _), _)| name.to_lowercase()).collect::<HashSet<String>>();
+                        o.list.retain(|(name, _)| !dangling.contains(&name.to_lowercase()));
+                        o.list_lowercased.retain(|(name, _)| !dangling.contains(name));
+                        o.first.retain(|((name, _), _)| !dangling.contains(name));
+                        o.delete.retain(|delta| !dangling.contains(&delta.subrecord_lowercased.0));
+                        o.stripped = true;
+                    }
+                    messages.dangling_subrecords.push(DanglingSubrecords {
+                        log_t: &cfg.$name.log_t,
+                        id: o.id.clone(),
+                        subrecords,
+                    });
+                }
+            }
+
+            pub(crate) fn [<check_nesting_ $short>](&self, messages: &mut LlMessages<'a>, cfg: &'a Cfg) {
+                check_nesting(
+                    self.0.iter().map(|o| (o.id.as_str(), o.list.as_slice())),
+                    usize::from(cfg.max_nesting_depth),
+                    &cfg.$name.log_t,
+                    messages,
+                );
+            }
+
             pub(crate) fn [<get_ $short>](&mut self, $short: $long, $helper: &mut InputHelper<'a>) {
                 match $helper.$name.ids.entry($short.id.to_lowercase()) {
                     Entry::Vacant(v) => {
+                        let explain = if v.key() == &$helper.cfg.explain {
+                            let list = &$short.$name;
+                            let no_weights = Weights::new();
+                            vec![ExplainStep::new(&$helper.plugin_info.name, list, list, &[], &no_weights, &no_weights, None)]
+                        } else {
+                            Vec::new()
+                        };
                         self.0.push($ll_long {
                             flags: vec![$short.flags],
                             id: $short.id,
                             $flags_kind: vec![$short.$flags_kind],
                             chance_nones: vec![$short.chance_none],
+                            list_flags_history: vec![($short.$flags_kind, &$helper.plugin_info.name)],
+                            chance_none_history: vec![($short.chance_none, &$helper.plugin_info.name)],
+                            list_plugins: vec![&$helper.plugin_info.name; $short.$name.len()],
                             list: $short.$name,
                             list_lowercased: Vec::new(),
                             first: Vec::new(),
+                            first_weights: Weights::new(),
                             delete: Vec::new(),
                             count: 1,
                             plugin_name_lowercased: &$helper.plugin_info.name_lowercased,
                             masters: vec![&$helper.plugin_info],
                             last: $last::default(),
                             last_plugin_name: None,
+                            stripped: false,
+                            explain,
                         });
                         v.insert($helper.$name.counter);
                         $helper.$name.counter += 1;
@@ -173,62 +326,55 @@ macro_rules! ll_record_methods {
                                 .zip(o.list.iter())
                                 .map(|(a, b)| (a.clone(), b.clone()))
                                 .collect();
+                            o.first_weights = get_weights(&o.list_lowercased);
                         };
                         let object_list_lowercased = $short
                             .$name
                             .iter()
                             .map(|(name, level)| (name.to_lowercase(), *level))
                             .collect::<Vec<_>>();
-                        if !$helper.cfg.no_delete
-                            && !($helper.cfg.extended_delete && $helper.cfg.never_delete.contains(&o.plugin_name_lowercased))
+                        let object_weights = get_weights(&object_list_lowercased);
+                        let removal_ignored = if $helper.cfg.no_delete {
+                            Some("--no-delete")
+                        } else if $helper.cfg.extended_delete && $helper.cfg.never_delete.contains(&o.plugin_name_lowercased) {
+                            Some("--never-delete")
+                        } else {
+                            None
+                        };
+                        if removal_ignored.is_none()
+                            && merge_removals(&o.first, &o.first_weights, &object_weights, &$helper.plugin_info.name, &mut o.delete)
                         {
-                            for (list_item_lowercased, list_item) in o.first.iter() {
-                                let object_count = object_list_lowercased.iter().filter(|x| x == &list_item_lowercased).count();
-                                let first_count = o.first.iter().filter(|(x, _)| x == list_item_lowercased).count();
-                                if object_count < first_count {
-                                    if o.delete
-                                        .iter()
-                                        .filter(|(subrecord_lowercased, _, _)| subrecord_lowercased == list_item_lowercased)
-                                        .count()
-                                        < (first_count - object_count)
-                                    {
-                                        o.delete.push((
-                                            list_item_lowercased.clone(),
-                                            list_item.clone(),
-                                            vec![&$helper.plugin_info.name],
-                                        ));
-                                        add_master = true;
-                                    } else {
-                                        for (_, _, responsible_plugins) in o
-                                            .delete
-                                            .iter_mut()
-                                            .filter(|(subrecord_lowercased, _, _)| subrecord_lowercased == list_item_lowercased)
-                                        {
-                                            if !responsible_plugins.contains(&&$helper.plugin_info.name) {
-                                                responsible_plugins.push(&$helper.plugin_info.name)
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                            add_master = true;
                         }
-                        for (index, list_item_lowercased) in object_list_lowercased.iter().enumerate() {
-                            if !o.list_lowercased.contains(list_item_lowercased)
-                                || object_list_lowercased.iter().filter(|x| x == &list_item_lowercased).count()
-                                    > o.list_lowercased.iter().filter(|x| x == &list_item_lowercased).count()
-                            {
-                                o.list_lowercased.push(list_item_lowercased.clone());
-                                o.list.push($short.$name[index].clone());
-                                add_master = true;
-                            } else {
-                                if o.first.iter().filter(|(x, _)| x == list_item_lowercased).count() == 0 {
-                                    add_master = true;
-                                }
-                            }
+                        let list_len = o.list.len();
+                        if merge_additions(
+                            &$short.$name,
+                            &object_list_lowercased,
+                            &object_weights,
+                            &o.first_weights,
+                            &mut o.list,
+                            &mut o.list_lowercased,
+                            &mut o.list_plugins,
+                            &$helper.plugin_info.name,
+                        ) {
+                            add_master = true;
+                        }
+                        if !o.explain.is_empty() {
+                            o.explain.push(ExplainStep::new(
+                                &$helper.plugin_info.name,
+                                &$short.$name,
+                                &o.list[list_len..],
+                                &o.first,
+                                &o.first_weights,
+                                &object_weights,
+                                removal_ignored,
+                            ));
                         }
                         if add_master {
                             o.masters.push(&$helper.plugin_info);
                         }
+                        o.list_flags_history.push(($short.$flags_kind, &$helper.plugin_info.name));
+                        o.chance_none_history.push(($short.chance_none, &$helper.plugin_info.name));
                         o.last.list = $short.$name;
                         o.last.flag = $short.flags;
                         o.last.list_flag = $short.$flags_kind;
@@ -0,0 +1,238 @@
+use super::messages::{msg_and_clear, msg_with_details_suggestion};
+use crate::{plural, Cfg, LlElement, Log, MsgTone};
+use anyhow::Result;
+use hashbrown::HashMap;
+use std::fmt::Write as _;
+use tes3::esp::{LeveledCreatureFlags, LeveledItemFlags};
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) trait ListFlags {
+    fn from_all_levels(&self) -> bool;
+    fn for_each_item(&self) -> bool;
+}
+
+impl ListFlags for LeveledCreatureFlags {
+    fn from_all_levels(&self) -> bool {
+        self.contains(LeveledCreatureFlags::CALCULATE_FROM_ALL_LEVELS)
+    }
+
+    fn for_each_item(&self) -> bool {
+        false
+    }
+}
+
+impl ListFlags for LeveledItemFlags {
+    fn from_all_levels(&self) -> bool {
+        self.contains(LeveledItemFlags::CALCULATE_FROM_ALL_LEVELS)
+    }
+
+    fn for_each_item(&self) -> bool {
+        self.contains(LeveledItemFlags::CALCULATE_FOR_EACH_ITEM)
+    }
+}
+
+struct SimulatedList {
+    id: String,
+    from_all_levels: bool,
+    for_each_item: bool,
+    chance_none: u8,
+    list: Vec<LlElement>,
+    delev: Option<Vec<LlElement>>,
+}
+
+// COMMENT: (id, probability) of leaf subrecords, whether cyclic nesting was met
+type Spawns = (Vec<(String, f64)>, bool);
+
+#[derive(Default)]
+pub(crate) struct SimulatedLists(HashMap<String, SimulatedList>);
+
+impl SimulatedLists {
+    pub(crate) fn push<F: ListFlags>(
+        &mut self,
+        id: &str,
+        flags: &F,
+        chance_none: u8,
+        list: &[LlElement],
+        delev: Option<&[LlElement]>,
+    ) {
+        self.0.insert(
+            id.to_lowercase(),
+            SimulatedList {
+                id: id.to_owned(),
+                from_all_levels: flags.from_all_levels(),
+                for_each_item: flags.for_each_item(),
+                chance_none,
+                list: list.to_vec(),
+                delev: delev.map(|delev| delev.to_vec()),
+            },
+        );
+    }
+
+    fn spawns<'a>(&'a self, id_low: &'a str, level: u16, delev: bool) -> Spawns {
+        let mut spawns = HashMap::new();
+        let mut stack = Vec::new();
+        let mut cyclic = false;
+        self.expand(id_low, level, delev, 1.0, &mut stack, &mut spawns, &mut cyclic);
+        let mut res = spawns.into_values().collect::<Vec<(String, f64)>>();
+        res.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase())));
+        (res, cyclic)
+    }
+
+    // COMMENT: picks uniformly among subrecords of the highest level up to player's, or of all of them with CALCULATE_FROM_ALL_LEVELS
+    #[allow(clippy::too_many_arguments)]
+    fn expand<'a>(
+        &'a self,
+        id_low: &'a str,
+        level: u16,
+        delev: bool,
+        probability: f64,
+        stack: &mut Vec<&'a str>,
+        spawns: &mut HashMap<String, (String, f64)>,
+        cyclic: &mut bool,
+    ) {
+        let Some(list) = self.0.get(id_low) else {
+            return;
+        };
+        if stack.contains(&id_low) {
+            *cyclic = true;
+            return;
+        }
+        let subrecords = match (&list.delev, delev) {
+            (Some(delev_list), true) => delev_list,
+            _ => &list.list,
+        };
+        let highest = subrecords.iter().map(|(_, sub_level)| *sub_level).filter(|sub_level| *sub_level <= level).max();
+        let candidates = subrecords
+            .iter()
+            .filter(|(_, sub_level)| *sub_level <= level && (list.from_all_levels || Some(*sub_level) == highest))
+            .collect::<Vec<&LlElement>>();
+        if candidates.is_empty() {
+            return;
+        }
+        let share = probability * (100 - list.chance_none.min(100)) as f64 / 100.0 / candidates.len() as f64;
+        stack.push(id_low);
+        for (name, _) in candidates {
+            let name_low = name.to_lowercase();
+            if let Some((nested_id_low, _)) = self.0.get_key_value(&name_low) {
+                self.expand(nested_id_low, level, delev, share, stack, spawns, cyclic);
+            } else {
+                spawns.entry(name_low).or_insert_with(|| (name.clone(), 0.0)).1 += share;
+            }
+        }
+        stack.pop();
+    }
+
+    fn describe(&self, log_t: &str, level: u16, compare_delev: bool, text: &mut String) -> Result<usize> {
+        let mut ids = self.0.keys().collect::<Vec<&String>>();
+        ids.sort();
+        let mut count = 0;
+        for id_low in ids {
+            let list = &self.0[id_low];
+            let (spawns, cyclic) = self.spawns(id_low, level, false);
+            let mut flags = String::new();
+            if list.from_all_levels {
+                flags.push('A');
+            }
+            if list.for_each_item {
+                flags.push('E');
+            }
+            if flags.is_empty() {
+                flags.push('-');
+            }
+            macro_rules! row {
+                ($($chance:expr),+; $spawn:expr) => {
+                    writeln!(
+                        text,
+                        "\t{:<1} {:<32} {:<5} {:>3}{} {}",
+                        log_t,
+                        list.id,
+                        flags,
+                        list.chance_none,
+                        [$(format!(" {:>7}", $chance)),+].concat(),
+                        $spawn
+                    )?
+                };
+            }
+            if compare_delev {
+                let (delev_spawns, _) = self.spawns(id_low, level, true);
+                if delev_spawns == spawns {
+                    continue;
+                }
+                let mut names = spawns.iter().chain(delev_spawns.iter()).map(|(name, _)| name).collect::<Vec<&String>>();
+                names.sort_by_key(|name| name.to_lowercase());
+                names.dedup_by_key(|name| name.to_lowercase());
+                for name in names {
+                    row!(percent(find(&spawns, name)), percent(find(&delev_spawns, name)); name);
+                }
+                row!(percent(nothing(&spawns)), percent(nothing(&delev_spawns)); "<nothing>");
+            } else {
+                for (name, probability) in spawns.iter() {
+                    row!(percent(*probability); name);
+                }
+                row!(percent(nothing(&spawns)); "<nothing>");
+            }
+            if cyclic {
+                row!("-"; "<cyclic nesting skipped>");
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+fn percent(probability: f64) -> String {
+    format!("{:.2}%", probability * 100.0)
+}
+
+fn find(spawns: &[(String, f64)], name: &str) -> f64 {
+    spawns
+        .iter()
+        .find(|(spawn, _)| spawn.eq_ignore_ascii_case(name))
+        .map_or(0.0, |(_, probability)| *probability)
+}
+
+fn nothing(spawns: &[(String, f64)]) -> f64 {
+    (1.0 - spawns.iter().map(|(_, probability)| probability).sum::<f64>()).max(0.0)
+}
+
+#[derive(Default)]
+pub(crate) struct LlSimulation {
+    pub(crate) creatures: SimulatedLists,
+    pub(crate) items: SimulatedLists,
+}
+
+impl LlSimulation {
+    pub(crate) fn show(&self, cfg: &Cfg, log: &mut Log) -> Result<()> {
+        let level = cfg.simulate;
+        let compare_delev = cfg.delev;
+        let details = cfg.guts.verboseness_details_simulation;
+        let mut table = String::with_capacity(cfg.guts.long_message_string_inital_capacity);
+        let mut count = self.creatures.describe(&cfg.creatures.log_t, level, compare_delev, &mut table)?;
+        count += self.items.describe(&cfg.items.log_t, level, compare_delev, &mut table)?;
+        let mut text = format!(
+            "Spawn probabilities at player level {level} {} for {count} leveled list{}",
+            if compare_delev { "were changed by delev" } else { "were simulated" },
+            plural("s", count)?,
+        );
+        if count == 0 {
+            return msg_and_clear(&mut text, MsgTone::Neutral, 0, cfg, log);
+        }
+        msg_with_details_suggestion(&mut text, MsgTone::Neutral, 0, details, cfg, log)?;
+        if !(cfg.no_log && cfg.verbose < details) {
+            if compare_delev {
+                writeln!(
+                    text,
+                    "\n\t{:<1} {:<32} {:<5} {:>3} {:>7} {:>7} SPAWN",
+                    "T", "LEVELED LIST", "FLAGS", "CN", "BEFORE", "AFTER"
+                )?;
+            } else {
+                writeln!(text, "\n\t{:<1} {:<32} {:<5} {:>3} {:>7} SPAWN", "T", "LEVELED LIST", "FLAGS", "CN", "CHANCE")?;
+            }
+            text.push_str(&table);
+            msg_and_clear(&mut text, MsgTone::Neutral, details, cfg, log)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use super::SimulatedLists;
+use pretty_assertions::assert_eq;
+use tes3::esp::{LeveledCreatureFlags, LeveledItemFlags};
+
+fn list(subrecords: &[(&str, u16)]) -> Vec<(String, u16)> {
+    subrecords.iter().map(|(name, level)| (name.to_string(), *level)).collect()
+}
+
+fn rounded(spawns: Vec<(String, f64)>) -> Vec<(String, f64)> {
+    spawns.into_iter().map(|(name, probability)| (name, (probability * 10000.0).round() / 10000.0)).collect()
+}
+
+#[test]
+fn highest_level_only() {
+    let mut lists = SimulatedLists::default();
+    let subrecords = list(&[("rat", 1), ("mudcrab", 1), ("kagouti", 5), ("alit", 5), ("ogrim", 20)]);
+    lists.push("ex_creatures", &LeveledCreatureFlags::empty(), 0, &subrecords, None);
+    let (spawns, cyclic) = lists.spawns("ex_creatures", 7, false);
+    assert_eq!(rounded(spawns), [("alit".to_string(), 0.5), ("kagouti".to_string(), 0.5)]);
+    assert!(!cyclic);
+}
+
+#[test]
+fn from_all_levels_with_chance_none() {
+    let mut lists = SimulatedLists::default();
+    let subrecords = list(&[("rat", 1), ("mudcrab", 1), ("kagouti", 5), ("ogrim", 20)]);
+    lists.push("ex_creatures", &LeveledCreatureFlags::CALCULATE_FROM_ALL_LEVELS, 40, &subrecords, None);
+    let (spawns, _) = lists.spawns("ex_creatures", 7, false);
+    assert_eq!(
+        rounded(spawns),
+        [("kagouti".to_string(), 0.2), ("mudcrab".to_string(), 0.2), ("rat".to_string(), 0.2)]
+    );
+}
+
+#[test]
+fn nested_lists_and_delev() {
+    let mut lists = SimulatedLists::default();
+    let flags = LeveledItemFlags::CALCULATE_FROM_ALL_LEVELS | LeveledItemFlags::CALCULATE_FOR_EACH_ITEM;
+    let nested = list(&[("iron dagger", 1), ("glass dagger", 15)]);
+    lists.push("l_daggers", &flags, 50, &nested, Some(&list(&[("iron dagger", 1), ("glass dagger", 1)])));
+    lists.push("l_loot", &flags, 0, &list(&[("gold_001", 1), ("L_Daggers", 1)]), None);
+    let (before, _) = lists.spawns("l_loot", 1, false);
+    assert_eq!(rounded(before), [("gold_001".to_string(), 0.5), ("iron dagger".to_string(), 0.25)]);
+    let (after, _) = lists.spawns("l_loot", 1, true);
+    assert_eq!(
+        rounded(after),
+        [
+            ("gold_001".to_string(), 0.5),
+            ("glass dagger".to_string(), 0.125),
+            ("iron dagger".to_string(), 0.125)
+        ]
+    );
+}
+
+#[test]
+fn cyclic_nesting() {
+    let mut lists = SimulatedLists::default();
+    lists.push("a", &LeveledCreatureFlags::empty(), 0, &list(&[("b", 1)]), None);
+    lists.push("b", &LeveledCreatureFlags::empty(), 0, &list(&[("a", 1), ("rat", 1)]), None);
+    let (spawns, cyclic) = lists.spawns("a", 1, false);
+    assert_eq!(rounded(spawns), [("rat".to_string(), 0.5)]);
+    assert!(cyclic);
+}
@@ -0,0 +1,52 @@
+use super::messages::{LlMessages, MergedValues};
+use crate::{MergeStrategy, PluginName};
+
+#[cfg(test)]
+mod tests;
+
+// COMMENT: history contains value of each plugin with the list in load order
+pub(super) fn merge_values<T: Copy + PartialEq>(
+    history: &[(T, PluginName)],
+    strategy: MergeStrategy,
+    min: fn(T, T) -> T,
+    max: fn(T, T) -> T,
+) -> T {
+    let values = history.iter().map(|(value, _)| *value);
+    let first = history[0].0;
+    match strategy {
+        MergeStrategy::Novel => values
+            .enumerate()
+            .filter(|(index, value)| !history[..*index].iter().any(|(previous, _)| previous == value))
+            .last()
+            .map_or(first, |(_, value)| value),
+        MergeStrategy::Last => history[history.len() - 1].0,
+        MergeStrategy::First => first,
+        MergeStrategy::Min => values.reduce(min).unwrap(),
+        MergeStrategy::Max => values.reduce(max).unwrap(),
+        MergeStrategy::Changed => values.rev().find(|value| value != &first).unwrap_or(first),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn log_merged_values<'a, T: Copy + PartialEq>(
+    history: &[(T, PluginName<'a>)],
+    merged: T,
+    show: fn(&T) -> String,
+    field: &'static str,
+    strategy: MergeStrategy,
+    id: &str,
+    log_t: &'a str,
+    messages: &mut LlMessages<'a>,
+) {
+    if history.iter().all(|(value, _)| value == &history[0].0) {
+        return;
+    }
+    messages.merged_values.push(MergedValues {
+        log_t,
+        id: id.to_owned(),
+        field,
+        strategy: strategy.name(),
+        merged: show(&merged),
+        values: history.iter().map(|(value, plugin_name)| (show(value), *plugin_name)).collect(),
+    });
+}
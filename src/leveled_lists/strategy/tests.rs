@@ -0,0 +1,52 @@
+use super::merge_values;
+use crate::MergeStrategy;
+use pretty_assertions::assert_eq;
+use std::{
+    cmp::{max, min},
+    ops::{BitAnd, BitOr},
+};
+use tes3::esp::LeveledItemFlags;
+
+fn merge(values: &[u8], strategy: MergeStrategy) -> u8 {
+    let plugin_name = String::from("plugin.esp");
+    let history = values.iter().map(|value| (*value, &plugin_name)).collect::<Vec<_>>();
+    merge_values(&history, strategy, min, max)
+}
+
+#[test]
+fn chance_none_strategies() {
+    let values = [50, 30, 70, 50];
+    assert_eq!(merge(&values, MergeStrategy::Novel), 70);
+    assert_eq!(merge(&values, MergeStrategy::Last), 50);
+    assert_eq!(merge(&values, MergeStrategy::First), 50);
+    assert_eq!(merge(&values, MergeStrategy::Min), 30);
+    assert_eq!(merge(&values, MergeStrategy::Max), 70);
+    assert_eq!(merge(&values, MergeStrategy::Changed), 70);
+}
+
+#[test]
+fn novel_skips_values_restated_from_previous_plugins() {
+    assert_eq!(merge(&[50, 30, 50], MergeStrategy::Novel), 30);
+    assert_eq!(merge(&[50, 50], MergeStrategy::Novel), 50);
+}
+
+#[test]
+fn changed_keeps_first_value_when_nothing_changed() {
+    assert_eq!(merge(&[25, 25, 25], MergeStrategy::Changed), 25);
+    assert_eq!(merge(&[25], MergeStrategy::Changed), 25);
+}
+
+#[test]
+fn list_flags_min_and_max() {
+    let plugin_name = String::from("plugin.esp");
+    let history = [
+        (LeveledItemFlags::CALCULATE_FROM_ALL_LEVELS | LeveledItemFlags::CALCULATE_FOR_EACH_ITEM, &plugin_name),
+        (LeveledItemFlags::CALCULATE_FROM_ALL_LEVELS, &plugin_name),
+        (LeveledItemFlags::empty(), &plugin_name),
+    ];
+    assert_eq!(merge_values(&history, MergeStrategy::Min, BitAnd::bitand, BitOr::bitor), LeveledItemFlags::empty());
+    assert_eq!(
+        merge_values(&history, MergeStrategy::Max, BitAnd::bitand, BitOr::bitor),
+        LeveledItemFlags::CALCULATE_FROM_ALL_LEVELS | LeveledItemFlags::CALCULATE_FOR_EACH_ITEM
+    );
+}
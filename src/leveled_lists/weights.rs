@@ -0,0 +1,98 @@
+use crate::{LlElement, PluginName, ResponsiblePlugins};
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(test)]
+mod tests;
+
+// COMMENT: duplicate subrecords act as weights, so lists are merged as multisets of subrecords
+pub(crate) type Weights = HashMap<LlElement, usize>;
+
+// COMMENT: the largest number of copies of the first plugin's subrecord removed by any single plugin
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct WeightDelta<'a> {
+    pub(crate) subrecord_lowercased: LlElement,
+    pub(crate) subrecord: LlElement,
+    pub(crate) removed: usize,
+    pub(crate) responsible_plugins: ResponsiblePlugins<'a>,
+}
+
+pub(super) fn get_weights(list_lowercased: &[LlElement]) -> Weights {
+    let mut weights = Weights::new();
+    for subrecord in list_lowercased {
+        *weights.entry(subrecord.clone()).or_insert(0) += 1;
+    }
+    weights
+}
+
+// COMMENT: returns true when plugin removed more copies than any of the previous plugins
+pub(super) fn merge_removals<'a>(
+    first: &[(LlElement, LlElement)],
+    first_weights: &Weights,
+    object_weights: &Weights,
+    plugin_name: PluginName<'a>,
+    delete: &mut Vec<WeightDelta<'a>>,
+) -> bool {
+    let mut is_changed = false;
+    let mut seen = HashSet::new();
+    for (subrecord_lowercased, subrecord) in first {
+        if !seen.insert(subrecord_lowercased) {
+            continue;
+        }
+        let first_weight = first_weights[subrecord_lowercased];
+        let object_weight = object_weights.get(subrecord_lowercased).copied().unwrap_or(0);
+        if object_weight >= first_weight {
+            continue;
+        }
+        let removed = first_weight - object_weight;
+        match delete.iter_mut().find(|delta| &delta.subrecord_lowercased == subrecord_lowercased) {
+            Some(delta) => {
+                if removed > delta.removed {
+                    delta.removed = removed;
+                    is_changed = true;
+                }
+                if !delta.responsible_plugins.contains(&plugin_name) {
+                    delta.responsible_plugins.push(plugin_name);
+                }
+            }
+            None => {
+                delete.push(WeightDelta {
+                    subrecord_lowercased: subrecord_lowercased.clone(),
+                    subrecord: subrecord.clone(),
+                    removed,
+                    responsible_plugins: vec![plugin_name],
+                });
+                is_changed = true;
+            }
+        }
+    }
+    is_changed
+}
+
+// COMMENT: returns true when plugin added copies or subrecords missing from the first plugin
+#[allow(clippy::too_many_arguments)]
+pub(super) fn merge_additions<'a>(
+    object_list: &[LlElement],
+    object_list_lowercased: &[LlElement],
+    object_weights: &Weights,
+    first_weights: &Weights,
+    list: &mut Vec<LlElement>,
+    list_lowercased: &mut Vec<LlElement>,
+    list_plugins: &mut Vec<PluginName<'a>>,
+    plugin_name: PluginName<'a>,
+) -> bool {
+    let mut is_changed = false;
+    let mut merged_weights = get_weights(list_lowercased);
+    for (subrecord_lowercased, subrecord) in object_list_lowercased.iter().zip(object_list) {
+        let merged_weight = merged_weights.entry(subrecord_lowercased.clone()).or_insert(0);
+        if object_weights[subrecord_lowercased] > *merged_weight {
+            *merged_weight += 1;
+            list_lowercased.push(subrecord_lowercased.clone());
+            list.push(subrecord.clone());
+            list_plugins.push(plugin_name);
+            is_changed = true;
+        } else if !first_weights.contains_key(subrecord_lowercased) {
+            is_changed = true;
+        }
+    }
+    is_changed
+}
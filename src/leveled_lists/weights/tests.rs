@@ -0,0 +1,60 @@
+use super::{get_weights, merge_additions, merge_removals, WeightDelta};
+use crate::LlElement;
+use pretty_assertions::assert_eq;
+
+fn list(src: &[(&str, u16)]) -> Vec<LlElement> {
+    src.iter().map(|(name, level)| (name.to_string(), *level)).collect()
+}
+
+fn lowercased(list: &[LlElement]) -> Vec<LlElement> {
+    list.iter().map(|(name, level)| (name.to_lowercase(), *level)).collect()
+}
+
+#[test]
+fn removals_keep_the_largest_per_plugin_delta() {
+    let (plugin_a, plugin_b) = (String::from("a.esp"), String::from("b.esp"));
+    let first = lowercased(&list(&[("Iron Dagger", 1), ("iron dagger", 1), ("iron dagger", 1), ("rat", 1)]));
+    let first = first.iter().cloned().zip(first.iter().cloned()).collect::<Vec<_>>();
+    let first_weights = get_weights(&first.iter().map(|(subrecord, _)| subrecord.clone()).collect::<Vec<_>>());
+    let mut delete = Vec::new();
+    let object_a = get_weights(&lowercased(&list(&[("iron dagger", 1), ("iron dagger", 1), ("rat", 1)])));
+    assert!(merge_removals(&first, &first_weights, &object_a, &plugin_a, &mut delete));
+    let object_b = get_weights(&lowercased(&list(&[("iron dagger", 1), ("iron dagger", 1), ("iron dagger", 1), ("rat", 1)])));
+    assert!(!merge_removals(&first, &first_weights, &object_b, &plugin_b, &mut delete));
+    let object_b = get_weights(&lowercased(&list(&[("iron dagger", 1), ("rat", 1)])));
+    assert!(merge_removals(&first, &first_weights, &object_b, &plugin_b, &mut delete));
+    assert_eq!(
+        delete,
+        [WeightDelta {
+            subrecord_lowercased: ("iron dagger".to_string(), 1),
+            subrecord: ("iron dagger".to_string(), 1),
+            removed: 2,
+            responsible_plugins: vec![&plugin_a, &plugin_b],
+        }]
+    );
+}
+
+#[test]
+fn additions_raise_weights_to_the_largest_one() {
+    let plugin_name = String::from("a.esp");
+    let mut merged = list(&[("rat", 1), ("Iron Dagger", 1)]);
+    let mut merged_lowercased = lowercased(&merged);
+    let first_weights = get_weights(&merged_lowercased);
+    let mut list_plugins = vec![&plugin_name; 2];
+    let object = list(&[("iron dagger", 1), ("iron dagger", 1), ("iron dagger", 1)]);
+    let object_lowercased = lowercased(&object);
+    let object_weights = get_weights(&object_lowercased);
+    assert!(merge_additions(
+        &object,
+        &object_lowercased,
+        &object_weights,
+        &first_weights,
+        &mut merged,
+        &mut merged_lowercased,
+        &mut list_plugins,
+        &plugin_name,
+    ));
+    assert_eq!(get_weights(&merged_lowercased)[&("iron dagger".to_string(), 1)], 3);
+    assert_eq!(merged.len(), 4);
+    assert_eq!(list_plugins.len(), 4);
+}
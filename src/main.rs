@@ -27,19 +27,25 @@ mod merge;
 mod output;
 mod show_result;
 mod util;
-use config::{get_self_config, Cfg, DelevSkipPatterns, ListKind, OutputFile, PluginKind};
+use config::{
+    get_self_config, Cfg, DelevRule, DelevSkipPatterns, DelevTarget, Guts, ListKind, MergeStrategy, OutputFile, PluginKind,
+    RelevelTransform,
+};
 use get_plugins::{get_plugins, get_plugins_to_compare, PluginInfo};
 use input::{
     get_records,
     merge::{
-        BirthsignRecordMap, CellKey, CellRecordMap, ContainerRecordMap, CreatureRecordMap, IntermediateRecords, Merge, NpcRecordMap,
-        RaceRecordMap, RecordMap,
+        AlchemyRecordMap, BirthsignRecordMap, CellKey, CellRecordMap, ContainerRecordMap, CreatureRecordMap, EnchantingRecordMap,
+        FactionRecordMap, GameSettingRecordMap, IntermediateRecords, Land, LandRecordMap, Merge, NpcRecordMap, PathGridRecordMap,
+        RaceRecordMap, RecordMap, RegionRecordMap, ScriptRecordMap, SpellRecordMap, Topic, TopicRecordMap,
     },
-    structs::{InputHelper, PluginName, ReadStats, ResponsiblePlugins},
+    structs::{InputHelper, KnownIds, PluginName, ReadStats, ResponsiblePlugins},
 };
 use leveled_lists::{
+    graph::LlGraph,
     messages::LlMessages,
-    records::{LlCreatureRecords, LlElement, LlItemRecords},
+    records::{append_masters, LlCreatureRecords, LlElement, LlItemRecords},
+    simulation::LlSimulation,
 };
 use merge::{merge_records, RawPlugins};
 use output::process_output;
@@ -58,6 +64,7 @@ fn main() {
         // 0: Ok / Plugins are the same in --compare-only mode
         // 1: Error
         // 2: Some leveled lists should probably be excluded from subrecords deletion mode
+        //    or nested leveled lists are cyclic or too deep
         // 3: Plugins are different in --compare-only mode
         Ok(exit_code) => {
             // println!("PEAK MEMORY USAGE: {:.0}MB", PEAK_ALLOC.peak_usage_as_mb()); // slows down the program too much
@@ -84,10 +91,11 @@ fn run() -> Result<i32> {
     // cfg.show_merge_types(&mut log)?;
     let plugins_to_compare = get_plugins_to_compare(&cfg, &mut log).with_context(|| "Failed to get plugins for comparison")?;
     let plugins = get_plugins(&cfg, &mut log).with_context(|| "Failed to get plugins")?;
-    let (ll_creatures, ll_items, intermediate_records, record_read_stats) =
+    let (ll_creatures, ll_items, intermediate_records, known_ids, record_read_stats) =
         get_records(&plugins, &cfg, &mut log).with_context(|| "Failed to get records")?;
     let (raw_plugins, mut counts, mut exit_code) =
-        merge_records(ll_creatures, ll_items, intermediate_records, &cfg, &mut log).with_context(|| "Failed to merge records")?;
+        merge_records(&plugins, ll_creatures, ll_items, intermediate_records, known_ids, &cfg, &mut log)
+            .with_context(|| "Failed to merge records")?;
     process_output(plugins_to_compare, raw_plugins, &mut counts, &mut exit_code, &cfg, &mut log)
         .with_context(|| "Failed to process output")?;
     show_result(timer, record_read_stats, counts, &cfg, &mut log)?;
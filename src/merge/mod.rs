@@ -1,4 +1,7 @@
-use crate::{Cfg, IntermediateRecords, ListCounts, LlCreatureRecords, LlItemRecords, LlMessages, Log};
+use crate::{
+    msg, Cfg, IntermediateRecords, KnownIds, ListCounts, LlCreatureRecords, LlGraph, LlItemRecords, LlMessages, LlSimulation, Log,
+    MsgTone, PluginInfo,
+};
 use anyhow::{Context, Result};
 use paste::paste;
 use rand::rngs::ThreadRng;
@@ -9,10 +12,17 @@ mod subrecord_helpers;
 use log::{MergeLog, OptionRecordMergeLog};
 use raw_plugins::RawPlugin;
 pub(crate) use raw_plugins::RawPlugins;
-use ref_records::{merge_bsgn, merge_cell, merge_cont, merge_crea, merge_mgef, merge_npc_, merge_race, merge_skil};
+use ref_records::{
+    check_script_conflicts, check_sleep_creatures, leveled_creatures_after_merge, merge_acti, merge_alch, merge_appa, merge_armo,
+    merge_body, merge_book, merge_bsgn, merge_cell, merge_clas, merge_clot, merge_cont, merge_crea, merge_dial, merge_door, merge_ench,
+    merge_fact, merge_gmst, merge_ingr, merge_land, merge_ligh, merge_lock, merge_ltex, merge_mgef, merge_misc, merge_npc_, merge_pgrd,
+    merge_prob, merge_race, merge_regn, merge_repa, merge_skil, merge_sndg, merge_soun, merge_spel, merge_stat, merge_weap,
+    remap_reference_masters,
+};
 use subrecord_helpers::{
-    ai_package_variant, ai_packages_equal, inventory_to_lowercase, sort_travel_destinations, spell_to_lowercase,
-    travel_destination_to_lowercase, AiPackagesHelper, InventoryHelper, SpellsHelper, TravelDestinationsHelper,
+    ai_package_variant, ai_packages_equal, inventory_to_lowercase, reaction_to_lowercase, sort_travel_destinations,
+    sound_to_lowercase, spell_to_lowercase, travel_destination_to_lowercase, AiPackagesHelper, EffectsHelper, InventoryHelper,
+    RankNamesHelper, ReactionsHelper, ReferencesHelper, SoundsHelper, SpellsHelper, TravelDestinationsHelper,
 };
 
 #[cfg(test)]
@@ -22,20 +32,35 @@ use subrecord_helpers::{
 };
 
 pub(super) fn merge_records<'a>(
-    creatures: LlCreatureRecords<'a>,
-    items: LlItemRecords<'a>,
-    im2_records: IntermediateRecords,
+    plugins: &'a [PluginInfo],
+    mut creatures: LlCreatureRecords<'a>,
+    mut items: LlItemRecords<'a>,
+    mut im2_records: IntermediateRecords,
+    known_ids: KnownIds,
     cfg: &'a Cfg,
     log: &mut Log,
 ) -> Result<(RawPlugins<'a>, ListCounts, i32)> {
     let mut raw_plugins = RawPlugins::new(cfg);
     let mut counts = ListCounts::default();
     let mut messages = LlMessages::new();
+    let mut simulation = LlSimulation::default();
+    let mut graph = LlGraph::default();
     if cfg.compare_only {
         return Ok((raw_plugins, counts, messages.exit_code()));
     }
+    if !cfg.creatures.skip {
+        creatures.check_dangling_levc(&known_ids.creatures, &mut messages, cfg);
+        creatures.check_nesting_levc(&mut messages, cfg);
+    }
+    if !cfg.items.skip {
+        items.check_dangling_levi(&known_ids.items, &mut messages, cfg);
+        items.check_nesting_levi(&mut messages, cfg);
+    }
     let mut merge_log = MergeLog::default();
     if !(cfg.merge.skip && cfg.multipatch.skip) {
+        if cfg.meta.read_groundcover {
+            im2_records.append_groundcover_cells();
+        }
         macro_rules! select_merge_condition {
             ($short:ident) => {
                 cfg.merge.$short
@@ -53,28 +78,92 @@ pub(super) fn merge_records<'a>(
             }};
         }
         merge!(
+            gmst,
+            clas,
+            fact,
             race,
+            soun,
             skil,
             mgef,
+            regn,
             bsgn,
+            ltex,
+            spel,
+            stat,
+            door,
+            misc,
+            weap,
             cont,
             crea::cfg.multipatch.summons,
+            body,
+            ligh,
+            ench,
             npc_,
-            cell::cfg.multipatch.cellnames || cfg.multipatch.fogbug
+            armo,
+            clot,
+            repa,
+            acti,
+            appa,
+            lock,
+            prob,
+            ingr,
+            book,
+            alch,
+            cell::cfg.multipatch.cellnames || cfg.multipatch.fogbug,
+            land::cfg.merge.ltex,
+            pgrd,
+            sndg,
+            dial
         );
     }
 
+    if !cfg.explain.is_empty()
+        && !creatures.0.iter().any(|levc| !levc.explain.is_empty())
+        && !items.0.iter().any(|levi| !levi.explain.is_empty())
+    {
+        let text = format!("Leveled list \"{}\" to explain was not found", cfg.explain);
+        msg(text, MsgTone::Ugly, 0, cfg, log)?;
+    }
     let mut rng = ThreadRng::default();
     if !cfg.creatures.skip {
-        creatures.make_levc(&mut raw_plugins, &mut messages, &mut counts, &mut rng, cfg, log)?;
+        creatures.make_levc(&mut raw_plugins, &mut messages, &mut simulation, &mut graph, &mut counts, &mut rng, cfg, log)?;
     }
     if !cfg.items.skip {
-        items.make_levi(&mut raw_plugins, &mut messages, &mut counts, &mut rng, cfg, log)?;
+        items.make_levi(&mut raw_plugins, &mut messages, &mut simulation, &mut graph, &mut counts, &mut rng, cfg, log)?;
+    }
+    if cfg.merge.references {
+        remap_reference_masters(
+            &mut raw_plugins.merge,
+            plugins,
+            if cfg.delev && cfg.delev_distinct {
+                &mut counts.merge.master
+            } else {
+                &mut counts.total.master
+            },
+            cfg,
+            log,
+        )
+        .with_context(|| "Failed to remap masters of cell references")?;
+    }
+
+    if cfg.meta.graph {
+        graph.write(cfg, log).with_context(|| "Failed to export leveled lists graph")?;
     }
 
     if !cfg.meta.silent {
+        if cfg.merge.regn {
+            let leveled_creatures = leveled_creatures_after_merge(&raw_plugins.merge, &known_ids.leveled_creatures);
+            check_sleep_creatures(&im2_records, &raw_plugins.merge, &leveled_creatures, cfg, &mut merge_log)
+                .with_context(|| "Failed to check REGN sleep creatures")?;
+        }
         merge_log.msg(cfg, log)?;
+        if cfg.merge.scpt {
+            check_script_conflicts(&im2_records, cfg, log).with_context(|| "Failed to check SCPT conflicts")?;
+        }
         messages.show(&counts, cfg, log)?;
+        if cfg.simulate > 0 {
+            simulation.show(cfg, log).with_context(|| "Failed to show leveled lists simulation")?;
+        }
     }
 
     Ok((raw_plugins, counts, messages.exit_code()))
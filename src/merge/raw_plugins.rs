@@ -9,6 +9,7 @@ type Masters<'a> = HashMap<PluginName<'a>, (usize, PluginName<'a>, u64)>;
 pub(crate) struct RawPlugins<'a> {
     pub(crate) merge: RawPlugin<'a>,
     pub(crate) delev: RawPlugin<'a>,
+    pub(crate) relevel: RawPlugin<'a>,
 }
 
 impl RawPlugins<'_> {
@@ -20,6 +21,11 @@ impl RawPlugins<'_> {
             } else {
                 RawPlugin::default()
             },
+            relevel: if cfg.relevel {
+                RawPlugin::new()
+            } else {
+                RawPlugin::default()
+            },
         }
     }
 }
@@ -29,7 +35,7 @@ pub(crate) struct RawPlugin<'a> {
     pub(crate) masters: Masters<'a>,
 }
 
-impl RawPlugin<'_> {
+impl<'a> RawPlugin<'a> {
     fn new() -> Self {
         let mut res = Self::default();
         res.plugin.objects.push(TES3Object::Header(Header::default()));
@@ -63,4 +69,15 @@ impl RawPlugin<'_> {
         masters_sorted.sort();
         masters_sorted.into_iter().map(|(_, name, size)| (name.to_owned(), size)).collect()
     }
+
+    // COMMENT: same order as in make_masters, index 0 is reserved for the plugin itself
+    pub(crate) fn master_indices(&self) -> HashMap<PluginName<'a>, u32> {
+        let mut masters_sorted: Vec<(usize, PluginName<'a>, u64)> = self.masters.values().cloned().collect();
+        masters_sorted.sort();
+        masters_sorted
+            .into_iter()
+            .zip(1u32..)
+            .map(|((_, name, _), index)| (name, index))
+            .collect()
+    }
 }
@@ -1,32 +1,93 @@
 use super::{
-    ai_package_variant, ai_packages_equal, inventory_to_lowercase, sort_travel_destinations, spell_to_lowercase,
-    travel_destination_to_lowercase, AiPackagesHelper, InventoryHelper, MergeLog, OptionRecordMergeLog, RawPlugin, SpellsHelper,
+    ai_package_variant, ai_packages_equal, inventory_to_lowercase, reaction_to_lowercase, sort_travel_destinations,
+    sound_to_lowercase, spell_to_lowercase, travel_destination_to_lowercase, AiPackagesHelper, EffectsHelper, InventoryHelper,
+    MergeLog, OptionRecordMergeLog, RankNamesHelper, RawPlugin, ReactionsHelper, ReferencesHelper, SoundsHelper, SpellsHelper,
     TravelDestinationsHelper,
 };
 use paste::paste;
 use tes3::esp::{ObjectFlags, ServiceFlags};
+mod acti;
+mod alch;
+mod appa;
+mod armo;
+mod body;
+mod book;
 mod bsgn;
 mod cell;
+mod clas;
+mod clot;
 mod cont;
 mod crea;
+mod dial;
+mod door;
+mod ench;
+mod fact;
 mod generic;
+mod gmst;
+mod ingr;
+mod land;
+mod ligh;
+mod lock;
+mod ltex;
 mod mgef;
+mod misc;
 mod npc_;
+mod pgrd;
+mod prob;
 mod race;
+mod regn;
+mod repa;
+mod scpt;
 mod skil;
+mod sndg;
+mod soun;
+mod spel;
+mod stat;
+mod weap;
+pub(super) use acti::merge_acti;
+pub(super) use alch::merge_alch;
+pub(super) use appa::merge_appa;
+pub(super) use armo::merge_armo;
+pub(super) use body::merge_body;
+pub(super) use book::merge_book;
 pub(super) use bsgn::merge_bsgn;
-pub(super) use cell::merge_cell;
+pub(super) use cell::{merge_cell, remap_reference_masters};
+pub(super) use clas::merge_clas;
+pub(super) use clot::merge_clot;
 pub(super) use cont::merge_cont;
 pub(super) use crea::merge_crea;
+pub(super) use dial::merge_dial;
+pub(super) use door::merge_door;
+pub(super) use ench::merge_ench;
+pub(super) use fact::merge_fact;
 use generic::{
-    count_changes, fields_are_equal, generic_make_merge, generic_ref_record_method_inventory, generic_ref_record_method_spells,
-    generic_ref_record_method_travel_destinations, generic_ref_record_methods, print_as, show_flags, LowInventory,
-    LowInventorySpellsTravelDestinations, LowSpells,
+    count_changes, fields_are_equal, generic_make_merge, generic_ref_record_method_effects, generic_ref_record_method_inventory,
+    generic_ref_record_method_rank_names, generic_ref_record_method_reactions, generic_ref_record_method_references,
+    generic_ref_record_method_sounds, generic_ref_record_method_spells, generic_ref_record_method_travel_destinations,
+    generic_ref_record_methods, print_as, show_flags, LowEffects, LowInventory, LowInventorySpellsTravelDestinations,
+    LowRankNamesReactions, LowReferences, LowSounds, LowSpells,
 };
+pub(super) use gmst::merge_gmst;
+pub(super) use ingr::merge_ingr;
+pub(super) use land::merge_land;
+pub(super) use ligh::merge_ligh;
+pub(super) use lock::merge_lock;
+pub(super) use ltex::merge_ltex;
 pub(super) use mgef::merge_mgef;
+pub(super) use misc::merge_misc;
 pub(super) use npc_::merge_npc_;
+pub(super) use pgrd::merge_pgrd;
+pub(super) use prob::merge_prob;
 pub(super) use race::merge_race;
+pub(super) use regn::{check_sleep_creatures, leveled_creatures_after_merge, merge_regn};
+pub(super) use repa::merge_repa;
+pub(super) use scpt::check_script_conflicts;
 pub(super) use skil::merge_skil;
+pub(super) use sndg::merge_sndg;
+pub(super) use soun::merge_soun;
+pub(super) use spel::merge_spel;
+pub(super) use stat::merge_stat;
+pub(super) use weap::merge_weap;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,22 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Activator, ObjectFlags, TES3Object};
+
+pub(crate) struct ActiRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+}
+
+generic_ref_record_methods!((ActiRef, Activator, id), (name, script, mesh), (flags), (), (), ());
+
+generic_make_merge!(acti, (ActiRef, Activator, id), (flags=ObjectFlags, name.&, script.&, mesh.&), (), (), (), ());
@@ -0,0 +1,54 @@
+use super::{
+    generic_make_merge, generic_ref_record_method_effects, generic_ref_record_methods, print_as, show_flags, show_object_flags,
+    EffectsHelper, LowEffects, MergeLog, OptionRecordMergeLog, RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Alchemy, AlchemyData, AlchemyFlags, Effect, ObjectFlags, TES3Object};
+
+pub(crate) struct AlchRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub data: AlchemyData,
+    pub effects: Vec<Effect>,
+    pub base: &'a Alchemy,
+    pub low: LowEffects,
+}
+
+show_flags!(AlchemyFlags, AUTO_CALCULATE);
+
+generic_ref_record_method_effects!(AlchRef);
+generic_ref_record_methods!(
+    (AlchRef, Alchemy, id),
+    (name, script, mesh, icon),
+    (flags, data),
+    (LowEffects),
+    (effects),
+    ()
+);
+
+generic_make_merge!(
+    alch,
+    (AlchRef, Alchemy, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        data:weight,
+        data:value,
+        data:flags=AlchemyFlags
+    ),
+    (effects),
+    (),
+    (),
+    ()
+);
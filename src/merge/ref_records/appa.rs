@@ -0,0 +1,32 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Apparatus, ApparatusData, ObjectFlags, TES3Object};
+
+pub(crate) struct AppaRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub data: ApparatusData,
+}
+
+generic_ref_record_methods!((AppaRef, Apparatus, id), (name, script, mesh, icon), (flags, data), (), (), ());
+
+generic_make_merge!(
+    appa,
+    (AppaRef, Apparatus, id),
+    (flags=ObjectFlags, name.&, script.&, mesh.&, icon.&, data:apparatus_type, data:quality, data:weight, data:value),
+    (),
+    (),
+    (),
+    ()
+);
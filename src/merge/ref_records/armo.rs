@@ -0,0 +1,55 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Armor, ArmorData, BipedObject, ObjectFlags, TES3Object};
+
+pub(crate) struct ArmoRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub enchanting: &'a str,
+    pub data: ArmorData,
+    pub biped_objects: &'a Vec<BipedObject>,
+}
+
+generic_ref_record_methods!(
+    (ArmoRef, Armor, id),
+    (name, script, mesh, icon, enchanting, biped_objects),
+    (flags, data),
+    (),
+    (),
+    ()
+);
+
+generic_make_merge!(
+    armo,
+    (ArmoRef, Armor, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        enchanting.&,
+        data:armor_type,
+        data:weight,
+        data:value,
+        data:health,
+        data:enchantment,
+        data:armor_rating,
+        biped_objects.&
+    ),
+    (),
+    (),
+    (),
+    ()
+);
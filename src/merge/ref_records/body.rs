@@ -0,0 +1,37 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_flags, show_object_flags, MergeLog, OptionRecordMergeLog,
+    RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Bodypart, BodypartData, BodypartFlags, ObjectFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct BodyRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub mesh: &'a str,
+    pub data: BodypartData,
+}
+
+show_flags!(BodypartFlags, FEMALE, NOT_PLAYABLE);
+
+generic_ref_record_methods!((BodyRef, Bodypart, id), (name, mesh), (flags, data), (), (), ());
+
+generic_make_merge!(
+    body,
+    (BodyRef, Bodypart, id),
+    (flags=ObjectFlags, name.&, mesh.&, data:part, data:vampire, data:flags=BodypartFlags, data:bodypart_type),
+    (),
+    (),
+    (),
+    ()
+);
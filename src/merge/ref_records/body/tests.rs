@@ -0,0 +1,36 @@
+use super::{assert_eq_inner, merge_body, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Bodypart, ObjectFlags, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(body, Bodypart, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(body, Bodypart, values_string:name);
+    }
+
+    mod mesh {
+        use super::{assert_eq, *};
+        test_basic!(body, Bodypart, values_string:mesh);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            body,
+            Bodypart,
+            "Merged BODY record: \"\"\n",
+            "Merging BODY record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"mesh\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged BODY record: \"\"\n",
+            values_string:mesh
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Book, BookData, ObjectFlags, TES3Object};
+
+pub(crate) struct BookRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub enchanting: &'a str,
+    pub text: &'a str,
+    pub data: BookData,
+}
+
+generic_ref_record_methods!(
+    (BookRef, Book, id),
+    (name, script, mesh, icon, enchanting, text),
+    (flags, data),
+    (),
+    (),
+    ()
+);
+
+generic_make_merge!(
+    book,
+    (BookRef, Book, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        enchanting.&,
+        text.&,
+        data:weight,
+        data:value,
+        data:book_type,
+        data:skill,
+        data:enchantment
+    ),
+    (),
+    (),
+    (),
+    ()
+);
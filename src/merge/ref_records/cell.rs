@@ -1,6 +1,6 @@
 use super::{
-    count_changes, fields_are_equal, generic_make_merge, generic_ref_record_methods, print_as, show_flags, show_object_flags,
-    MergeLog, OptionRecordMergeLog, RawPlugin, SpecificFlags,
+    count_changes, fields_are_equal, generic_make_merge, generic_ref_record_method_references, generic_ref_record_methods, print_as,
+    show_flags, show_object_flags, LowReferences, MergeLog, OptionRecordMergeLog, RawPlugin, ReferencesHelper, SpecificFlags,
 };
 use crate::{Cfg, IntermediateRecords, RecordMap};
 use anyhow::{anyhow, Context, Result};
@@ -9,7 +9,9 @@ use paste::paste;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{convert::identity, fmt};
 use tes3::esp::{AtmosphereData, Cell, CellData, CellFlags, ObjectFlags, Reference, TES3Object};
+mod references;
 mod specific;
+pub(crate) use references::remap_reference_masters;
 use specific::{specific, specific_multipatch, specific_multipatch_check};
 
 #[cfg(test)]
@@ -28,7 +30,9 @@ pub(crate) struct CellRef<'a> {
     pub map_color: &'a Option<[u8; 4]>,
     pub water_height: &'a Option<f32>,
     pub atmosphere_data: &'a Option<AtmosphereData>,
-    pub references: &'a HashMap<(u32, u32), Reference>,
+    pub references: HashMap<(u32, u32), Reference>,
+    pub base: &'a Cell,
+    pub low: LowReferences,
 }
 
 struct FogDensityGrid((i32, i32));
@@ -195,12 +199,13 @@ fn fog_from_float(fog: f32) -> i32 {
 
 show_flags!(CellFlags, IS_INTERIOR, HAS_WATER, RESTING_IS_ILLEGAL, BEHAVES_LIKE_EXTERIOR);
 
+generic_ref_record_method_references!(CellRef);
 generic_ref_record_methods!(
     (CellRef, Cell),
-    (name, region, map_color, water_height, atmosphere_data, references),
+    (name, region, map_color, water_height, atmosphere_data),
     (flags, data),
-    (),
-    (),
+    (LowReferences),
+    (references),
     ()
 );
 
@@ -215,10 +220,9 @@ generic_make_merge!(
         region.&::print_as::NonOptRegion::revert_to_none::false,
         map_color.&::print_as::NonOptMapColor::revert_to_none::false,
         water_height.&::print_as::NonOptWaterHeight::revert_to_none::false,
-        atmosphere_data.&::print_as::NonOptAtmosphereData::print_compact::verbose_atmosphere_data::revert_to_none::false,
-        references.&
+        atmosphere_data.&::print_as::NonOptAtmosphereData::print_compact::verbose_atmosphere_data::revert_to_none::false
     ),
-    (),
+    (references),
     (),
     (),
     (specific)
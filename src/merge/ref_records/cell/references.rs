@@ -0,0 +1,56 @@
+use super::RawPlugin;
+use crate::{append_masters, Cfg, Log, PluginInfo};
+use anyhow::{Context, Result};
+use hashbrown::HashMap;
+use std::mem::take;
+use tes3::esp::{Reference, TES3Object};
+
+// COMMENT: references were rebased on the load order while reading, so they are rebased on the output masters here
+pub(crate) fn remap_reference_masters<'a>(
+    raw_plugin: &mut RawPlugin<'a>,
+    plugins: &'a [PluginInfo],
+    master_count: &mut usize,
+    cfg: &Cfg,
+    log: &mut Log,
+) -> Result<()> {
+    let mut plugin_indices = raw_plugin
+        .plugin
+        .objects
+        .iter()
+        .filter_map(|object| match object {
+            TES3Object::Cell(cell) => Some(cell.references.keys().map(|key| key.0)),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<u32>>();
+    if plugin_indices.is_empty() {
+        return Ok(());
+    }
+    plugin_indices.sort();
+    plugin_indices.dedup();
+    let masters = plugin_indices
+        .iter()
+        .map(|index| {
+            plugins
+                .get(*index as usize)
+                .with_context(|| format!("Bug: failed to get plugin with index {index} for reference master"))
+        })
+        .collect::<Result<Vec<&PluginInfo>>>()?;
+    append_masters(masters, &mut raw_plugin.masters, master_count, cfg, log)?;
+    let master_indices = raw_plugin.master_indices();
+    for object in raw_plugin.plugin.objects.iter_mut() {
+        if let TES3Object::Cell(cell) = object {
+            cell.references = take(&mut cell.references)
+                .into_values()
+                .map(|mut reference| {
+                    let name = &plugins[reference.mast_index as usize].name;
+                    reference.mast_index = *master_indices
+                        .get(name)
+                        .with_context(|| format!("Bug: failed to get master index for \"{name}\""))?;
+                    Ok(((reference.mast_index, reference.refr_index), reference))
+                })
+                .collect::<Result<HashMap<(u32, u32), Reference>>>()?;
+        }
+    }
+    Ok(())
+}
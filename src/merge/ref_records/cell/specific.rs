@@ -118,7 +118,9 @@ pub(crate) fn specific_multipatch(
 ) -> Result<()> {
     let (ambi_fog_density, multipatched, prev_ambi) = multipatch_fogbug_and_get_ambi_fog_density(merged, map, option_log, cfg)
         .with_context(|| "Bug: failed to multipatch_fogbug_and_get_ambi_fog_density()")?;
-    merged.references.clear();
+    if !cfg.merge.references {
+        merged.references.clear();
+    }
     if !cfg.meta.silent {
         specific_flags.multipatched = Some(MULTIPATCH_KIND);
         if multipatched {
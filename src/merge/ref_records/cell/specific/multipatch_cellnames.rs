@@ -13,7 +13,9 @@ pub(super) fn multipatch_cellnames(
     specific_flags: &mut SpecificFlags,
     cfg: &Cfg,
 ) -> Result<()> {
-    merged.references.clear();
+    if !cfg.merge.references {
+        merged.references.clear();
+    }
     if !cfg.meta.silent {
         specific_flags.multipatched = Some(MULTIPATCH_KIND);
         option_log
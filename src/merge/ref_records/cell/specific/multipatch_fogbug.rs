@@ -62,7 +62,9 @@ pub(super) fn multipatch_fogbug_log(
             )?;
         }
     } else {
-        merged.references.clear();
+        if !cfg.merge.references {
+            merged.references.clear();
+        }
         if !cfg.meta.silent {
             specific_flags.multipatched = Some(MULTIPATCH_KIND);
             option_log
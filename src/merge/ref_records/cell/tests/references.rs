@@ -0,0 +1,55 @@
+use super::{assert_eq, *};
+
+fn reference(mast_index: u32, refr_index: u32, translation: [f32; 3]) -> ((u32, u32), Reference) {
+    (
+        (mast_index, refr_index),
+        Reference {
+            mast_index,
+            refr_index,
+            id: String::from("reference"),
+            translation,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn no_merge_without_conflict() {
+    test_init!(src, plugins, cfg, Cell, 3, values_string; cfg=merge:references = true);
+    src[0].references = [reference(0, 1, [0.0; 3])].into_iter().collect();
+    src[1].references = [reference(0, 1, [1.0; 3])].into_iter().collect();
+    src[2].references = [reference(0, 1, [1.0; 3]), reference(2, 1, [2.0; 3])].into_iter().collect();
+    test_merge!(cell, src, plugins, cfg, log, im, res, dst:0);
+}
+
+#[test]
+fn merge_moved_reference() {
+    test_init!(src, plugins, cfg, Cell, 3, values_string; cfg=merge:references = true);
+    src[0].references = [reference(0, 1, [0.0; 3]), reference(0, 2, [0.0; 3])].into_iter().collect();
+    src[1].references = [reference(0, 1, [1.0; 3]), reference(1, 1, [1.0; 3])].into_iter().collect();
+    src[2].references = [reference(0, 1, [0.0; 3]), reference(0, 2, [2.0; 3])].into_iter().collect();
+    let expected = Cell {
+        references: [reference(0, 1, [1.0; 3]), reference(0, 2, [2.0; 3]), reference(1, 1, [1.0; 3])].into_iter().collect(),
+        ..Default::default()
+    };
+    test_merge!(cell, src, plugins, cfg, log, im, res, dst:1);
+    assert_eq_inner!(Cell, expected, dst[0]);
+    assert!(log
+        .test_file()
+        .contains("\"reference\"(0:1).translation: [0.0, 0.0, 0.0] -> [1.0, 1.0, 1.0] [\"Plugin1.esp\"]"));
+    assert!(log.test_file().contains("\"reference\"(1:1) [\"Plugin1.esp\"]"));
+}
+
+#[test]
+fn merge_keeps_added_reference() {
+    test_init!(src, plugins, cfg, Cell, 3, values_string; cfg=merge:references = true);
+    src[0].references = [reference(0, 1, [0.0; 3])].into_iter().collect();
+    src[1].references = [reference(1, 1, [1.0; 3])].into_iter().collect();
+    src[2].references = [reference(0, 1, [2.0; 3])].into_iter().collect();
+    let expected = Cell {
+        references: [reference(0, 1, [2.0; 3]), reference(1, 1, [1.0; 3])].into_iter().collect(),
+        ..Default::default()
+    };
+    test_merge!(cell, src, plugins, cfg, log, im, res, dst:1);
+    assert_eq_inner!(Cell, expected, dst[0]);
+}
@@ -0,0 +1,51 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_flags, show_object_flags, show_service_flags, MergeLog,
+    OptionRecordMergeLog, RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Class, ClassData, ClassFlags, ObjectFlags, ServiceFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct ClasRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub data: ClassData,
+    pub description: &'a str,
+}
+
+show_flags!(ClassFlags, PLAYABLE);
+
+generic_ref_record_methods!((ClasRef, Class, id), (name, description), (flags, data), (), (), ());
+
+generic_make_merge!(
+    clas,
+    (ClasRef, Class, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        data:primary_attributes;0,
+        data:primary_attributes;1,
+        data:specialization,
+        data:minor_and_major_skills;0,
+        data:minor_and_major_skills;1,
+        data:minor_and_major_skills;2,
+        data:minor_and_major_skills;3,
+        data:minor_and_major_skills;4,
+        data:flags=ClassFlags,
+        data:services=ServiceFlags,
+        description.&
+    ),
+    (),
+    (),
+    (),
+    ()
+);
@@ -0,0 +1,36 @@
+use super::{assert_eq_inner, merge_clas, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Class, ObjectFlags, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(clas, Class, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(clas, Class, values_string:name);
+    }
+
+    mod description {
+        use super::{assert_eq, *};
+        test_basic!(clas, Class, values_string:description);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            clas,
+            Class,
+            "Merged CLAS record: \"\"\n",
+            "Merging CLAS record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"description\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged CLAS record: \"\"\n",
+            values_string:description
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{BipedObject, Clothing, ClothingData, ObjectFlags, TES3Object};
+
+pub(crate) struct ClotRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub enchanting: &'a str,
+    pub data: ClothingData,
+    pub biped_objects: &'a Vec<BipedObject>,
+}
+
+generic_ref_record_methods!(
+    (ClotRef, Clothing, id),
+    (name, script, mesh, icon, enchanting, biped_objects),
+    (flags, data),
+    (),
+    (),
+    ()
+);
+
+generic_make_merge!(
+    clot,
+    (ClotRef, Clothing, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        enchanting.&,
+        data:clothing_type,
+        data:weight,
+        data:value,
+        data:enchantment,
+        biped_objects.&
+    ),
+    (),
+    (),
+    (),
+    ()
+);
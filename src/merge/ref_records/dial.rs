@@ -0,0 +1,283 @@
+use super::{MergeLog, OptionRecordMergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo, Topic, TopicRecordMap};
+use anyhow::Result;
+use hashbrown::{hash_map::Entry, HashMap, HashSet};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::{
+    collections::hash_map::DefaultHasher,
+    convert::identity,
+    hash::{Hash, Hasher},
+};
+use tes3::esp::{DialogueInfo, ObjectFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{test_init, test_merge};
+
+pub(crate) fn merge_dial(
+    intermediate_records: &IntermediateRecords,
+    raw_plugin: &mut RawPlugin,
+    cfg: &Cfg,
+    merge_log: &mut MergeLog,
+) -> Result<()> {
+    let (indexed_records, logs): (Vec<Option<(usize, Topic)>>, Vec<OptionRecordMergeLog>) = intermediate_records
+        .dial
+        .par_iter()
+        .map(|(_id_low, map)| -> Result<Option<(Option<(usize, Topic)>, OptionRecordMergeLog)>> {
+            if map.records.len() > 1 {
+                let mut option_log = OptionRecordMergeLog::default();
+                let merged = merge_map(map, &mut option_log, cfg)?.map(|merged| (map.init_id, merged));
+                if merged.is_some() || option_log.is_some() {
+                    return Ok(Some((merged, option_log)));
+                }
+            } else if cfg.meta.debug_single {
+                return Ok(Some((None, OptionRecordMergeLog::single_instance(&map, cfg)?)));
+            }
+            Ok(None)
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<(Vec<Option<(usize, Topic)>>, Vec<OptionRecordMergeLog>)>>()?;
+
+    let mut sorted_records: Vec<(usize, Topic)> = indexed_records.into_iter().filter_map(identity).collect();
+    merge_log.push(logs, sorted_records.len(), cfg);
+    if !sorted_records.is_empty() {
+        sorted_records.sort_by_key(|element| element.0);
+        for (_, topic) in sorted_records {
+            raw_plugin.plugin.objects.push(TES3Object::Dialogue(topic.dialogue));
+            raw_plugin.plugin.objects.extend(topic.infos.into_iter().map(TES3Object::DialogueInfo));
+        }
+    };
+    Ok(())
+}
+
+// COMMENT: INFO records are placed into the merged chain by their links, so responses inserted by different plugins all survive
+fn merge_map(map: &TopicRecordMap, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<Option<Topic>> {
+    let last = &map.last_record()?.dial;
+    if last.dialogue.flags.contains(ObjectFlags::DELETED) {
+        return Ok(None);
+    }
+    let first = &map.record(0)?.dial;
+    let mut order = first.infos.iter().map(|info| info.id.as_str()).collect::<Vec<&str>>();
+    let mut merged = first.infos.iter().map(|info| (info.id.as_str(), info)).collect::<HashMap<&str, &DialogueInfo>>();
+    // COMMENT: links are ignored when looking for changes, because they depend on the load order
+    let mut seen = first
+        .infos
+        .iter()
+        .map(|info| (info.id.as_str(), vec![unlinked_hash(info)]))
+        .collect::<HashMap<&str, Vec<u64>>>();
+    // COMMENT: INFO is moved when its previous INFO differs from the one of the plugin it was introduced by
+    let mut introduced = first
+        .infos
+        .iter()
+        .map(|info| (info.id.as_str(), (0, info.prev_id.as_str())))
+        .collect::<HashMap<&str, (usize, &str)>>();
+    for (record, plugin_index) in map.records.iter().zip(0usize..).skip(1) {
+        let cyclic = check_cycles(&record.dial.infos, record.plugin_info, map, option_log, cfg)?;
+        for info in record.dial.infos.iter() {
+            introduced.entry(info.id.as_str()).or_insert((plugin_index, info.prev_id.as_str()));
+        }
+        let links = record
+            .dial
+            .infos
+            .iter()
+            .map(|info| (info.id.as_str(), info.prev_id.as_str()))
+            .collect::<HashMap<&str, &str>>();
+        let mut pending = Vec::new();
+        for info in record.dial.infos.iter() {
+            let new = unlinked_hash(info);
+            let versions = seen.entry(info.id.as_str()).or_default();
+            match merged.entry(info.id.as_str()) {
+                Entry::Occupied(mut o) => {
+                    if !versions.contains(&new) {
+                        if !cfg.meta.silent {
+                            option_log.field_changed_custom(
+                                "infos",
+                                format_args!("{:?}: changed", info.id),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?;
+                        }
+                        o.insert(info);
+                    }
+                    if !cyclic && is_moved(info, &links, &introduced) {
+                        if !cfg.meta.silent {
+                            option_log.field_changed_custom(
+                                "infos",
+                                format_args!("{:?}: moved", info.id),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?;
+                        }
+                        order.retain(|id| *id != info.id);
+                        pending.push(info);
+                    }
+                }
+                Entry::Vacant(v) => {
+                    if !cfg.meta.silent {
+                        option_log.field_extend(
+                            "infos",
+                            false,
+                            format_args!("{:?}", info.id),
+                            &record.plugin_info.name,
+                            &map,
+                            cfg,
+                        )?;
+                    }
+                    v.insert(info);
+                    pending.push(info);
+                }
+            }
+            if !versions.contains(&new) {
+                versions.push(new);
+            }
+        }
+        // COMMENT: new INFO may be linked to another new INFO that comes later in the plugin
+        loop {
+            let pending_len = pending.len();
+            pending.retain(|&info| !insert_linked(&mut order, info));
+            if pending.is_empty() || pending.len() == pending_len {
+                break;
+            }
+        }
+        for info in pending {
+            if !cfg.meta.silent {
+                option_log.warn(
+                    format_args!(
+                        "Invalid INFO link: {id:?}: {info:?}: \"prev_id\": {prev_id:?}, \"next_id\": {next_id:?} [\"{plugin}\"] {{ orphaned, appended to the end of the topic }}",
+                        id = map.record_id_debug()?,
+                        info = info.id,
+                        prev_id = info.prev_id,
+                        next_id = info.next_id,
+                        plugin = record.plugin_info.name,
+                    ),
+                    &map,
+                    cfg,
+                )?;
+            }
+            order.push(&info.id);
+        }
+    }
+    // COMMENT: only INFO records that differ from their last instance are placed, including the ones with changed links
+    let last_versions = map
+        .records
+        .iter()
+        .flat_map(|record| record.dial.infos.iter())
+        .map(|info| (info.id.as_str(), info))
+        .collect::<HashMap<&str, &DialogueInfo>>();
+    let infos = relink(&order, &merged)
+        .into_iter()
+        .filter(|info| last_versions.get(info.id.as_str()).map_or(true, |last_version| *last_version != info))
+        .collect::<Vec<DialogueInfo>>();
+    if infos.is_empty() {
+        if !cfg.meta.silent {
+            option_log.equal_to_the_last_or_clear(&map, cfg)?;
+        }
+        return Ok(None);
+    }
+    if !cfg.meta.silent {
+        option_log.record_merged(&map, cfg)?;
+    }
+    Ok(Some(Topic {
+        dialogue: last.dialogue.clone(),
+        infos,
+    }))
+}
+
+fn insert_linked<'a>(order: &mut Vec<&'a str>, info: &'a DialogueInfo) -> bool {
+    let index = if info.prev_id.is_empty() {
+        Some(0)
+    } else if let Some(index) = order.iter().position(|id| *id == info.prev_id) {
+        Some(index + 1)
+    } else if info.next_id.is_empty() {
+        None
+    } else {
+        order.iter().position(|id| *id == info.next_id)
+    };
+    match index {
+        Some(index) => {
+            order.insert(index, &info.id);
+            true
+        }
+        None => false,
+    }
+}
+
+// COMMENT: INFO records inserted by later plugins are skipped, so insertions next to an INFO don't count as moving it
+fn is_moved(info: &DialogueInfo, links: &HashMap<&str, &str>, introduced: &HashMap<&str, (usize, &str)>) -> bool {
+    let Some(&(since, first_prev_id)) = introduced.get(info.id.as_str()) else {
+        return false;
+    };
+    let mut prev_id = info.prev_id.as_str();
+    let mut visited = HashSet::new();
+    while introduced.get(prev_id).map_or(false, |&(at, _)| at > since) && visited.insert(prev_id) {
+        prev_id = links.get(prev_id).copied().unwrap_or_default();
+    }
+    prev_id != first_prev_id
+}
+
+fn unlinked(info: &DialogueInfo) -> DialogueInfo {
+    DialogueInfo {
+        prev_id: String::new(),
+        next_id: String::new(),
+        ..info.clone()
+    }
+}
+
+// COMMENT: only hashes of previous versions are kept, records don't implement Hash, so their debug representation is hashed
+fn unlinked_hash(info: &DialogueInfo) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", unlinked(info)).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn relink(order: &[&str], merged: &HashMap<&str, &DialogueInfo>) -> Vec<DialogueInfo> {
+    order
+        .iter()
+        .enumerate()
+        .filter_map(|(index, id)| {
+            merged.get(id).map(|info| DialogueInfo {
+                prev_id: if index == 0 { String::new() } else { order[index - 1].to_owned() },
+                next_id: order.get(index + 1).map(|id| id.to_string()).unwrap_or_default(),
+                ..(*info).clone()
+            })
+        })
+        .collect()
+}
+
+fn check_cycles(
+    infos: &[DialogueInfo],
+    plugin_info: &PluginInfo,
+    map: &TopicRecordMap,
+    option_log: &mut OptionRecordMergeLog,
+    cfg: &Cfg,
+) -> Result<bool> {
+    let links = infos.iter().map(|info| (info.id.as_str(), info.prev_id.as_str())).collect::<HashMap<&str, &str>>();
+    for info in infos {
+        let mut visited = HashSet::new();
+        let mut current = info.id.as_str();
+        while let Some(prev_id) = links.get(current) {
+            if !visited.insert(current) {
+                if cfg.meta.silent {
+                    return Ok(true);
+                }
+                option_log.warn(
+                    format_args!(
+                        "Invalid INFO link: {id:?}: {info:?}: \"prev_id\": {prev_id:?} [\"{plugin}\"] {{ cyclic, the chain is relinked }}",
+                        id = map.record_id_debug()?,
+                        info = info.id,
+                        prev_id = info.prev_id,
+                        plugin = plugin_info.name,
+                    ),
+                    &map,
+                    cfg,
+                )?;
+                return Ok(true);
+            }
+            current = *prev_id;
+        }
+    }
+    Ok(false)
+}
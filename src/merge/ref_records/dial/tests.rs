@@ -0,0 +1,86 @@
+use super::{merge_dial, test_init, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo, Topic};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{DialogueInfo, TES3Object};
+
+fn infos(objects: &[TES3Object]) -> Vec<DialogueInfo> {
+    objects
+        .iter()
+        .filter_map(|object| match object {
+            TES3Object::DialogueInfo(info) => Some(info.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn no_merge_last_has_all_infos() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[0].clone();
+    src[1].infos = values_infos[1].clone();
+    src[2].infos = values_infos[1].clone();
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:0);
+    assert_eq!(log.test_warn(), "");
+}
+
+#[test]
+fn merge_inserted_infos() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[0].clone();
+    src[1].infos = values_infos[1].clone();
+    src[2].infos = values_infos[2].clone();
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:4);
+    assert!(matches!(dst.objects[0], TES3Object::Dialogue(_)));
+    assert_eq!(infos(&dst.objects), values_infos[3][1..].to_vec());
+    assert_eq!(log.test_warn(), "");
+}
+
+#[test]
+fn merge_changed_info_only() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[1].clone();
+    src[1].infos = values_infos[1].clone();
+    src[1].infos[1].text = String::from("changed");
+    src[2].infos = values_infos[1].clone();
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:2);
+    assert_eq!(infos(&dst.objects), vec![src[1].infos[1].clone()]);
+}
+
+#[test]
+fn merge_moved_info() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[3].clone();
+    src[1].infos = values_infos[5].clone();
+    src[2].infos = values_infos[3].clone();
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:5);
+    assert_eq!(infos(&dst.objects), values_infos[5]);
+    assert!(log.test_file().contains("\"infos\": \"3\": moved [\"Plugin1.esp\"]"));
+    assert_eq!(log.test_warn(), "");
+}
+
+#[test]
+fn merge_orphaned_info() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[0].clone();
+    src[1].infos = values_infos[1].clone();
+    src[2].infos = values_infos[4][3..].to_vec();
+    src[2].infos[0].prev_id = String::from("missing");
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:3);
+    assert_eq!(infos(&dst.objects), values_infos[4][2..].to_vec());
+    assert!(log
+        .test_warn()
+        .contains("\"5\": \"prev_id\": \"missing\", \"next_id\": \"\" [\"Plugin2.esp\"] { orphaned"));
+}
+
+#[test]
+fn warn_cyclic_infos() {
+    test_init!(src, plugins, cfg, Topic, 3, values_infos);
+    src[0].infos = values_infos[0].clone();
+    src[1].infos = values_infos[1].clone();
+    src[1].infos[0].prev_id = String::from("2");
+    src[2].infos = values_infos[2].clone();
+    test_merge!(dial, src, plugins, cfg, log, im, res, dst:4);
+    assert!(log.test_warn().contains("[\"Plugin1.esp\"] { cyclic"));
+}
@@ -0,0 +1,32 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Door, ObjectFlags, TES3Object};
+
+pub(crate) struct DoorRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub mesh: &'a str,
+    pub script: &'a str,
+    pub open_sound: &'a str,
+    pub close_sound: &'a str,
+}
+
+generic_ref_record_methods!((DoorRef, Door, id), (name, mesh, script, open_sound, close_sound), (flags), (), (), ());
+
+generic_make_merge!(
+    door,
+    (DoorRef, Door, id),
+    (flags=ObjectFlags, name.&, mesh.&, script.&, open_sound.&, close_sound.&),
+    (),
+    (),
+    (),
+    ()
+);
@@ -0,0 +1,39 @@
+use super::{
+    generic_make_merge, generic_ref_record_method_effects, generic_ref_record_methods, print_as, show_flags, show_object_flags,
+    EffectsHelper, LowEffects, MergeLog, OptionRecordMergeLog, RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Effect, Enchanting, EnchantingData, EnchantingFlags, ObjectFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct EnchRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub data: EnchantingData,
+    pub effects: Vec<Effect>,
+    pub base: &'a Enchanting,
+    pub low: LowEffects,
+}
+
+show_flags!(EnchantingFlags, AUTO_CALCULATE);
+
+generic_ref_record_method_effects!(EnchRef);
+generic_ref_record_methods!((EnchRef, Enchanting, id), (), (flags, data), (LowEffects), (effects), ());
+
+generic_make_merge!(
+    ench,
+    (EnchRef, Enchanting, id),
+    (flags=ObjectFlags, data:enchant_type, data:cost, data:max_charge, data:flags=EnchantingFlags),
+    (effects),
+    (),
+    (),
+    ()
+);
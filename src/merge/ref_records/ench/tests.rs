@@ -0,0 +1,60 @@
+use super::{assert_eq_inner, merge_ench, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Enchanting, ObjectFlags, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(ench, Enchanting, values_object_flags:flags);
+    }
+
+    mod data_cost {
+        use super::{assert_eq, *};
+        test_basic!(ench, Enchanting, values_u32:data:cost);
+    }
+
+    mod data_max_charge {
+        use super::{assert_eq, *};
+        test_basic!(ench, Enchanting, values_u32:data:max_charge);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            ench,
+            Enchanting,
+            "Merged ENCH record: \"\"\n",
+            "Merging ENCH record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"data.cost\": 0 -> 1 [\"Plugin1.esp\"]\nMerged ENCH record: \"\"\n",
+            values_u32:data:cost
+        );
+    }
+}
+
+mod effects {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn no_merge_last_equal_to_merged() {
+        test_init!(src, plugins, cfg, Enchanting, 3, values_effects);
+        src[0].effects = values_effects[0].clone();
+        src[1].effects = values_effects[0].clone();
+        src[2].effects = values_effects[3].clone();
+        test_merge!(ench, src, plugins, cfg, log, im, res, dst:0);
+    }
+
+    #[test]
+    fn merge_edit_and_add() {
+        test_init!(src, plugins, cfg, Enchanting, 3, values_effects);
+        src[0].effects = values_effects[0].clone();
+        src[1].effects = values_effects[1].clone();
+        src[2].effects = values_effects[2].clone();
+        let expected = Enchanting { effects: values_effects[3].clone(), ..Default::default() };
+        test_merge!(ench, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Enchanting, expected, dst[0]);
+    }
+}
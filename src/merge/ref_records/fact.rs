@@ -0,0 +1,73 @@
+use super::{
+    generic_make_merge, generic_ref_record_method_rank_names, generic_ref_record_method_reactions, generic_ref_record_methods, print_as,
+    reaction_to_lowercase, show_flags, show_object_flags, LowRankNamesReactions, MergeLog, OptionRecordMergeLog, RankNamesHelper,
+    RawPlugin, ReactionsHelper, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Faction, FactionData, FactionFlags, FactionReaction, ObjectFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct FactRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub rank_names: Vec<String>,
+    pub reactions: Vec<FactionReaction>,
+    pub data: FactionData,
+    pub base: &'a Faction,
+    pub low: LowRankNamesReactions,
+}
+
+show_flags!(FactionFlags, HIDDEN_FROM_PLAYER);
+
+generic_ref_record_method_rank_names!(FactRef);
+generic_ref_record_method_reactions!(FactRef);
+generic_ref_record_methods!(
+    (FactRef, Faction, id),
+    (name),
+    (flags, data),
+    (LowRankNamesReactions),
+    (rank_names, reactions),
+    ()
+);
+
+generic_make_merge!(
+    fact,
+    (FactRef, Faction, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        data:favored_attributes;0,
+        data:favored_attributes;1,
+        data:requirements;0,
+        data:requirements;1,
+        data:requirements;2,
+        data:requirements;3,
+        data:requirements;4,
+        data:requirements;5,
+        data:requirements;6,
+        data:requirements;7,
+        data:requirements;8,
+        data:requirements;9,
+        data:favored_skills;0,
+        data:favored_skills;1,
+        data:favored_skills;2,
+        data:favored_skills;3,
+        data:favored_skills;4,
+        data:favored_skills;5,
+        data:favored_skills;6,
+        data:flags=FactionFlags
+    ),
+    (rank_names, reactions),
+    (),
+    (),
+    ()
+);
@@ -0,0 +1,138 @@
+use super::{assert_eq_inner, merge_fact, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Faction, FactionReaction, ObjectFlags, TES3Object};
+
+fn reaction(faction: &str, reaction: i32) -> FactionReaction {
+    FactionReaction {
+        faction: faction.to_string(),
+        reaction,
+    }
+}
+
+fn ranks(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(fact, Faction, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(fact, Faction, values_string:name);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            fact,
+            Faction,
+            "Merged FACT record: \"\"\n",
+            "Merging FACT record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"name\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged FACT record: \"\"\n",
+            values_string:name
+        );
+    }
+}
+
+mod reactions {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn no_merge_case_differs() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        src[0].reactions = vec![reaction("Faction_0", 1)];
+        src[1].reactions = vec![reaction("Faction_0", 1), reaction("Faction_1", 2)];
+        src[2].reactions = vec![reaction("FACTION_0", 1), reaction("FACTION_1", 2)];
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:0);
+    }
+
+    #[test]
+    fn merge_union() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        src[0].reactions = vec![reaction("faction_0", 1)];
+        src[1].reactions = vec![reaction("faction_0", 1), reaction("faction_1", 2)];
+        src[2].reactions = vec![reaction("faction_0", 1), reaction("faction_2", 3)];
+        let expected = Faction {
+            reactions: vec![reaction("faction_0", 1), reaction("faction_1", 2), reaction("faction_2", 3)],
+            ..Default::default()
+        };
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Faction, expected, dst[0]);
+        assert!(log.test_file().contains("\"reactions\": + \"faction_1\"(2) [\"Plugin1.esp\"]"));
+    }
+
+    #[test]
+    fn merge_edit_and_delete() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        src[0].reactions = vec![reaction("faction_0", 1), reaction("faction_1", 2)];
+        src[1].reactions = vec![reaction("faction_0", -5), reaction("faction_1", 2)];
+        src[2].reactions = vec![reaction("faction_0", 1)];
+        let expected = Faction {
+            reactions: vec![reaction("faction_0", -5)],
+            ..Default::default()
+        };
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Faction, expected, dst[0]);
+        assert!(log
+            .test_file()
+            .contains("\"reactions\": \"faction_0\": 1 -> -5 [\"Plugin1.esp\"]"));
+        assert!(log.test_file().contains("\"reactions\": - \"faction_1\"(2) [\"Plugin2.esp\"]"));
+    }
+}
+
+mod rank_names {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn merge_per_index() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        src[0].rank_names = ranks(&["rank_0", "rank_1", "rank_2"]);
+        src[1].rank_names = ranks(&["Rank_A", "rank_1", "rank_2"]);
+        src[2].rank_names = ranks(&["rank_0", "rank_1", "rank_2", "rank_3"]);
+        let expected = Faction {
+            rank_names: ranks(&["Rank_A", "rank_1", "rank_2", "rank_3"]),
+            ..Default::default()
+        };
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Faction, expected, dst[0]);
+    }
+
+    #[test]
+    fn merge_edited_and_truncated() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        let base = (0..10).map(|index| format!("rank_{index}")).collect::<Vec<String>>();
+        src[0].rank_names = base.clone();
+        src[1].rank_names = base.clone();
+        src[1].rank_names[9] = "Rank_J".to_string();
+        src[2].rank_names = base[..8].to_vec();
+        let mut expected_rank_names = base.clone();
+        expected_rank_names[9] = "Rank_J".to_string();
+        let expected = Faction {
+            rank_names: expected_rank_names,
+            ..Default::default()
+        };
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Faction, expected, dst[0]);
+    }
+
+    #[test]
+    fn merge_truncated() {
+        test_init!(src, plugins, cfg, Faction, 3, values_string);
+        src[0].rank_names = ranks(&["rank_0", "rank_1", "rank_2", "rank_3"]);
+        src[1].rank_names = ranks(&["rank_0", "rank_1"]);
+        src[2].rank_names = ranks(&["Rank_A", "rank_1", "rank_2", "rank_3"]);
+        let expected = Faction {
+            rank_names: ranks(&["Rank_A", "rank_1"]),
+            ..Default::default()
+        };
+        test_merge!(fact, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Faction, expected, dst[0]);
+    }
+}
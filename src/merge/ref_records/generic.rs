@@ -9,17 +9,37 @@ pub(super) use count_changes_macro::count_changes;
 pub(super) use fields_are_equal_macro::fields_are_equal;
 pub(super) use make_merge_macro::generic_make_merge;
 pub(super) use method_macro::{
-    generic_ref_record_method_inventory, generic_ref_record_method_spells, generic_ref_record_method_travel_destinations,
-    generic_ref_record_methods,
+    generic_ref_record_method_effects, generic_ref_record_method_inventory, generic_ref_record_method_rank_names,
+    generic_ref_record_method_reactions, generic_ref_record_method_references, generic_ref_record_method_sounds,
+    generic_ref_record_method_spells, generic_ref_record_method_travel_destinations, generic_ref_record_methods,
 };
 pub(super) use print_as_macro::print_as;
 pub(super) use show_flags_macro::show_flags;
 
+// COMMENT: effects have nothing to lowercase, but generic methods expect the low field
+#[derive(Default)]
+pub(super) struct LowEffects;
+
+// COMMENT: same as LowEffects, references are compared as they are
+#[derive(Default)]
+pub(super) struct LowReferences;
+
 #[derive(Default)]
 pub(super) struct LowInventory {
     pub(super) inventory: Vec<(i32, String)>,
 }
 
+#[derive(Default)]
+pub(super) struct LowRankNamesReactions {
+    pub(super) rank_names: Vec<String>,
+    pub(super) reactions: Vec<(String, i32)>,
+}
+
+#[derive(Default)]
+pub(super) struct LowSounds {
+    pub(super) sounds: Vec<(String, u8)>,
+}
+
 #[derive(Default)]
 pub(super) struct LowSpells {
     pub(super) spells: Vec<String>,
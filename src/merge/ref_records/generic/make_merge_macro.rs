@@ -224,23 +224,24 @@ pub(crate) fn [<merge_ $short>](intermediate_records: &IntermediateRecords, raw_
                         }
                     };
                 }
+                // COMMENT: not every record has an id, e.g. CELL
                 #[allow(unused_macros)]
                 macro_rules! merge_context {
-                    ($err_field:ident, $record:ident) => {
+                    ($err_field:ident) => {
                         format!(
-                            "Failed to merge \"{field}\" field for {kind} record: {id:?}",
+                            "Failed to merge \"{field}\" field for {kind} record: {id}",
                             field = stringify!($err_field),
                             kind = stringify!([<$short:upper>]),
-                            id = $record.$short.id,
+                            id = map.record_id_debug().map(|id| format!("{id:?}")).unwrap_or_default(),
                         )
                     };
                 }
-                $( // COMMENT: process vector fields: invetory, spells, travel_destinations
+                $( // COMMENT: process vector fields: invetory, spells, travel_destinations, references
                     let mut $vec_field = [<$vec_field:camel Helper>]::default();
                     for (record, next_index) in map.records.iter().skip(1).zip(1usize..) {
                         process_helper_common!(record, next_index, $vec_field);
                         $vec_field.[<make_ $short>](&map, next_index, &mut option_log, cfg)
-                            .with_context(|| merge_context!($vec_field, record))?;
+                            .with_context(|| merge_context!($vec_field))?;
                     }
                 )*
                 $( // COMMENT: process ai_packages
@@ -254,7 +255,7 @@ pub(crate) fn [<merge_ $short>](intermediate_records: &IntermediateRecords, raw_
                             &mut option_log,
                             &map,
                             cfg
-                        ).with_context(|| merge_context!($ai_packages, record))?;
+                        ).with_context(|| merge_context!($ai_packages))?;
                     }
                 )?
                 // COMMENT: [MERGE FINISH]
@@ -272,11 +273,21 @@ pub(crate) fn [<merge_ $short>](intermediate_records: &IntermediateRecords, raw_
                     let last = &map.last_record()
                         .with_context(|| err_context!("succeed map.last_record() while merging"))?
                         .$short;
+                    // COMMENT: effects are committed infallibly, other vector fields may fail on added_to_owned
+                    #[allow(unused_macros)]
+                    macro_rules! commit_vec_field {
+                        (effects, $commit_field:ident) => {
+                            $commit_field.commit(&merged.base.$commit_field)
+                        };
+                        ($_vec_field:ident, $commit_field:ident) => {
+                            $commit_field.commit(&merged.base.$commit_field)
+                                .with_context(|| err_context!(
+                                        format!("succeed {}.commit while merging", stringify!($commit_field))
+                                ))?
+                        };
+                    }
                     $( // COMMENT: cook vector and ai_packages fields from half-baked state
-                        merged.$vec_field = $vec_field.commit(&merged.base.$vec_field)
-                            .with_context(|| err_context!(
-                                    format!("succeed {}.commit while merging", stringify!($vec_field))
-                            ))?;
+                        merged.$vec_field = commit_vec_field!($vec_field, $vec_field);
                     )*
                     $(merged.$ai_packages = $ai_packages.commit(&merged.base.$ai_packages);)?
                     if merged.equal(last$(, &$vec_field)*) // COMMENT: ai_packages are implicitly included
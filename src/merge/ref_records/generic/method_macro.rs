@@ -15,6 +15,68 @@ macro_rules! generic_ref_record_method_inventory {
     };
 }
 
+macro_rules! generic_ref_record_method_effects {
+    ($ref_record:ident) => {
+        impl<'a> $ref_record<'a> {
+            // COMMENT: order of effects is meaningful, so they are neither lowercased nor sorted
+            fn get_low_sorted_effects(&mut self) -> Vec<&Effect> {
+                self.effects.iter().collect::<Vec<_>>()
+            }
+        }
+    };
+}
+
+macro_rules! generic_ref_record_method_rank_names {
+    ($ref_record:ident) => {
+        impl<'a> $ref_record<'a> {
+            // COMMENT: order of ranks is meaningful, so they are lowercased but not sorted
+            fn get_low_sorted_rank_names(&mut self) -> Vec<&String> {
+                self.low.rank_names = self.rank_names.iter().map(|rank_name| rank_name.to_lowercase()).collect::<Vec<_>>();
+                self.low.rank_names.iter().collect::<Vec<_>>()
+            }
+        }
+    };
+}
+
+macro_rules! generic_ref_record_method_reactions {
+    ($ref_record:ident) => {
+        impl<'a> $ref_record<'a> {
+            fn get_low_sorted_reactions(&mut self) -> Vec<&(String, i32)> {
+                self.low.reactions = self.reactions.iter().map(|reaction| reaction_to_lowercase(reaction)).collect::<Vec<_>>();
+                let mut res = self.low.reactions.iter().collect::<Vec<_>>();
+                res.sort();
+                res
+            }
+        }
+    };
+}
+
+macro_rules! generic_ref_record_method_references {
+    ($ref_record:ident) => {
+        impl<'a> $ref_record<'a> {
+            // COMMENT: references have nothing to lowercase, they are only sorted by key
+            fn get_low_sorted_references(&mut self) -> Vec<(&(u32, u32), &Reference)> {
+                let mut res = self.references.iter().collect::<Vec<_>>();
+                res.sort_by_key(|(key, _)| **key);
+                res
+            }
+        }
+    };
+}
+
+macro_rules! generic_ref_record_method_sounds {
+    ($ref_record:ident) => {
+        impl<'a> $ref_record<'a> {
+            fn get_low_sorted_sounds(&mut self) -> Vec<&(String, u8)> {
+                self.low.sounds = self.sounds.iter().map(|sound| sound_to_lowercase(sound)).collect::<Vec<_>>();
+                let mut res = self.low.sounds.iter().collect::<Vec<_>>();
+                res.sort();
+                res
+            }
+        }
+    };
+}
+
 macro_rules! generic_ref_record_method_spells {
     ($ref_record:ident) => {
         impl<'a> $ref_record<'a> {
@@ -48,7 +110,7 @@ macro_rules! generic_ref_record_method_travel_destinations {
 macro_rules! generic_ref_record_methods {
     (
         ($ref_record:ident, $tes3_record:ident$(, $id:ident)?),
-        ($($field:ident),+),
+        ($($field:ident),*),
         ($($clone_field:ident),*),
         ($($low:ident)?),
         ($($vec_field:ident),*),
@@ -59,13 +121,13 @@ macro_rules! generic_ref_record_methods {
                 $ref_record {
                     $($id: &source.$id,)?
                     $($clone_field: source.$clone_field.clone(),)*
-                    $($vec_field: Vec::new(),)*
+                    $($vec_field: Default::default(),)*
                     $(
                         base: source,
                         low: $low::default(),
                     )?
                     $($ai_packages: Vec::new(),)?
-                    $($field: &source.$field,)+
+                    $($field: &source.$field,)*
                 }
             }
 
@@ -75,7 +137,7 @@ macro_rules! generic_ref_record_methods {
                     $($clone_field: self.$clone_field,)*
                     $($vec_field: self.$vec_field,)*
                     $($ai_packages: self.$ai_packages,)?
-                    $($field: self.$field.to_owned(),)+
+                    $($field: self.$field.to_owned(),)*
                 }
             }
 
@@ -84,7 +146,7 @@ macro_rules! generic_ref_record_methods {
                     #[allow(unused_mut)]
                     let mut res = true
                             $(&& self.$clone_field == target.$clone_field)*
-                            $(&& *self.$field == target.$field)+;
+                            $(&& *self.$field == target.$field)*;
                     $(
                         if res {
                             res = if self.$vec_field.len() == target.$vec_field.len() {
@@ -113,6 +175,7 @@ macro_rules! generic_ref_record_methods {
 }
 
 pub(crate) use {
-    generic_ref_record_method_inventory, generic_ref_record_method_spells, generic_ref_record_method_travel_destinations,
-    generic_ref_record_methods,
+    generic_ref_record_method_effects, generic_ref_record_method_inventory, generic_ref_record_method_rank_names,
+    generic_ref_record_method_reactions, generic_ref_record_method_references, generic_ref_record_method_sounds,
+    generic_ref_record_method_spells, generic_ref_record_method_travel_destinations, generic_ref_record_methods,
 };
@@ -0,0 +1,249 @@
+use super::{MergeLog, OptionRecordMergeLog, RawPlugin};
+use crate::{Cfg, GameSettingRecordMap, IntermediateRecords, PluginInfo, RecordMap};
+use anyhow::Result;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::{convert::identity, fmt};
+use tes3::esp::{GameSetting, GameSettingValue, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_init, test_merge};
+
+// COMMENT: according to tes3cmd these are injected by the original CS at default values when saving a dirty plugin
+// COMMENT: kept sorted for binary search
+const EVIL_GMSTS: [&str; 78] = [
+    "fcombatdistancewerewolfmod",
+    "ffleedistance",
+    "fwerewolfacrobatics",
+    "fwerewolfagility",
+    "fwerewolfalchemy",
+    "fwerewolfalteration",
+    "fwerewolfarmorer",
+    "fwerewolfathletics",
+    "fwerewolfaxe",
+    "fwerewolfblock",
+    "fwerewolfbluntweapon",
+    "fwerewolfconjuration",
+    "fwerewolfdestruction",
+    "fwerewolfenchant",
+    "fwerewolfendurance",
+    "fwerewolffatigue",
+    "fwerewolfhandtohand",
+    "fwerewolfhealth",
+    "fwerewolfheavyarmor",
+    "fwerewolfillusion",
+    "fwerewolfintellegence",
+    "fwerewolflightarmor",
+    "fwerewolflongblade",
+    "fwerewolfluck",
+    "fwerewolfmagicka",
+    "fwerewolfmarksman",
+    "fwerewolfmediumarmor",
+    "fwerewolfmerchantile",
+    "fwerewolfmysticism",
+    "fwerewolfpersonality",
+    "fwerewolfrestoration",
+    "fwerewolfrunmult",
+    "fwerewolfsecurity",
+    "fwerewolfshortblade",
+    "fwerewolfsilverweapondamagemult",
+    "fwerewolfsneak",
+    "fwerewolfspear",
+    "fwerewolfspeechcraft",
+    "fwerewolfspeed",
+    "fwerewolfstrength",
+    "fwerewolfunarmored",
+    "fwerewolfwillpower",
+    "iwerewolfbounty",
+    "iwerewolffightmod",
+    "iwerewolffleemod",
+    "iwerewolfleveltoattack",
+    "sbookskillmessage",
+    "scompanionshare",
+    "scompanionwarningbuttonone",
+    "scompanionwarningbuttontwo",
+    "scompanionwarningmessage",
+    "scondition",
+    "seditnote",
+    "seffectsummonboneswalker",
+    "seffectsummoncenturionsphere",
+    "seffectsummoncreature01",
+    "seffectsummoncreature02",
+    "seffectsummoncreature03",
+    "seffectsummoncreature04",
+    "seffectsummoncreature05",
+    "seffectsummonfabricant",
+    "seffectsummonfrostatronach",
+    "seffectsummonstormatronach",
+    "seffectsummonwingedtwilight",
+    "slevitatedisabled",
+    "smagiccreature01id",
+    "smagiccreature02id",
+    "smagiccreature03id",
+    "smagiccreature04id",
+    "smagiccreature05id",
+    "smagicfabricantid",
+    "smaxsale",
+    "sprofitvalue",
+    "steleportdisabled",
+    "swerewolfalarmmessage",
+    "swerewolfpopup",
+    "swerewolfrefusal",
+    "swerewolfrestmessage",
+];
+
+// COMMENT: only these are trusted to define vanilla values, other masters may be just as dirty as plugins
+const VANILLA_MASTERS: [&str; 3] = ["morrowind.esm", "tribunal.esm", "bloodmoon.esm"];
+
+struct ShowValue<'a>(&'a GameSettingValue);
+
+impl fmt::Debug for ShowValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            GameSettingValue::Integer(value) => write!(f, "{value}"),
+            GameSettingValue::Float(value) => write!(f, "{value:?}"),
+            GameSettingValue::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+pub(crate) fn merge_gmst(
+    intermediate_records: &IntermediateRecords,
+    raw_plugin: &mut RawPlugin,
+    cfg: &Cfg,
+    merge_log: &mut MergeLog,
+) -> Result<()> {
+    let (indexed_records, logs): (Vec<Option<(usize, GameSetting)>>, Vec<OptionRecordMergeLog>) = intermediate_records
+        .gmst
+        .par_iter()
+        .map(|(id_low, map)| -> Result<Option<(Option<(usize, GameSetting)>, OptionRecordMergeLog)>> {
+            let mut option_log = OptionRecordMergeLog::default();
+            if map.records.len() > 1 {
+                let merged = merge_map(id_low, map, &mut option_log, cfg)?.map(|merged| (map.init_id, merged));
+                if merged.is_some() || option_log.is_some() {
+                    return Ok(Some((merged, option_log)));
+                }
+            } else {
+                if !cfg.meta.silent {
+                    check_evil_gmst(id_low, map, &mut option_log, cfg)?;
+                }
+                if option_log.is_some() {
+                    return Ok(Some((None, option_log)));
+                } else if cfg.meta.debug_single {
+                    return Ok(Some((None, OptionRecordMergeLog::single_instance(&map, cfg)?)));
+                }
+            }
+            Ok(None)
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<(Vec<Option<(usize, GameSetting)>>, Vec<OptionRecordMergeLog>)>>()?;
+
+    let mut sorted_records: Vec<(usize, GameSetting)> = indexed_records.into_iter().filter_map(identity).collect();
+    merge_log.push(logs, sorted_records.len(), cfg);
+    if !sorted_records.is_empty() {
+        sorted_records.sort_by_key(|element| element.0);
+        raw_plugin.plugin.objects.extend(sorted_records.into_iter().map(|(_, record)| TES3Object::GameSetting(record)));
+    };
+    Ok(())
+}
+
+fn is_vanilla_master(plugin_info: &PluginInfo) -> bool {
+    VANILLA_MASTERS.contains(&plugin_info.name_lowercased.as_str())
+}
+
+// COMMENT: the last instance from vanilla masters(or the first one if none) is considered vanilla, the last distinct value wins
+fn merge_map(
+    id_low: &str,
+    map: &GameSettingRecordMap,
+    option_log: &mut OptionRecordMergeLog,
+    cfg: &Cfg,
+) -> Result<Option<GameSetting>> {
+    let vanilla_index = map.records.iter().rposition(|record| is_vanilla_master(record.plugin_info)).unwrap_or(0);
+    let vanilla = &map.record(vanilla_index)?.gmst.value;
+    let non_vanilla = map
+        .records
+        .iter()
+        .skip(vanilla_index + 1)
+        .filter(|record| &record.gmst.value != vanilla)
+        .collect::<Vec<_>>();
+    if !cfg.meta.silent {
+        check_evil_gmst(id_low, map, option_log, cfg)?;
+        if let Some(first) = non_vanilla.first() {
+            if non_vanilla.iter().any(|record| record.gmst.value != first.gmst.value) {
+                option_log.warn(
+                    format_args!(
+                        "Conflicting GMST: {id:?}: {count} plugins set different values, vanilla value is {value:?}",
+                        id = map.record_id_debug()?,
+                        count = non_vanilla.len(),
+                        value = ShowValue(vanilla),
+                    ),
+                    &map,
+                    cfg,
+                )?;
+                for record in non_vanilla.iter() {
+                    option_log.field_changed(
+                        "value",
+                        format_args!("{:?}", ShowValue(vanilla)),
+                        format_args!("{:?}", ShowValue(&record.gmst.value)),
+                        &record.plugin_info.name,
+                        &map,
+                        cfg,
+                    )?;
+                }
+            }
+        }
+    }
+    let Some(last_non_vanilla) = non_vanilla.last() else {
+        if cfg.meta.debug_all {
+            option_log.all_equal(&map, cfg)?;
+        }
+        return Ok(None);
+    };
+    if last_non_vanilla.gmst.value == map.last_record()?.gmst.value {
+        return Ok(None);
+    }
+    if !cfg.meta.silent {
+        option_log.field_changed(
+            "value",
+            format_args!("{:?}", ShowValue(&map.last_record()?.gmst.value)),
+            format_args!("{:?}", ShowValue(&last_non_vanilla.gmst.value)),
+            &last_non_vanilla.plugin_info.name,
+            &map,
+            cfg,
+        )?;
+        option_log.record_merged(&map, cfg)?;
+    }
+    Ok(Some(last_non_vanilla.gmst.clone()))
+}
+
+// COMMENT: evil GMSTs are reported when a plugin resets vanilla value or defines them while no vanilla master does
+fn check_evil_gmst(id_low: &str, map: &GameSettingRecordMap, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+    if EVIL_GMSTS.binary_search(&id_low).is_err() {
+        return Ok(());
+    }
+    let vanilla = map
+        .records
+        .iter()
+        .rev()
+        .find(|record| is_vanilla_master(record.plugin_info))
+        .map(|record| &record.gmst.value);
+    for record in map.records.iter().filter(|record| !is_vanilla_master(record.plugin_info)) {
+        let reason = match vanilla {
+            Some(vanilla) if vanilla == &record.gmst.value => "default value reset",
+            Some(_) => continue,
+            None => "defined without vanilla masters",
+        };
+        option_log.warn(
+            format_args!(
+                "Evil GMST: {id:?}: {value:?} {{ {reason} in [\"{plugin}\"] }}",
+                id = map.record_id_debug()?,
+                value = ShowValue(&record.gmst.value),
+                plugin = record.plugin_info.name,
+            ),
+            &map,
+            cfg,
+        )?;
+    }
+    Ok(())
+}
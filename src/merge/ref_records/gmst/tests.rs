@@ -0,0 +1,122 @@
+use super::{assert_eq_inner, merge_gmst, test_init, test_merge, MergeLog, RawPlugin, EVIL_GMSTS};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{GameSetting, GameSettingValue, TES3Object};
+
+macro_rules! set_vanilla_master {
+    ($plugins:ident[$index:expr] = $name:literal) => {
+        $plugins[$index].name = $name.to_string();
+        $plugins[$index].name_lowercased = $name.to_lowercase();
+    };
+}
+
+mod merge {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn no_merge_last_is_non_vanilla() {
+        test_init!(src, plugins, cfg, GameSetting, 4, values_gmst, (id = "iLevelupTotal".to_string()));
+        src[0].value = values_gmst[1].clone();
+        src[1].value = values_gmst[2].clone();
+        src[2].value = values_gmst[1].clone();
+        src[3].value = values_gmst[3].clone();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert!(log.test_warn().contains("Conflicting GMST"));
+    }
+
+    #[test]
+    fn merge_last_non_vanilla_over_reset() {
+        test_init!(src, plugins, cfg, GameSetting, 3, values_gmst, (id = "iLevelupTotal".to_string()));
+        src[0].value = values_gmst[1].clone();
+        src[1].value = values_gmst[2].clone();
+        src[2].value = values_gmst[1].clone();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(GameSetting, src[1], dst[0]);
+        assert_eq!(log.test_warn(), "");
+    }
+
+    #[test]
+    fn merge_last_non_vanilla_repeated_value() {
+        test_init!(src, plugins, cfg, GameSetting, 5, values_gmst, (id = "iLevelupTotal".to_string()));
+        src[0].value = values_gmst[1].clone();
+        src[1].value = values_gmst[2].clone();
+        src[2].value = values_gmst[3].clone();
+        src[3].value = values_gmst[2].clone();
+        src[4].value = values_gmst[1].clone();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(GameSetting, src[3], dst[0]);
+    }
+
+    #[test]
+    fn merge_vanilla_is_the_last_vanilla_master() {
+        test_init!(src, plugins, cfg, GameSetting, 4, values_gmst, (id = "iLevelupTotal".to_string()));
+        set_vanilla_master!(plugins[0] = "Morrowind.esm");
+        set_vanilla_master!(plugins[1] = "Tribunal.esm");
+        src[0].value = values_gmst[1].clone();
+        src[1].value = values_gmst[2].clone();
+        src[2].value = values_gmst[3].clone();
+        src[3].value = values_gmst[2].clone();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(GameSetting, src[2], dst[0]);
+    }
+}
+
+mod evil {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn evil_gmsts_are_sorted() {
+        assert!(EVIL_GMSTS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn default_value_reset() {
+        test_init!(src, plugins, cfg, GameSetting, 2, values_gmst, (id = "iWereWolfBounty".to_string()));
+        set_vanilla_master!(plugins[0] = "Bloodmoon.esm");
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert!(log
+            .test_warn()
+            .contains("Evil GMST: \"iWereWolfBounty\": 1000 { default value reset in [\"Plugin1.esp\"] }"));
+    }
+
+    #[test]
+    fn default_value_reset_in_non_vanilla_master() {
+        test_init!(src, plugins, cfg, GameSetting, 2, values_gmst, (id = "iWereWolfBounty".to_string()));
+        set_vanilla_master!(plugins[0] = "Bloodmoon.esm");
+        plugins[1].name = "Plugin1.esm".to_string();
+        plugins[1].name_lowercased = "plugin1.esm".to_string();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert!(log
+            .test_warn()
+            .contains("Evil GMST: \"iWereWolfBounty\": 1000 { default value reset in [\"Plugin1.esm\"] }"));
+    }
+
+    #[test]
+    fn defined_without_vanilla_masters() {
+        test_init!(src, plugins, cfg, GameSetting, 1, values_gmst, (id = "iWereWolfBounty".to_string()));
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert!(log
+            .test_warn()
+            .contains("Evil GMST: \"iWereWolfBounty\": 1000 { defined without vanilla masters in [\"Plugin0.esp\"] }"));
+    }
+
+    #[test]
+    fn no_warning_for_vanilla_masters() {
+        test_init!(src, plugins, cfg, GameSetting, 2, values_gmst, (id = "iWereWolfBounty".to_string()));
+        set_vanilla_master!(plugins[0] = "Tribunal.esm");
+        set_vanilla_master!(plugins[1] = "Bloodmoon.esm");
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert_eq!(log.test_warn(), "");
+    }
+
+    #[test]
+    fn no_warning_for_changed_value() {
+        test_init!(src, plugins, cfg, GameSetting, 2, values_gmst, (id = "iWereWolfBounty".to_string()));
+        set_vanilla_master!(plugins[0] = "Bloodmoon.esm");
+        src[1].value = values_gmst[1].clone();
+        test_merge!(gmst, src, plugins, cfg, log, im, res, dst:0);
+        assert_eq!(log.test_warn(), "");
+    }
+}
@@ -0,0 +1,52 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Ingredient, IngredientData, ObjectFlags, TES3Object};
+
+pub(crate) struct IngrRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub data: IngredientData,
+}
+
+generic_ref_record_methods!((IngrRef, Ingredient, id), (name, script, mesh, icon), (flags, data), (), (), ());
+
+generic_make_merge!(
+    ingr,
+    (IngrRef, Ingredient, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        data:weight,
+        data:value,
+        data:effects;0,
+        data:effects;1,
+        data:effects;2,
+        data:effects;3,
+        data:skills;0,
+        data:skills;1,
+        data:skills;2,
+        data:skills;3,
+        data:attributes;0,
+        data:attributes;1,
+        data:attributes;2,
+        data:attributes;3
+    ),
+    (),
+    (),
+    (),
+    ()
+);
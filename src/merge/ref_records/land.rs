@@ -0,0 +1,327 @@
+use super::{MergeLog, OptionRecordMergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, Land, LandRecordMap};
+use anyhow::{anyhow, Context, Result};
+use hashbrown::HashMap;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::{convert::identity, fmt};
+use tes3::esp::{Landscape, LandscapeFlags, LandscapeTexture, TES3Object, VertexHeights};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{test_init, test_merge};
+
+const VERTICES: usize = 65;
+const TEXTURES: usize = 16;
+
+struct MergedLand {
+    landscape: Landscape,
+    textures: Vec<Option<String>>,
+    definitions: HashMap<String, LandscapeTexture>,
+}
+
+// COMMENT: (field, changed, overlapping)
+struct ShowCompactLandscape<'a>(&'a [(&'static str, usize, usize)]);
+
+impl fmt::Debug for ShowCompactLandscape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut not_empty = false;
+        for (field, changed, overlapping) in self.0.iter().filter(|(_, changed, _)| *changed > 0) {
+            write!(f, "{}{field}: {changed} changed", if not_empty { ", " } else { "" })?;
+            if *overlapping > 0 {
+                write!(f, " ({overlapping} overlapping)")?;
+            }
+            not_empty = true;
+        }
+        Ok(())
+    }
+}
+
+struct Grids {
+    heights: Vec<i32>,
+    normals: Vec<[i8; 3]>,
+    colors: Vec<[u8; 3]>,
+    textures: Vec<Option<String>>,
+    unknown_textures: usize,
+}
+
+impl Grids {
+    fn new(land: &Land) -> Grids {
+        let mut unknown_textures = 0;
+        let textures = land
+            .landscape
+            .texture_indices
+            .data
+            .iter()
+            .flatten()
+            .map(|value| match value {
+                0 => None,
+                _ => match land.textures.get(value) {
+                    Some(ltex) => Some(ltex.id.to_lowercase()),
+                    None => {
+                        unknown_textures += 1;
+                        None
+                    }
+                },
+            })
+            .collect();
+        Grids {
+            heights: decode_heights(&land.landscape.vertex_heights),
+            normals: land.landscape.vertex_normals.data.iter().flatten().copied().collect(),
+            colors: land.landscape.vertex_colors.data.iter().flatten().copied().collect(),
+            textures,
+            unknown_textures,
+        }
+    }
+}
+
+pub(crate) fn merge_land(
+    intermediate_records: &IntermediateRecords,
+    raw_plugin: &mut RawPlugin,
+    cfg: &Cfg,
+    merge_log: &mut MergeLog,
+) -> Result<()> {
+    let (indexed_records, logs): (Vec<Option<(usize, MergedLand)>>, Vec<OptionRecordMergeLog>) = intermediate_records
+        .land
+        .par_iter()
+        .map(|(_id_low, map)| -> Result<Option<(Option<(usize, MergedLand)>, OptionRecordMergeLog)>> {
+            if map.records.len() > 1 {
+                let mut option_log = OptionRecordMergeLog::default();
+                let merged = merge_map(map, &mut option_log, cfg)?.map(|merged| (map.init_id, merged));
+                if merged.is_some() || option_log.is_some() {
+                    return Ok(Some((merged, option_log)));
+                }
+            } else if cfg.meta.debug_single {
+                return Ok(Some((None, OptionRecordMergeLog::single_instance(&map, cfg)?)));
+            }
+            Ok(None)
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<(Vec<Option<(usize, MergedLand)>>, Vec<OptionRecordMergeLog>)>>()?;
+
+    let mut sorted_records: Vec<(usize, MergedLand)> = indexed_records.into_iter().filter_map(identity).collect();
+    merge_log.push(logs, sorted_records.len(), cfg);
+    sorted_records.sort_by_key(|element| element.0);
+    // COMMENT: merged LTEX records need indices even when there is no merged LAND
+    let landscapes = assign_texture_indices(sorted_records.into_iter().map(|(_, merged)| merged).collect(), raw_plugin)?;
+    raw_plugin.plugin.objects.extend(landscapes.into_iter().map(TES3Object::Landscape));
+    Ok(())
+}
+
+// COMMENT: vertices are merged against the first instance, so edits of different plugins to different vertices are combined
+fn merge_map(map: &LandRecordMap, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<Option<MergedLand>> {
+    let grids = map.records.iter().map(|record| Grids::new(&record.land)).collect::<Vec<Grids>>();
+    macro_rules! merge_grid {
+        ($field:ident) => {
+            merge_grid(&grids.iter().map(|grid| &grid.$field).collect::<Vec<_>>())
+        };
+    }
+    let (heights, heights_changes) = merge_grid!(heights);
+    let (normals, normals_changes) = merge_grid!(normals);
+    let (colors, colors_changes) = merge_grid!(colors);
+    let (textures, textures_changes) = merge_grid!(textures);
+    let last = grids.last().with_context(|| "Bug: failed to get last LAND grids")?;
+    if heights == last.heights && normals == last.normals && colors == last.colors && textures == last.textures {
+        if !cfg.meta.silent {
+            option_log.equal_to_the_last_or_clear(&map, cfg)?;
+        }
+        return Ok(None);
+    }
+    let mut landscape = map.last_record()?.land.landscape.clone();
+    if !encode_heights(&heights, &mut landscape.vertex_heights) {
+        if !cfg.meta.silent {
+            option_log.warn(
+                format_args!(
+                    "Invalid merged LAND record: {id:?}: \"vertex_heights\": height difference between neighbouring vertices exceeds {max} {{ merge skipped, the last instance is kept }}",
+                    id = map.record_id_debug()?,
+                    max = i8::MAX,
+                ),
+                &map,
+                cfg,
+            )?;
+        }
+        return Ok(None);
+    }
+    if !cfg.meta.silent {
+        for (index, (record, grid)) in map.records.iter().zip(grids.iter()).enumerate() {
+            if grid.unknown_textures > 0 {
+                option_log.warn(
+                    format_args!(
+                        "Invalid LAND record: {id:?}: \"texture_indices\": {count} unknown texture indices [\"{plugin}\"] {{ replaced with the default texture }}",
+                        id = map.record_id_debug()?,
+                        count = grid.unknown_textures,
+                        plugin = record.plugin_info.name,
+                    ),
+                    &map,
+                    cfg,
+                )?;
+            }
+            if index == 0 {
+                continue;
+            }
+            let changes = [
+                ("vertex_heights", heights_changes[index].0, heights_changes[index].1),
+                ("vertex_normals", normals_changes[index].0, normals_changes[index].1),
+                ("vertex_colors", colors_changes[index].0, colors_changes[index].1),
+                ("texture_indices", textures_changes[index].0, textures_changes[index].1),
+            ];
+            if changes.iter().any(|(_, changed, _)| *changed > 0) {
+                option_log.field_changed_custom(
+                    "landscape",
+                    format_args!("{:?}", ShowCompactLandscape(&changes)),
+                    &record.plugin_info.name,
+                    &map,
+                    cfg,
+                )?;
+            }
+            if changes.iter().any(|(_, _, overlapping)| *overlapping > 0) {
+                option_log.warn(
+                    format_args!(
+                        "Overlapping LAND edits: {id:?}: {changes:?} [\"{plugin}\"] {{ the last plugin wins for overlapping vertices }}",
+                        id = map.record_id_debug()?,
+                        changes = ShowCompactLandscape(&changes),
+                        plugin = record.plugin_info.name,
+                    ),
+                    &map,
+                    cfg,
+                )?;
+            }
+        }
+        option_log.record_merged(&map, cfg)?;
+    }
+    landscape.landscape_flags = map
+        .records
+        .iter()
+        .fold(LandscapeFlags::empty(), |flags, record| flags | record.land.landscape.landscape_flags);
+    for (merged, value) in landscape.vertex_normals.data.iter_mut().flatten().zip(normals) {
+        *merged = value;
+    }
+    for (merged, value) in landscape.vertex_colors.data.iter_mut().flatten().zip(colors) {
+        *merged = value;
+    }
+    let mut definitions = HashMap::new();
+    for record in map.records.iter() {
+        for ltex in record.land.textures.values() {
+            definitions.insert(ltex.id.to_lowercase(), ltex.clone());
+        }
+    }
+    definitions.retain(|id, _| textures.iter().flatten().any(|texture| texture == id));
+    Ok(Some(MergedLand {
+        landscape,
+        textures,
+        definitions,
+    }))
+}
+
+// COMMENT: returns merged grid and (changed, overlapping) counts for every instance
+fn merge_grid<T: Clone + PartialEq>(grids: &[&Vec<T>]) -> (Vec<T>, Vec<(usize, usize)>) {
+    let base = grids[0];
+    let mut merged = base.clone();
+    let mut owners = vec![0usize; base.len()];
+    let mut changes = vec![(0, 0); grids.len()];
+    for (index, grid) in grids.iter().enumerate().skip(1) {
+        for (point, value) in grid.iter().enumerate() {
+            if *value != base[point] && *value != merged[point] {
+                changes[index].0 += 1;
+                if owners[point] != 0 {
+                    changes[index].1 += 1;
+                }
+                merged[point] = value.clone();
+                owners[point] = index;
+            }
+        }
+    }
+    (merged, changes)
+}
+
+// COMMENT: the first height of the row is relative to the first height of the previous row, others are relative to the previous height
+fn decode_heights(vertex_heights: &VertexHeights) -> Vec<i32> {
+    let mut res = Vec::with_capacity(VERTICES * VERTICES);
+    let mut row_start = vertex_heights.offset as i32;
+    for row in vertex_heights.data.iter() {
+        row_start += row[0] as i32;
+        let mut height = row_start;
+        res.push(height);
+        for delta in row.iter().skip(1) {
+            height += *delta as i32;
+            res.push(height);
+        }
+    }
+    res
+}
+
+// COMMENT: returns false when merged heights are too steep to be stored as deltas, instead of writing corrupted heights
+fn encode_heights(heights: &[i32], vertex_heights: &mut VertexHeights) -> bool {
+    vertex_heights.offset = heights[0] as f32;
+    let mut prev_height = heights[0];
+    for (row, row_heights) in vertex_heights.data.iter_mut().zip(heights.chunks(VERTICES)) {
+        let Ok(first_delta) = i8::try_from(row_heights[0] - prev_height) else {
+            return false;
+        };
+        row[0] = first_delta;
+        prev_height = row_heights[0];
+        for (delta, pair) in row.iter_mut().skip(1).zip(row_heights.windows(2)) {
+            let Ok(value) = i8::try_from(pair[1] - pair[0]) else {
+                return false;
+            };
+            *delta = value;
+        }
+    }
+    true
+}
+
+// COMMENT: LTEX index only has meaning inside its plugin, so every LTEX of the output plugin gets a sequential index, merged ones
+// COMMENT: first, then textures that are only used by merged LAND, whose texture ids were resolved per plugin in Grids::new
+fn assign_texture_indices(merged_lands: Vec<MergedLand>, raw_plugin: &mut RawPlugin) -> Result<Vec<Landscape>> {
+    let mut indices: HashMap<String, u16> = HashMap::new();
+    let mut next_index = 0;
+    for object in raw_plugin.plugin.objects.iter_mut() {
+        if let TES3Object::LandscapeTexture(ltex) = object {
+            let index = check_texture_index(next_index, &ltex.id)?;
+            next_index += 1;
+            ltex.index = index as u32;
+            indices.insert(ltex.id.to_lowercase(), index);
+        }
+    }
+    let mut new_textures = Vec::new();
+    let mut res = Vec::with_capacity(merged_lands.len());
+    for mut merged in merged_lands {
+        for (point, texture) in merged.textures.iter().enumerate() {
+            let value = match texture {
+                None => 0,
+                Some(id) => match indices.get(id) {
+                    Some(index) => index + 1,
+                    None => {
+                        let index = check_texture_index(next_index, id)?;
+                        next_index += 1;
+                        let ltex = merged
+                            .definitions
+                            .get(id)
+                            .with_context(|| format!("Bug: failed to get LTEX record {id:?}"))?;
+                        new_textures.push(LandscapeTexture {
+                            index: index as u32,
+                            ..ltex.clone()
+                        });
+                        indices.insert(id.to_owned(), index);
+                        index + 1
+                    }
+                },
+            };
+            merged.landscape.texture_indices.data[point / TEXTURES][point % TEXTURES] = value;
+        }
+        res.push(merged.landscape);
+    }
+    raw_plugin.plugin.objects.extend(new_textures.into_iter().map(TES3Object::LandscapeTexture));
+    Ok(res)
+}
+
+// COMMENT: LAND refers to LTEX by index + 1, so the index must leave room for it
+fn check_texture_index(index: u32, id: &str) -> Result<u16> {
+    match u16::try_from(index) {
+        Ok(index) if index < u16::MAX - 1 => Ok(index),
+        _ => Err(anyhow!(
+            "Failed to assign index {index} to LTEX record {id:?}: output plugin can't contain more than {} textures",
+            u16::MAX - 1
+        )),
+    }
+}
@@ -0,0 +1,166 @@
+use super::{merge_land, test_init, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, Land, PluginInfo};
+use hashbrown::HashMap;
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::{iter::repeat, sync::Arc};
+use tes3::esp::{Landscape, LandscapeTexture, TES3Object};
+
+fn ltex(id: &str, index: u32) -> LandscapeTexture {
+    LandscapeTexture {
+        id: id.to_string(),
+        index,
+        file_name: format!("{id}.dds"),
+        ..Default::default()
+    }
+}
+
+fn textures(id: &str) -> Arc<HashMap<u16, LandscapeTexture>> {
+    Arc::new(HashMap::from([(1, ltex(id, 0))]))
+}
+
+fn landscape(objects: &[TES3Object]) -> &Landscape {
+    objects
+        .iter()
+        .find_map(|object| match object {
+            TES3Object::Landscape(landscape) => Some(landscape),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn texture_ids(objects: &[TES3Object]) -> Vec<(&str, u32)> {
+    objects
+        .iter()
+        .filter_map(|object| match object {
+            TES3Object::LandscapeTexture(ltex) => Some((ltex.id.as_str(), ltex.index)),
+            _ => None,
+        })
+        .collect()
+}
+
+macro_rules! init_land {
+    ($src:ident, $plugins:ident, $cfg:ident, $repeat:expr, $values:ident) => {
+        test_init!(
+            $src,
+            $plugins,
+            $cfg,
+            Land,
+            $repeat,
+            $values,
+            (landscape = Landscape { grid: (1, 1), ..Default::default() })
+        );
+    };
+}
+
+#[test]
+fn no_merge_last_has_all_edits() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.vertex_colors.data[0][0] = values_vertex_colors[1];
+    src[2].landscape.vertex_colors.data[0][0] = values_vertex_colors[1];
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:0);
+    assert_eq!(log.test_warn(), "");
+}
+
+#[test]
+fn merge_distinct_vertices() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.vertex_colors.data[0][0] = values_vertex_colors[1];
+    src[2].landscape.vertex_colors.data[0][1] = values_vertex_colors[2];
+    src[2].landscape.vertex_normals.data[1][1] = [0, 0, 127];
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:1);
+    let merged = landscape(&dst.objects);
+    assert_eq!(merged.vertex_colors.data[0][0], values_vertex_colors[1]);
+    assert_eq!(merged.vertex_colors.data[0][1], values_vertex_colors[2]);
+    assert_eq!(merged.vertex_normals.data[1][1], [0, 0, 127]);
+    assert_eq!(log.test_warn(), "");
+    assert!(log
+        .test_file()
+        .contains("\"landscape\": vertex_normals: 1 changed, vertex_colors: 1 changed [\"Plugin2.esp\"]"));
+}
+
+#[test]
+fn merge_heights() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.vertex_heights.data[0][1] = 5;
+    src[1].landscape.vertex_heights.data[0][2] = -5;
+    src[2].landscape.vertex_heights.data[2][0] = 3;
+    src[2].landscape.vertex_heights.data[3][0] = -3;
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:1);
+    let merged = landscape(&dst.objects);
+    assert_eq!(merged.vertex_heights.data[0][1..3], [5, -5]);
+    assert_eq!(merged.vertex_heights.data[2][0], 3);
+    assert_eq!(merged.vertex_heights.data[3][0], -3);
+}
+
+#[test]
+fn no_merge_heights_overflow() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.vertex_heights.data[0][1] = 100;
+    src[1].landscape.vertex_heights.data[0][2] = -100;
+    src[2].landscape.vertex_heights.data[0][2] = -100;
+    src[2].landscape.vertex_heights.data[0][3] = 100;
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:0);
+    assert!(log
+        .test_warn()
+        .contains("Invalid merged LAND record: (1, 1): \"vertex_heights\""));
+}
+
+#[test]
+fn warn_overlapping_vertices() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.vertex_colors.data[0][0] = values_vertex_colors[1];
+    src[2].landscape.vertex_colors.data[0][0] = values_vertex_colors[2];
+    src[2].landscape.vertex_colors.data[0][1] = values_vertex_colors[2];
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:1);
+    assert_eq!(landscape(&dst.objects).vertex_colors.data[0][0], values_vertex_colors[2]);
+    assert!(log
+        .test_warn()
+        .contains("Overlapping LAND edits: (1, 1): vertex_colors: 2 changed (1 overlapping) [\"Plugin2.esp\"]"));
+}
+
+#[test]
+fn merge_textures_with_new_indices() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.texture_indices.data[0][0] = 1;
+    src[1].textures = textures("Grass");
+    src[2].landscape.texture_indices.data[0][1] = 1;
+    src[2].textures = textures("Rock");
+    test_merge!(land, src, plugins, cfg, log, im, res, dst:3);
+    assert_eq!(texture_ids(&dst.objects), [("Grass", 0), ("Rock", 1)]);
+    assert_eq!(landscape(&dst.objects).texture_indices.data[0][0..2], [1, 2]);
+}
+
+#[test]
+fn merge_textures_with_colliding_local_indices() {
+    init_land!(src, plugins, cfg, 3, values_vertex_colors);
+    src[1].landscape.texture_indices.data[0][0] = 1;
+    src[1].textures = textures("Grass");
+    src[2].landscape.texture_indices.data[0][1] = 1;
+    src[2].textures = textures("Rock");
+    cfg.reset_meta();
+    let mut raw_dst = RawPlugin::default();
+    raw_dst.plugin.objects.push(TES3Object::LandscapeTexture(ltex("dirt", 0)));
+    raw_dst.plugin.objects.push(TES3Object::LandscapeTexture(ltex("grass", 0)));
+    let mut im = IntermediateRecords::default();
+    let mut log = MergeLog::default();
+    for (object, plugin) in src.iter().zip(&plugins) {
+        im.get_land(object.clone(), plugin);
+    }
+    assert!(merge_land(&im, &mut raw_dst, &cfg, &mut log).is_ok());
+    let dst = raw_dst.plugin;
+    assert_eq!(texture_ids(&dst.objects), [("dirt", 0), ("grass", 1), ("Rock", 2)]);
+    assert_eq!(landscape(&dst.objects).texture_indices.data[0][0..2], [2, 3]);
+}
+
+#[test]
+fn merged_ltex_without_land_get_sequential_indices() {
+    let mut cfg = Cfg::default();
+    cfg.reset_meta();
+    let mut raw_dst = RawPlugin::default();
+    raw_dst.plugin.objects.push(TES3Object::LandscapeTexture(ltex("dirt", 0)));
+    raw_dst.plugin.objects.push(TES3Object::LandscapeTexture(ltex("grass", 0)));
+    let mut log = MergeLog::default();
+    assert!(merge_land(&IntermediateRecords::default(), &mut raw_dst, &cfg, &mut log).is_ok());
+    assert_eq!(texture_ids(&raw_dst.plugin.objects), [("dirt", 0), ("grass", 1)]);
+}
@@ -0,0 +1,66 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_flags, show_object_flags, MergeLog, OptionRecordMergeLog,
+    RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Light, LightData, LightFlags, ObjectFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct LighRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub sound: &'a str,
+    pub data: LightData,
+}
+
+show_flags!(
+    LightFlags,
+    DYNAMIC,
+    CAN_CARRY,
+    NEGATIVE,
+    FLICKER,
+    FIRE,
+    OFF_BY_DEFAULT,
+    FLICKER_SLOW,
+    PULSE,
+    PULSE_SLOW
+);
+
+generic_ref_record_methods!((LighRef, Light, id), (name, script, mesh, icon, sound), (flags, data), (), (), ());
+
+generic_make_merge!(
+    ligh,
+    (LighRef, Light, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        script.&,
+        mesh.&,
+        icon.&,
+        sound.&,
+        data:weight,
+        data:value,
+        data:time,
+        data:radius,
+        data:color;0,
+        data:color;1,
+        data:color;2,
+        data:flags=LightFlags
+    ),
+    (),
+    (),
+    (),
+    ()
+);
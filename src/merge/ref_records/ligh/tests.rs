@@ -0,0 +1,46 @@
+use super::{assert_eq_inner, merge_ligh, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Light, ObjectFlags, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(ligh, Light, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(ligh, Light, values_string:name);
+    }
+
+    mod sound {
+        use super::{assert_eq, *};
+        test_basic!(ligh, Light, values_string:sound);
+    }
+
+    mod data_radius {
+        use super::{assert_eq, *};
+        test_basic!(ligh, Light, values_u32:data:radius);
+    }
+
+    mod data_weight {
+        use super::{assert_eq, *};
+        test_basic!(ligh, Light, values_f32:data:weight);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            ligh,
+            Light,
+            "Merged LIGH record: \"\"\n",
+            "Merging LIGH record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"sound\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged LIGH record: \"\"\n",
+            values_string:sound
+        );
+    }
+}
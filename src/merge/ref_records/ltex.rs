@@ -0,0 +1,30 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{LandscapeTexture, ObjectFlags, TES3Object};
+
+pub(crate) struct LtexRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub index: u32,
+    pub file_name: &'a str,
+}
+
+generic_ref_record_methods!((LtexRef, LandscapeTexture, id), (file_name), (flags, index), (), (), ());
+
+// COMMENT: index is not merged, because it only has meaning inside its plugin, it's assigned for the output plugin in merge_land
+generic_make_merge!(
+    ltex,
+    (LtexRef, LandscapeTexture, id),
+    (flags=ObjectFlags, file_name.&),
+    (),
+    (),
+    (),
+    ()
+);
@@ -0,0 +1,32 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{MiscItem, MiscItemData, ObjectFlags, TES3Object};
+
+pub(crate) struct MiscRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub script: &'a str,
+    pub data: MiscItemData,
+}
+
+generic_ref_record_methods!((MiscRef, MiscItem, id), (name, mesh, icon, script), (flags, data), (), (), ());
+
+generic_make_merge!(
+    misc,
+    (MiscRef, MiscItem, id),
+    (flags=ObjectFlags, name.&, mesh.&, icon.&, script.&, data:weight, data:value, data:flags),
+    (),
+    (),
+    (),
+    ()
+);
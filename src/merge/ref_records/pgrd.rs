@@ -0,0 +1,199 @@
+use super::{MergeLog, OptionRecordMergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PathGridRecordMap};
+use anyhow::{Context, Result};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{PathGrid, PathGridPoint, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{test_init, test_merge};
+
+struct Graph {
+    points: Vec<PathGridPoint>,
+    edges: Vec<Vec<u32>>,
+}
+
+impl Graph {
+    fn new(pgrd: &PathGrid) -> Graph {
+        let mut connections = pgrd.connections.iter();
+        let edges = pgrd
+            .points
+            .iter()
+            .map(|point| {
+                let mut targets = connections.by_ref().take(point.connection_count as usize).copied().collect::<Vec<u32>>();
+                targets.sort();
+                targets.dedup();
+                targets
+            })
+            .collect();
+        Graph {
+            points: pgrd.points.clone(),
+            edges,
+        }
+    }
+
+    fn is_equal(&self, other: &Graph) -> bool {
+        self.edges == other.edges
+            && self.points.len() == other.points.len()
+            && self.points.iter().zip(other.points.iter()).all(|(a, b)| a.location == b.location)
+    }
+}
+
+pub(crate) fn merge_pgrd(
+    intermediate_records: &IntermediateRecords,
+    raw_plugin: &mut RawPlugin,
+    cfg: &Cfg,
+    merge_log: &mut MergeLog,
+) -> Result<()> {
+    let (indexed_records, logs): (Vec<Option<(usize, PathGrid)>>, Vec<OptionRecordMergeLog>) = intermediate_records
+        .pgrd
+        .par_iter()
+        .map(|(_id_low, map)| -> Result<Option<(Option<(usize, PathGrid)>, OptionRecordMergeLog)>> {
+            if map.records.len() > 1 {
+                let mut option_log = OptionRecordMergeLog::default();
+                let merged = merge_map(map, &mut option_log, cfg)?.map(|merged| (map.init_id, merged));
+                if merged.is_some() || option_log.is_some() {
+                    return Ok(Some((merged, option_log)));
+                }
+            } else if cfg.meta.debug_single {
+                return Ok(Some((None, OptionRecordMergeLog::single_instance(&map, cfg)?)));
+            }
+            Ok(None)
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<(Vec<Option<(usize, PathGrid)>>, Vec<OptionRecordMergeLog>)>>()?;
+
+    let mut sorted_records: Vec<(usize, PathGrid)> = indexed_records.into_iter().filter_map(identity).collect();
+    merge_log.push(logs, sorted_records.len(), cfg);
+    if !sorted_records.is_empty() {
+        sorted_records.sort_by_key(|element| element.0);
+        raw_plugin.plugin.objects.extend(sorted_records.into_iter().map(|(_, record)| TES3Object::PathGrid(record)));
+    };
+    Ok(())
+}
+
+// COMMENT: points of every plugin are joined with similar points of the merged grid, so nothing is ever removed
+fn merge_map(map: &PathGridRecordMap, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<Option<PathGrid>> {
+    let mut merged = Graph::new(&map.record(0)?.pgrd);
+    for record in map.records.iter().skip(1) {
+        let new = Graph::new(&record.pgrd);
+        let mut matched = vec![false; merged.points.len()];
+        let mut added_points = 0;
+        let indices = new
+            .points
+            .iter()
+            .map(|point| {
+                // COMMENT: points appended for the current plugin are not matched against, because they are distinct in it
+                match (0..matched.len()).find(|index| {
+                    !matched[*index]
+                        && merged.points[*index]
+                            .location
+                            .iter()
+                            .zip(point.location.iter())
+                            .all(|(a, b)| (a - b).abs() as f32 <= cfg.merge.pathgrid_similarity)
+                }) {
+                    Some(index) => {
+                        matched[index] = true;
+                        index as u32
+                    }
+                    None => {
+                        merged.points.push(point.clone());
+                        merged.edges.push(Vec::new());
+                        added_points += 1;
+                        (merged.points.len() - 1) as u32
+                    }
+                }
+            })
+            .collect::<Vec<u32>>();
+        let mut added_edges = 0;
+        let mut invalid_edges = 0;
+        for (from, targets) in new.edges.iter().enumerate() {
+            for target in targets {
+                let Some(to) = indices.get(*target as usize) else {
+                    invalid_edges += 1;
+                    continue;
+                };
+                let edges = &mut merged.edges[indices[from] as usize];
+                if *to != indices[from] && !edges.contains(to) {
+                    edges.push(*to);
+                    edges.sort();
+                    added_edges += 1;
+                }
+            }
+        }
+        if !cfg.meta.silent {
+            if invalid_edges > 0 {
+                option_log.warn(
+                    format_args!(
+                        "Invalid PGRD record: {id:?}: \"connections\": {invalid_edges} connections to missing points [\"{plugin}\"] {{ skipped }}",
+                        id = map.record_id_debug()?,
+                        plugin = record.plugin_info.name,
+                    ),
+                    &map,
+                    cfg,
+                )?;
+            }
+            if added_points > 0 || added_edges > 0 {
+                option_log.field_extend(
+                    "points",
+                    false,
+                    format_args!("{added_points} points, {added_edges} connections"),
+                    &record.plugin_info.name,
+                    &map,
+                    cfg,
+                )?;
+            }
+        }
+    }
+    let last = &map.last_record()?.pgrd;
+    if merged.is_equal(&Graph::new(last)) {
+        if !cfg.meta.silent {
+            option_log.equal_to_the_last_or_clear(&map, cfg)?;
+        }
+        return Ok(None);
+    }
+    let Ok(connection_counts) = merged
+        .edges
+        .iter()
+        .map(|edges| u8::try_from(edges.len()))
+        .collect::<Result<Vec<u8>, _>>()
+    else {
+        if !cfg.meta.silent {
+            let (point, edges) = merged
+                .edges
+                .iter()
+                .enumerate()
+                .find(|(_, edges)| edges.len() > u8::MAX as usize)
+                .with_context(|| "Bug: failed to find PGRD point with too many connections")?;
+            option_log.warn(
+                format_args!(
+                    "Invalid merged PGRD record: {id:?}: \"connections\": point {point} has {count} connections, more than {max} {{ merge skipped, the last instance is kept }}",
+                    id = map.record_id_debug()?,
+                    count = edges.len(),
+                    max = u8::MAX,
+                ),
+                &map,
+                cfg,
+            )?;
+        }
+        return Ok(None);
+    };
+    if !cfg.meta.silent {
+        option_log.record_merged(&map, cfg)?;
+    }
+    let mut res = last.clone();
+    res.data.point_count = merged.points.len() as u16;
+    res.points = merged
+        .points
+        .into_iter()
+        .zip(connection_counts)
+        .map(|(point, connection_count)| PathGridPoint {
+            connection_count,
+            ..point
+        })
+        .collect();
+    res.connections = merged.edges.into_iter().flatten().collect();
+    Ok(Some(res))
+}
@@ -0,0 +1,106 @@
+use super::{merge_pgrd, test_init, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::{iter::repeat, ops::Range};
+use tes3::esp::{PathGrid, PathGridPoint, TES3Object};
+
+fn pathgrid(objects: &[TES3Object]) -> &PathGrid {
+    objects
+        .iter()
+        .find_map(|object| match object {
+            TES3Object::PathGrid(pgrd) => Some(pgrd),
+            _ => None,
+        })
+        .unwrap()
+}
+
+// COMMENT: the first point is connected to the given range of other points
+fn star(points: i32, connected: Range<u32>) -> PathGrid {
+    let mut pgrd = PathGrid {
+        cell: String::from("Test Cell"),
+        ..Default::default()
+    };
+    pgrd.points = (0..points)
+        .map(|index| PathGridPoint {
+            location: [index * 1000, 0, 0],
+            ..Default::default()
+        })
+        .collect();
+    pgrd.points[0].connection_count = connected.len() as u8;
+    pgrd.connections = connected.collect();
+    pgrd.data.point_count = pgrd.points.len() as u16;
+    pgrd
+}
+
+#[test]
+fn no_merge_last_has_all_points() {
+    test_init!(src, plugins, cfg, PathGrid, 3, values_pathgrid; cfg=merge:pathgrid_similarity = 64.0);
+    src[0] = values_pathgrid[0].clone();
+    src[1] = values_pathgrid[1].clone();
+    src[2] = values_pathgrid[1].clone();
+    test_merge!(pgrd, src, plugins, cfg, log, im, res, dst:0);
+    assert_eq!(log.test_warn(), "");
+}
+
+#[test]
+fn merge_points_of_different_plugins() {
+    test_init!(src, plugins, cfg, PathGrid, 3, values_pathgrid; cfg=merge:pathgrid_similarity = 64.0);
+    src[0] = values_pathgrid[0].clone();
+    src[1] = values_pathgrid[1].clone();
+    src[2] = values_pathgrid[2].clone();
+    test_merge!(pgrd, src, plugins, cfg, log, im, res, dst:1);
+    let merged = pathgrid(&dst.objects);
+    assert_eq!(merged.data.point_count, 4);
+    assert_eq!(
+        merged.points.iter().map(|point| point.location).collect::<Vec<_>>(),
+        [[0, 0, 0], [100, 0, 0], [200, 0, 0], [0, 300, 0]]
+    );
+    assert_eq!(
+        merged.points.iter().map(|point| point.connection_count).collect::<Vec<_>>(),
+        [2, 2, 1, 1]
+    );
+    assert_eq!(merged.connections, [1, 3, 0, 2, 1, 0]);
+    assert_eq!(log.test_warn(), "");
+    assert!(log
+        .test_file()
+        .contains("\"points\": + 1 points, 2 connections [\"Plugin2.esp\"]"));
+}
+
+#[test]
+fn similar_points_are_joined() {
+    test_init!(src, plugins, cfg, PathGrid, 3, values_pathgrid; cfg=merge:pathgrid_similarity = 64.0);
+    src[0] = values_pathgrid[0].clone();
+    src[1] = values_pathgrid[1].clone();
+    src[2] = values_pathgrid[3].clone();
+    test_merge!(pgrd, src, plugins, cfg, log, im, res, dst:1);
+    let merged = pathgrid(&dst.objects);
+    assert_eq!(merged.points.len(), 4);
+    assert_eq!(merged.points[0].location, [0, 0, 0]);
+    assert_eq!(merged.points[3].location, [200, 200, 0]);
+}
+
+#[test]
+fn warn_connections_to_missing_points() {
+    test_init!(src, plugins, cfg, PathGrid, 3, values_pathgrid; cfg=merge:pathgrid_similarity = 64.0);
+    src[0] = values_pathgrid[0].clone();
+    src[1] = values_pathgrid[1].clone();
+    src[2] = values_pathgrid[4].clone();
+    test_merge!(pgrd, src, plugins, cfg, log, im, res, dst:1);
+    assert_eq!(pathgrid(&dst.objects).points.len(), 3);
+    assert!(log
+        .test_warn()
+        .contains("Invalid PGRD record: (\"Test Cell\", (0, 0)): \"connections\": 1 connections to missing points [\"Plugin2.esp\"]"));
+}
+
+#[test]
+fn no_merge_too_many_connections() {
+    test_init!(src, plugins, cfg, PathGrid, 3, values_pathgrid; cfg=merge:pathgrid_similarity = 64.0);
+    src[0] = star(401, 0..0);
+    src[1] = star(401, 1..201);
+    src[2] = star(401, 201..401);
+    test_merge!(pgrd, src, plugins, cfg, log, im, res, dst:0);
+    assert!(log
+        .test_warn()
+        .contains("Invalid merged PGRD record: (\"Test Cell\", (0, 0)): \"connections\": point 0 has 400 connections, more than 255"));
+}
@@ -0,0 +1,32 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{ObjectFlags, Probe, ProbeData, TES3Object};
+
+pub(crate) struct ProbRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub script: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub data: ProbeData,
+}
+
+generic_ref_record_methods!((ProbRef, Probe, id), (name, script, mesh, icon), (flags, data), (), (), ());
+
+generic_make_merge!(
+    prob,
+    (ProbRef, Probe, id),
+    (flags=ObjectFlags, name.&, script.&, mesh.&, icon.&, data:weight, data:value, data:quality, data:uses),
+    (),
+    (),
+    (),
+    ()
+);
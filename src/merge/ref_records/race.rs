@@ -10,6 +10,11 @@ use std::convert::identity;
 // use tes3::esp::{ObjectFlags, Race, RaceData, RaceFlags, SkillBonuses, SkillId, TES3Object};
 use tes3::esp::{ObjectFlags, Race, RaceData, RaceFlags, TES3Object};
 
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
 pub(crate) struct RaceRef<'a> {
     pub flags: ObjectFlags,
     pub id: &'a str,
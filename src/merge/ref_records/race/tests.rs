@@ -0,0 +1,36 @@
+use super::{assert_eq_inner, merge_race, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{ObjectFlags, Race, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(race, Race, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(race, Race, values_string:name);
+    }
+
+    mod description {
+        use super::{assert_eq, *};
+        test_basic!(race, Race, values_string:description);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            race,
+            Race,
+            "Merged RACE record: \"\"\n",
+            "Merging RACE record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"description\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged RACE record: \"\"\n",
+            values_string:description
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use super::{
+    generic_make_merge, generic_ref_record_method_sounds, generic_ref_record_methods, print_as, show_object_flags, sound_to_lowercase,
+    LowSounds, MergeLog, OptionRecordMergeLog, RawPlugin, SoundsHelper, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use hashbrown::{HashMap, HashSet};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{FixedString, ObjectFlags, Region, TES3Object, WeatherChances};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct RegnRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub weather_chances: WeatherChances,
+    pub sleep_creature: &'a str,
+    pub map_color: [u8; 4],
+    pub sounds: Vec<(FixedString<32>, u8)>,
+    pub base: &'a Region,
+    pub low: LowSounds,
+}
+
+generic_ref_record_method_sounds!(RegnRef);
+generic_ref_record_methods!(
+    (RegnRef, Region, id),
+    (name, sleep_creature),
+    (flags, weather_chances, map_color),
+    (LowSounds),
+    (sounds),
+    ()
+);
+
+generic_make_merge!(
+    regn,
+    (RegnRef, Region, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        weather_chances:clear,
+        weather_chances:cloudy,
+        weather_chances:foggy,
+        weather_chances:overcast,
+        weather_chances:rain,
+        weather_chances:thunder,
+        weather_chances:ash,
+        weather_chances:blight,
+        weather_chances:snow,
+        weather_chances:blizzard,
+        sleep_creature.&,
+        map_color
+    ),
+    (sounds),
+    (),
+    (),
+    ()
+);
+
+// COMMENT: leveled creature lists written into the output plugin override the last loaded ones
+pub(crate) fn leveled_creatures_after_merge(raw_plugin: &RawPlugin, last_loaded: &HashSet<String>) -> HashSet<String> {
+    let mut res = last_loaded.clone();
+    for levc in raw_plugin.plugin.objects.iter().filter_map(|object| match object {
+        TES3Object::LeveledCreature(levc) => Some(levc),
+        _ => None,
+    }) {
+        if levc.flags.contains(ObjectFlags::DELETED) {
+            res.remove(&levc.id.to_lowercase());
+        } else {
+            res.insert(levc.id.to_lowercase());
+        }
+    }
+    res
+}
+
+// COMMENT: sleep creature must be a leveled creature list, otherwise the game fails to spawn anything when resting
+pub(crate) fn check_sleep_creatures(
+    intermediate_records: &IntermediateRecords,
+    raw_plugin: &RawPlugin,
+    leveled_creatures: &HashSet<String>,
+    cfg: &Cfg,
+    merge_log: &mut MergeLog,
+) -> Result<()> {
+    let merged_regions = raw_plugin
+        .plugin
+        .objects
+        .iter()
+        .filter_map(|object| match object {
+            TES3Object::Region(region) => Some((region.id.to_lowercase(), region)),
+            _ => None,
+        })
+        .collect::<HashMap<String, &Region>>();
+    let logs = intermediate_records
+        .regn
+        .par_iter()
+        .map(|(id_low, map)| -> Result<Option<OptionRecordMergeLog>> {
+            let (sleep_creature, plugin_name) = match merged_regions.get(id_low) {
+                Some(merged) => (&merged.sleep_creature, cfg.output.name.as_str()),
+                None => {
+                    let last = map.last_record()?;
+                    (&last.regn.sleep_creature, last.plugin_info.name.as_str())
+                }
+            };
+            if sleep_creature.is_empty() || leveled_creatures.contains(&sleep_creature.to_lowercase()) {
+                return Ok(None);
+            }
+            let mut option_log = OptionRecordMergeLog::default();
+            option_log.warn(
+                format_args!(
+                    "Invalid REGN record: {id:?}: \"sleep_creature\": {sleep_creature:?} [\"{plugin_name}\"] {{ leveled creature list does not exist after merge }}",
+                    id = map.record_id_debug()?,
+                ),
+                &map,
+                cfg,
+            )?;
+            Ok(Some(option_log))
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<Vec<OptionRecordMergeLog>>>()?;
+    merge_log.push(logs, 0, cfg);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use super::{
+    assert_eq_inner, check_sleep_creatures, leveled_creatures_after_merge, merge_regn, test_basic, test_init, test_log, test_merge,
+    MergeLog, RawPlugin,
+};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use hashbrown::HashSet;
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{FixedString, LeveledCreature, ObjectFlags, Region, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(regn, Region, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(regn, Region, values_string:name);
+    }
+
+    mod weather_chances_rain {
+        use super::{assert_eq, *};
+        test_basic!(regn, Region, values_u8:weather_chances:rain);
+    }
+
+    mod sleep_creature {
+        use super::{assert_eq, *};
+        test_basic!(regn, Region, values_string:sleep_creature);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            regn,
+            Region,
+            "Merged REGN record: \"\"\n",
+            "Merging REGN record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"weather_chances.rain\": 20 -> 21 [\"Plugin1.esp\"]\nMerged REGN record: \"\"\n",
+            values_u8:weather_chances:rain
+        );
+    }
+}
+
+mod sounds {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn no_merge_case_differs() {
+        test_init!(src, plugins, cfg, Region, 3, values_sounds);
+        src[0].sounds = values_sounds[0].clone();
+        src[1].sounds = values_sounds[1].clone();
+        src[2].sounds = values_sounds[5].clone();
+        test_merge!(regn, src, plugins, cfg, log, im, res, dst:0);
+    }
+
+    #[test]
+    fn merge_delete_and_add() {
+        test_init!(src, plugins, cfg, Region, 3, values_sounds);
+        src[0].sounds = values_sounds[0].clone();
+        src[1].sounds = values_sounds[2].clone();
+        src[2].sounds = values_sounds[3].clone();
+        let expected = Region { sounds: values_sounds[4].clone(), ..Default::default() };
+        test_merge!(regn, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Region, expected, dst[0]);
+        assert!(log.test_file().contains("\"sounds\": - \"sound_0\"(10) [\"Plugin1.esp\"]"));
+        assert!(log.test_file().contains("\"sounds\": + \"sound_2\"(30) [\"Plugin2.esp\"]"));
+    }
+}
+
+mod sleep_creatures {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn missing_leveled_creature_list() {
+        test_init!(src, plugins, cfg, Region, 2, values_string);
+        src[1].sleep_creature = "Missing_LEVC".to_string();
+        test_merge!(regn, src, plugins, cfg, log, im, res, dst:0);
+        let leveled_creatures = HashSet::from(["present_levc".to_string()]);
+        assert!(check_sleep_creatures(&im, &RawPlugin::default(), &leveled_creatures, &cfg, &mut log).is_ok());
+        assert_eq!(
+            log.test_warn(),
+            "Warning: Invalid REGN record: \"\": \"sleep_creature\": \"Missing_LEVC\" [\"Plugin1.esp\"] { leveled creature list does not exist after merge }\n"
+        );
+    }
+
+    #[test]
+    fn present_leveled_creature_list() {
+        test_init!(src, plugins, cfg, Region, 2, values_string);
+        src[1].sleep_creature = "Present_LEVC".to_string();
+        test_merge!(regn, src, plugins, cfg, log, im, res, dst:0);
+        let leveled_creatures = HashSet::from(["present_levc".to_string()]);
+        assert!(check_sleep_creatures(&im, &RawPlugin::default(), &leveled_creatures, &cfg, &mut log).is_ok());
+        assert_eq!(log.test_warn(), "");
+    }
+
+    #[test]
+    fn leveled_creature_list_deleted_in_output() {
+        let mut raw_plugin = RawPlugin::default();
+        raw_plugin.plugin.objects.push(TES3Object::LeveledCreature(LeveledCreature {
+            flags: ObjectFlags::DELETED,
+            id: "Present_LEVC".to_string(),
+            ..Default::default()
+        }));
+        raw_plugin.plugin.objects.push(TES3Object::LeveledCreature(LeveledCreature {
+            id: "Merged_LEVC".to_string(),
+            ..Default::default()
+        }));
+        let last_loaded = HashSet::from(["present_levc".to_string()]);
+        assert_eq!(
+            leveled_creatures_after_merge(&raw_plugin, &last_loaded),
+            HashSet::from(["merged_levc".to_string()])
+        );
+    }
+}
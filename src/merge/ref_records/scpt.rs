@@ -0,0 +1,110 @@
+use crate::{append_for_details_or_check_log, msg, plural, Cfg, IntermediateRecords, Log, MsgTone, ScriptRecordMap};
+use anyhow::Result;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::{convert::identity, fmt::Write as _};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::test_init;
+
+const DIFF_MAX_LINES: usize = 1000;
+
+pub(crate) fn check_script_conflicts(intermediate_records: &IntermediateRecords, cfg: &Cfg, log: &mut Log) -> Result<()> {
+    let conflicts = get_script_conflicts(intermediate_records, cfg)?;
+    if !conflicts.is_empty() {
+        let level = cfg.guts.verboseness_details_script_conflicts;
+        let mut text = format!(
+            "{} script{} {} overridden with different text",
+            conflicts.len(),
+            plural("s", conflicts.len())?,
+            plural("were", conflicts.len())?
+        );
+        append_for_details_or_check_log(&mut text, level, cfg)?;
+        msg(text, MsgTone::Ugly, 0, cfg, log)?;
+        msg("", MsgTone::Neutral, level, cfg, log)?;
+        let report = conflicts.concat();
+        msg(&report[..report.len() - 1], MsgTone::Neutral, level, cfg, log)?;
+    }
+    Ok(())
+}
+
+pub(super) fn get_script_conflicts(intermediate_records: &IntermediateRecords, cfg: &Cfg) -> Result<Vec<String>> {
+    let mut conflicts = intermediate_records
+        .scpt
+        .par_iter()
+        .map(|(_id_low, map)| -> Result<Option<(usize, String)>> {
+            if map.records.len() > 1 {
+                Ok(describe_conflict(map, cfg)?.map(|text| (map.init_id, text)))
+            } else {
+                Ok(None)
+            }
+        })
+        .filter_map(|result_option| identity(result_option.transpose()))
+        .collect::<Result<Vec<(usize, String)>>>()?;
+    conflicts.sort_by_key(|element| element.0);
+    Ok(conflicts.into_iter().map(|(_, text)| text).collect())
+}
+
+// COMMENT: scripts are compared line by line with trailing whitespace ignored, because line endings differ between editors
+fn describe_conflict(map: &ScriptRecordMap, cfg: &Cfg) -> Result<Option<String>> {
+    let first = lines(&map.record(0)?.scpt.text);
+    if map.records.iter().skip(1).all(|record| lines(&record.scpt.text) == first) {
+        return Ok(None);
+    }
+    let mut text = String::new();
+    let plugins = map.records.iter().map(|record| record.plugin_info.name.as_str()).collect::<Vec<&str>>();
+    writeln!(text, "{}SCPT {:?}: {:?}", cfg.guts.tab_l1, map.record_id_debug()?, plugins)?;
+    let last = lines(&map.last_record()?.scpt.text);
+    if last == first {
+        writeln!(text, "{}first and last definitions are equal", cfg.guts.tab_l2)?;
+    } else {
+        diff_lines(&first, &last, &cfg.guts.tab_l2, &mut text)?;
+    }
+    Ok(Some(text))
+}
+
+fn lines(text: &str) -> Vec<&str> {
+    text.lines().map(str::trim_end).collect()
+}
+
+// COMMENT: longest common subsequence of lines, with common head and tail skipped first to keep the table small, while changes
+// COMMENT: longer than DIFF_MAX_LINES are not shown, because the table grows quadratically
+pub(super) fn diff_lines(old: &[&str], new: &[&str], tab: &str, text: &mut String) -> Result<()> {
+    let head = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let tail = old[head..].iter().rev().zip(new[head..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (old, new) = (&old[head..old.len() - tail], &new[head..new.len() - tail]);
+    if old.len() > DIFF_MAX_LINES || new.len() > DIFF_MAX_LINES {
+        writeln!(
+            text,
+            "{tab}scripts differ in lines {}..{}, too many changed lines to show",
+            head + 1,
+            head + old.len().max(new.len())
+        )?;
+        return Ok(());
+    }
+    let mut table = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || table[i + 1][j] >= table[i][j + 1]) {
+            writeln!(text, "{tab}- {}: {}", head + i + 1, old[i])?;
+            i += 1;
+        } else {
+            writeln!(text, "{tab}+ {}: {}", head + j + 1, new[j])?;
+            j += 1;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use super::{diff_lines, get_script_conflicts, test_init, DIFF_MAX_LINES};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::Script;
+
+fn conflicts(src: &[Script], plugins: &[PluginInfo], cfg: &mut Cfg) -> Vec<String> {
+    cfg.reset_meta();
+    let mut im = IntermediateRecords::default();
+    for (object, plugin) in src.iter().zip(plugins) {
+        im.get_scpt(object.clone(), plugin);
+    }
+    get_script_conflicts(&im, cfg).unwrap()
+}
+
+#[test]
+fn no_conflict_for_equal_text() {
+    test_init!(src, plugins, cfg, Script, 2, values_script_text, (id = String::from("TestScript")));
+    src[0].text = values_script_text[0].clone();
+    src[1].text = values_script_text[1].clone();
+    assert_eq!(conflicts(&src, &plugins, &mut cfg), Vec::<String>::new());
+}
+
+#[test]
+fn conflict_lists_plugins_and_diff() {
+    test_init!(src, plugins, cfg, Script, 3, values_script_text, (id = String::from("TestScript")));
+    src[0].text = values_script_text[2].clone();
+    src[1].text = values_script_text[0].clone();
+    src[2].text = values_script_text[3].clone();
+    let report = conflicts(&src, &plugins, &mut cfg);
+    assert_eq!(report.len(), 1);
+    assert_eq!(
+        report[0],
+        "SCPT \"TestScript\": [\"Plugin0.esp\", \"Plugin1.esp\", \"Plugin2.esp\"]\n- 3: set a to 1\n+ 3: set a to 2\n"
+    );
+}
+
+#[test]
+fn conflict_with_equal_first_and_last() {
+    test_init!(src, plugins, cfg, Script, 3, values_script_text, (id = String::from("TestScript")));
+    src[0].text = values_script_text[0].clone();
+    src[1].text = values_script_text[4].clone();
+    src[2].text = values_script_text[0].clone();
+    let report = conflicts(&src, &plugins, &mut cfg);
+    assert_eq!(report.len(), 1);
+    assert!(report[0].ends_with("first and last definitions are equal\n"));
+}
+
+#[test]
+fn diff_lines_insertions_and_deletions() {
+    let mut text = String::new();
+    diff_lines(&["a", "b", "c", "d"], &["a", "c", "x", "d"], "", &mut text).unwrap();
+    assert_eq!(text, "- 2: b\n+ 3: x\n");
+}
+
+#[test]
+fn diff_lines_too_many_changes() {
+    let old = [vec!["c"], vec!["a"; DIFF_MAX_LINES + 1], vec!["d"]].concat();
+    let new = [vec!["c"], vec!["b"; DIFF_MAX_LINES + 1], vec!["d"]].concat();
+    let mut text = String::new();
+    diff_lines(&old, &new, "", &mut text).unwrap();
+    assert_eq!(text, format!("scripts differ in lines 2..{}, too many changed lines to show\n", DIFF_MAX_LINES + 2));
+}
@@ -0,0 +1,30 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{ObjectFlags, SoundGen, SoundGenType, TES3Object};
+
+pub(crate) struct SndgRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub sound_gen_type: SoundGenType,
+    pub creature: &'a str,
+    pub sound: &'a str,
+}
+
+generic_ref_record_methods!((SndgRef, SoundGen, id), (creature, sound), (flags, sound_gen_type), (), (), ());
+
+generic_make_merge!(
+    sndg,
+    (SndgRef, SoundGen, id),
+    (flags=ObjectFlags, sound_gen_type, creature.&, sound.&),
+    (),
+    (),
+    (),
+    ()
+);
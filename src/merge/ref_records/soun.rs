@@ -0,0 +1,29 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{ObjectFlags, Sound, SoundData, TES3Object};
+
+pub(crate) struct SounRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub sound_path: &'a str,
+    pub data: SoundData,
+}
+
+generic_ref_record_methods!((SounRef, Sound, id), (sound_path), (flags, data), (), (), ());
+
+generic_make_merge!(
+    soun,
+    (SounRef, Sound, id),
+    (flags=ObjectFlags, sound_path.&, data:volume, data:range:0, data:range:1),
+    (),
+    (),
+    (),
+    ()
+);
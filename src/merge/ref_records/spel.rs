@@ -0,0 +1,40 @@
+use super::{
+    generic_make_merge, generic_ref_record_method_effects, generic_ref_record_methods, print_as, show_flags, show_object_flags,
+    EffectsHelper, LowEffects, MergeLog, OptionRecordMergeLog, RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{Effect, ObjectFlags, Spell, SpellData, SpellFlags, TES3Object};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_init, test_log, test_merge};
+
+pub(crate) struct SpelRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub data: SpellData,
+    pub effects: Vec<Effect>,
+    pub base: &'a Spell,
+    pub low: LowEffects,
+}
+
+show_flags!(SpellFlags, AUTO_CALCULATE, STARTER_SPELL, ALWAYS_SUCCEEDS);
+
+generic_ref_record_method_effects!(SpelRef);
+generic_ref_record_methods!((SpelRef, Spell, id), (name), (flags, data), (LowEffects), (effects), ());
+
+generic_make_merge!(
+    spel,
+    (SpelRef, Spell, id),
+    (flags=ObjectFlags, name.&, data:spell_type, data:cost, data:flags=SpellFlags),
+    (effects),
+    (),
+    (),
+    ()
+);
@@ -0,0 +1,79 @@
+use super::{assert_eq_inner, merge_spel, test_basic, test_init, test_log, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{Effect, EffectId, ObjectFlags, Spell, TES3Object};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(spel, Spell, values_object_flags:flags);
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(spel, Spell, values_string:name);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            spel,
+            Spell,
+            "Merged SPEL record: \"\"\n",
+            "Merging SPEL record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"name\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged SPEL record: \"\"\n",
+            values_string:name
+        );
+    }
+}
+
+mod effects {
+    use super::{assert_eq, *};
+
+    #[test]
+    fn no_merge_last_equal_to_merged() {
+        test_init!(src, plugins, cfg, Spell, 3, values_effects);
+        src[0].effects = values_effects[0].clone();
+        src[1].effects = values_effects[0].clone();
+        src[2].effects = values_effects[3].clone();
+        test_merge!(spel, src, plugins, cfg, log, im, res, dst:0);
+    }
+
+    #[test]
+    fn merge_edit_and_add() {
+        test_init!(src, plugins, cfg, Spell, 3, values_effects);
+        src[0].effects = values_effects[0].clone();
+        src[1].effects = values_effects[1].clone();
+        src[2].effects = values_effects[2].clone();
+        let expected = Spell { effects: values_effects[3].clone(), ..Default::default() };
+        test_merge!(spel, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Spell, expected, dst[0]);
+    }
+
+    #[test]
+    fn merge_delete_and_add() {
+        test_init!(src, plugins, cfg, Spell, 3, values_effects);
+        src[0].effects = values_effects[2].clone();
+        src[1].effects = values_effects[4].clone();
+        src[2].effects = values_effects[5].clone();
+        let expected = Spell { effects: values_effects[6].clone(), ..Default::default() };
+        test_merge!(spel, src, plugins, cfg, log, im, res, dst:1);
+        assert_eq_inner!(Spell, expected, dst[0]);
+    }
+
+    #[test]
+    fn merge_log() {
+        test_init!(src, plugins, cfg, Spell, 3, values_effects);
+        src[0].effects = values_effects[0].clone();
+        src[1].effects = values_effects[1].clone();
+        src[2].effects = values_effects[2].clone();
+        test_merge!(spel, src, plugins, cfg, log, im, res, dst:1);
+        assert!(log
+            .test_file()
+            .contains("\"effects\": FireDamage[0].max_magnitude: 10 -> 20 [\"Plugin1.esp\"]"));
+        assert!(log.test_file().contains("\"effects\": + FrostDamage"));
+    }
+}
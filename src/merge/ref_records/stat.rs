@@ -0,0 +1,20 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_object_flags, MergeLog, OptionRecordMergeLog, RawPlugin,
+    SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{ObjectFlags, Static, TES3Object};
+
+pub(crate) struct StatRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub mesh: &'a str,
+}
+
+generic_ref_record_methods!((StatRef, Static, id), (mesh), (flags), (), (), ());
+
+generic_make_merge!(stat, (StatRef, Static, id), (flags=ObjectFlags, mesh.&), (), (), (), ());
@@ -269,6 +269,23 @@ macro_rules! test_init {
                     vec!["SPELL_1".to_string(), "SPELL_2".to_string(), "SPELL_3".to_string()],
                 ] }; }
 
+                #[allow(unused_macros)]
+                macro_rules! values_effects { () => {{
+                        let fire = Effect { magic_effect: EffectId::FireDamage, min_magnitude: 10, max_magnitude: 10, ..Default::default() };
+                        let fire_edited = Effect { magic_effect: EffectId::FireDamage, min_magnitude: 10, max_magnitude: 20, ..Default::default() };
+                        let frost = Effect { magic_effect: EffectId::FrostDamage, min_magnitude: 5, max_magnitude: 5, ..Default::default() };
+                        let shock = Effect { magic_effect: EffectId::ShockDamage, min_magnitude: 1, max_magnitude: 1, ..Default::default() };
+                        [
+                            vec![fire.clone()],
+                            vec![fire_edited.clone()],
+                            vec![fire.clone(), frost.clone()],
+                            vec![fire_edited.clone(), frost.clone()],
+                            vec![frost.clone()],
+                            vec![fire.clone(), frost.clone(), shock.clone()],
+                            vec![frost.clone(), shock.clone()],
+                        ]
+                    }}; }
+
                 #[allow(unused_macros)]
                 macro_rules! values_inventory { () => {{
                         let i0 = (0_i32, FixedString::<32>("inventory_0".to_string()));
@@ -289,6 +306,21 @@ macro_rules! test_init {
                         ]
                     }}; }
 
+                #[allow(unused_macros)]
+                macro_rules! values_sounds { () => {{
+                        let s0 = (FixedString::<32>("sound_0".to_string()), 10_u8);
+                        let s1 = (FixedString::<32>("sound_1".to_string()), 20_u8);
+                        let s2 = (FixedString::<32>("sound_2".to_string()), 30_u8);
+                        [
+                            vec![s0.clone()],
+                            vec![s0.clone(), s1.clone()],
+                            vec![s1.clone()],
+                            vec![s0.clone(), s2.clone()],
+                            vec![s1.clone(), s2.clone()],
+                            vec![(FixedString::<32>("SOUND_0".to_string()), 10_u8), s1.clone()],
+                        ]
+                    }}; }
+
                 #[allow(unused_macros)]
                 macro_rules! values_travel_destinations_i { () => {{
                         let tdi0 = TravelDestination { cell: "cell_0".to_string(), ..Default::default() };
@@ -335,6 +367,76 @@ macro_rules! test_init {
                         ]
                     }}; }
 
+                #[allow(unused_macros)]
+                macro_rules! values_gmst { () => {[
+                    GameSettingValue::Integer(1000),
+                    GameSettingValue::Integer(10),
+                    GameSettingValue::Integer(12),
+                    GameSettingValue::Integer(15),
+                ]}; }
+
+                #[allow(unused_macros)]
+                macro_rules! values_infos { () => {{
+                    // COMMENT: links are made from the order of ids, e.g. ["1", "2"] makes "1" <-> "2"
+                    let chain = |ids: &[&str]| -> Vec<DialogueInfo> {
+                        ids.iter()
+                            .enumerate()
+                            .map(|(index, id)| DialogueInfo {
+                                id: id.to_string(),
+                                prev_id: if index == 0 { String::new() } else { ids[index - 1].to_string() },
+                                next_id: ids.get(index + 1).map(|id| id.to_string()).unwrap_or_default(),
+                                ..Default::default()
+                            })
+                            .collect::<Vec<DialogueInfo>>()
+                    };
+                    [
+                        chain(&["1", "2"]),
+                        chain(&["1", "3", "2"]),
+                        chain(&["1", "4", "2"]),
+                        chain(&["1", "4", "3", "2"]),
+                        chain(&["1", "3", "2", "5"]),
+                        chain(&["1", "3", "4", "2"]),
+                    ]
+                }}; }
+
+                #[allow(unused_macros)]
+                macro_rules! values_vertex_colors { () => {[[0, 0, 0], [1, 2, 3], [4, 5, 6], [7, 8, 9]]}; }
+
+                #[allow(unused_macros)]
+                macro_rules! values_pathgrid { () => {{
+                    // COMMENT: every point is given with the list of points it's connected to
+                    let grid = |points: &[([i32; 3], &[u32])]| -> PathGrid {
+                        let mut pgrd = PathGrid { cell: String::from("Test Cell"), ..Default::default() };
+                        pgrd.data.point_count = points.len() as u16;
+                        pgrd.points = points
+                            .iter()
+                            .map(|(location, targets)| PathGridPoint {
+                                location: *location,
+                                connection_count: targets.len() as u8,
+                                ..Default::default()
+                            })
+                            .collect();
+                        pgrd.connections = points.iter().flat_map(|(_, targets)| targets.iter().copied()).collect();
+                        pgrd
+                    };
+                    [
+                        grid(&[([0, 0, 0], &[1]), ([100, 0, 0], &[0])]),
+                        grid(&[([0, 0, 0], &[1]), ([100, 0, 0], &[0, 2]), ([200, 0, 0], &[1])]),
+                        grid(&[([0, 0, 0], &[1, 2]), ([100, 0, 0], &[0]), ([0, 300, 0], &[0])]),
+                        grid(&[([10, -10, 5], &[1]), ([100, 0, 0], &[0, 2]), ([200, 200, 0], &[1])]),
+                        grid(&[([0, 0, 0], &[1, 5]), ([100, 0, 0], &[0])]),
+                    ]
+                }}; }
+
+                #[allow(unused_macros)]
+                macro_rules! values_script_text { () => {[
+                    String::from("Begin TestScript\nEnd"),
+                    String::from("Begin TestScript  \r\nEnd\n"),
+                    String::from("Begin TestScript\nshort a\nset a to 1\nEnd"),
+                    String::from("Begin TestScript\nshort a\nset a to 2\nEnd"),
+                    String::from("Begin TestScript\nMessageBox \"Hi\"\nEnd"),
+                ]}; }
+
                 #[allow(unused_macros)]
                 macro_rules! values_travel_destinations { () => {{
                     let mut joined = values_travel_destinations_i!().to_vec();
@@ -0,0 +1,61 @@
+use super::{
+    generic_make_merge, generic_ref_record_methods, print_as, show_flags, show_object_flags, MergeLog, OptionRecordMergeLog,
+    RawPlugin, SpecificFlags,
+};
+use crate::{Cfg, IntermediateRecords};
+use anyhow::{Context, Result};
+use paste::paste;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::convert::identity;
+use tes3::esp::{ObjectFlags, TES3Object, Weapon, WeaponData, WeaponFlags};
+
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+use super::{assert_eq_inner, test_basic, test_flags, test_init, test_log, test_log_flags, test_merge};
+
+pub(crate) struct WeapRef<'a> {
+    pub flags: ObjectFlags,
+    pub id: &'a str,
+    pub name: &'a str,
+    pub mesh: &'a str,
+    pub icon: &'a str,
+    pub enchanting: &'a str,
+    pub script: &'a str,
+    pub data: WeaponData,
+}
+
+show_flags!(WeaponFlags, IGNORES_NORMAL_WEAPON_RESISTANCE, SILVER);
+
+generic_ref_record_methods!((WeapRef, Weapon, id), (name, mesh, icon, enchanting, script), (flags, data), (), (), ());
+
+generic_make_merge!(
+    weap,
+    (WeapRef, Weapon, id),
+    (
+        flags=ObjectFlags,
+        name.&,
+        mesh.&,
+        icon.&,
+        enchanting.&,
+        script.&,
+        data:weight,
+        data:value,
+        data:weapon_type,
+        data:health,
+        data:speed,
+        data:reach,
+        data:enchantment,
+        data:chop_min,
+        data:chop_max,
+        data:slash_min,
+        data:slash_max,
+        data:thrust_min,
+        data:thrust_max,
+        data:flags=WeaponFlags
+    ),
+    (),
+    (),
+    (),
+    ()
+);
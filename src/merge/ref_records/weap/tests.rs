@@ -0,0 +1,45 @@
+use super::{assert_eq_inner, merge_weap, test_basic, test_flags, test_init, test_log, test_log_flags, test_merge, MergeLog, RawPlugin};
+use crate::{Cfg, IntermediateRecords, PluginInfo};
+use paste::paste;
+use pretty_assertions::assert_eq;
+use std::iter::repeat;
+use tes3::esp::{ObjectFlags, TES3Object, Weapon};
+
+mod basic {
+    use super::{assert_eq, *};
+
+    mod object_flags {
+        use super::{assert_eq, *};
+        test_basic!(weap, Weapon, values_object_flags:flags);
+        test_flags!(weap, Weapon, values_object_flags:flags);
+        test_log_flags!(
+            weap,
+            Weapon,
+            false,
+            "Merged WEAP record: \"\"\n",
+            "Merging WEAP record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"flags\": - DELETED [\"Plugin1.esp\"]\n\"flags\": - PERSISTENT [\"Plugin1.esp\"]\n\"flags\": + IGNORED [\"Plugin1.esp\"]\nMerged WEAP record: \"\"\n",
+            values_object_flags:flags
+        );
+    }
+
+    mod name {
+        use super::{assert_eq, *};
+        test_basic!(weap, Weapon, values_string:name);
+    }
+
+    mod data_speed {
+        use super::{assert_eq, *};
+        test_basic!(weap, Weapon, values_f32:data:speed);
+    }
+
+    mod log {
+        use super::{assert_eq, *};
+        test_log!(
+            weap,
+            Weapon,
+            "Merged WEAP record: \"\"\n",
+            "Merging WEAP record: \"\" [\"Plugin0.esp\"...\"Plugin2.esp\"]\n\"script\": \"\" -> \"string_1\" [\"Plugin1.esp\"]\nMerged WEAP record: \"\"\n",
+            values_string:script
+        );
+    }
+}
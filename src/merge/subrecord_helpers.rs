@@ -1,7 +1,12 @@
 use super::OptionRecordMergeLog;
 mod ai_package;
+mod effects;
 mod generic;
 mod inventory;
+mod rank_names;
+mod reactions;
+mod references;
+mod sounds;
 mod spells;
 mod travel_destinations;
 use generic::{add_and_log_field_lengthen, get_vec_element, log_field_shorten};
@@ -9,7 +14,12 @@ use inventory::{added_to_owned, commit, get_low_sorted_last};
 use spells::make;
 pub(super) use {
     ai_package::{ai_package_variant, ai_packages_equal, AiPackagesHelper},
+    effects::EffectsHelper,
     inventory::{to_lowercase as inventory_to_lowercase, InventoryHelper},
+    rank_names::RankNamesHelper,
+    reactions::{to_lowercase as reaction_to_lowercase, ReactionsHelper},
+    references::ReferencesHelper,
+    sounds::{to_lowercase as sound_to_lowercase, SoundsHelper},
     spells::{to_lowercase as spell_to_lowercase, SpellsHelper},
     travel_destinations::{sort_travel_destinations, to_lowercase as travel_destination_to_lowercase, TravelDestinationsHelper},
 };
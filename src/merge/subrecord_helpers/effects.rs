@@ -0,0 +1,223 @@
+// COMMENT: used in [Alchemy, Enchanting, Spell].
+use super::OptionRecordMergeLog;
+use crate::{AlchemyRecordMap, Cfg, EnchantingRecordMap, PluginInfo, RecordMap, SpellRecordMap};
+use anyhow::{Context, Result};
+use paste::paste;
+use tes3::esp::Effect;
+
+macro_rules! log_field_extend {
+    ($option_log:ident, $shorten:expr, $element:expr, $plugin_info:expr, $map:ident, $cfg:ident) => {
+        $option_log.field_extend(
+            "effects",
+            $shorten,
+            format_args!(
+                "{:?}({:?},{:?}) {:?} magnitude: {}-{}, duration: {}, area: {}",
+                $element.magic_effect,
+                $element.skill,
+                $element.attribute,
+                $element.range,
+                $element.min_magnitude,
+                $element.max_magnitude,
+                $element.duration,
+                $element.area,
+            ),
+            &$plugin_info.name,
+            $map,
+            $cfg,
+        )?;
+    };
+}
+
+macro_rules! make {
+    ($($short:ident, $long:ident),+) => {
+        $(paste! {
+            pub(crate) fn [<make_ $short>](&mut self, map: &'a [<$long RecordMap>], next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+                if !self.generated {
+                    self.effects = map.records.iter().map(|record| record.$short.effects.as_slice()).collect();
+                    self.generated = true;
+                }
+                let plugin_info = map.record(next_index)?.plugin_info;
+                if cfg.merge.plus_before_minus {
+                    self.add(next_index, plugin_info, option_log, &map, cfg)?;
+                };
+                self.delete(next_index, plugin_info, option_log, &map, cfg)?;
+                self.edit(next_index, plugin_info, option_log, &map, cfg)?;
+                if !cfg.merge.plus_before_minus {
+                    self.add(next_index, plugin_info, option_log, &map, cfg)?;
+                }
+                Ok(())
+            }
+        })+
+    };
+}
+
+macro_rules! edit_fields {
+    ($self:ident, $subindex:ident, $base:ident, $new:ident, $plugin_info:ident, $option_log:ident, $map:ident, $cfg:ident, $($field:ident),+) => {
+        $(if $new.$field != $base.$field {
+            let edited = match $self.edited.iter_mut().find(|edited| edited.0 == $subindex) {
+                Some(edited) => edited,
+                None => {
+                    $self.edited.push(($subindex, $base.clone()));
+                    $self.edited.last_mut().with_context(|| "Bug: failed to get EffectsHelper.edited.last_mut()")?
+                }
+            };
+            if edited.1.$field != $new.$field {
+                if !$cfg.meta.silent {
+                    $option_log.field_changed_custom(
+                        "effects",
+                        format_args!(
+                            "{:?}[{}].{}: {:?} -> {:?}",
+                            $base.magic_effect,
+                            $subindex,
+                            stringify!($field),
+                            edited.1.$field,
+                            $new.$field
+                        ),
+                        &$plugin_info.name,
+                        $map,
+                        $cfg,
+                    )?;
+                }
+                edited.1.$field = $new.$field;
+            }
+        })+
+    };
+}
+
+type Added<'a> = (&'a Effect, &'a PluginInfo);
+
+#[derive(Default)]
+pub(crate) struct EffectsHelper<'a> {
+    generated: bool,
+    effects: Vec<&'a [Effect]>,
+    added: Vec<Added<'a>>,
+    deleted: Vec<(usize, &'a PluginInfo)>,
+    edited: Vec<(usize, Effect)>,
+}
+
+impl<'a> EffectsHelper<'a> {
+    make!(spel, Spell, ench, Enchanting, alch, Alchemy);
+
+    fn delete<'b, T: RecordMap<'b>>(
+        &mut self,
+        next_index: usize,
+        plugin_info: &'a PluginInfo,
+        option_log: &mut OptionRecordMergeLog,
+        map: &'b T,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        let (base, new) = (self.effects[0], self.effects[next_index]);
+        for (subindex, effect) in base.iter().enumerate() {
+            if find_match(new, effect, occurrence(base, subindex)).is_none()
+                && !self.deleted.iter().any(|deleted| deleted.0 == subindex)
+            {
+                self.deleted.push((subindex, plugin_info));
+                if !cfg.meta.silent {
+                    log_field_extend!(option_log, true, effect, plugin_info, map, cfg);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add<'b, T: RecordMap<'b>>(
+        &mut self,
+        next_index: usize,
+        plugin_info: &'a PluginInfo,
+        option_log: &mut OptionRecordMergeLog,
+        map: &'b T,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        let (base, new) = (self.effects[0], self.effects[next_index]);
+        for (subindex, effect) in new.iter().enumerate() {
+            let new_occurrence = occurrence(new, subindex);
+            if find_match(base, effect, new_occurrence).is_none() {
+                // COMMENT: n-th extra instance of the same effect is added only once
+                let extra_index = new_occurrence - base.iter().filter(|base_effect| same_effect(base_effect, effect)).count();
+                if self.added.iter().filter(|added| same_effect(added.0, effect)).count() <= extra_index {
+                    self.added.push((effect, plugin_info));
+                    if !cfg.meta.silent {
+                        log_field_extend!(option_log, false, effect, plugin_info, map, cfg);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn edit<'b, T: RecordMap<'b>>(
+        &mut self,
+        next_index: usize,
+        plugin_info: &'a PluginInfo,
+        option_log: &mut OptionRecordMergeLog,
+        map: &'b T,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        let (base_effects, new_effects) = (self.effects[0], self.effects[next_index]);
+        for (subindex, base) in base_effects.iter().enumerate() {
+            if self.deleted.iter().any(|deleted| deleted.0 == subindex) {
+                continue;
+            }
+            if let Some(new_index) = find_match(new_effects, base, occurrence(base_effects, subindex)) {
+                let new = &new_effects[new_index];
+                edit_fields!(
+                    self,
+                    subindex,
+                    base,
+                    new,
+                    plugin_info,
+                    option_log,
+                    map,
+                    cfg,
+                    range,
+                    area,
+                    duration,
+                    min_magnitude,
+                    max_magnitude
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn commit(&mut self, base: &[Effect]) -> Vec<Effect> {
+        let mut res = base
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.deleted.iter().any(|deleted| &deleted.0 == index))
+            .map(|(index, kept)| match self.edited.iter().find(|edited| edited.0 == index) {
+                Some(edited) => edited.1.clone(),
+                None => kept.clone(),
+            })
+            .collect::<Vec<Effect>>();
+        res.extend(self.added.iter().map(|added| added.0.clone()));
+        res
+    }
+
+    // COMMENT: order of effects is meaningful, so the last list is not sorted
+    pub(crate) fn get_low_sorted_last(&self) -> Result<Vec<&Effect>> {
+        Ok(self
+            .effects
+            .last()
+            .with_context(|| "Bug: failed to get EffectsHelper.effects.last()")?
+            .iter()
+            .collect::<Vec<&Effect>>())
+    }
+}
+
+fn same_effect(a: &Effect, b: &Effect) -> bool {
+    a.magic_effect == b.magic_effect && a.skill == b.skill && a.attribute == b.attribute
+}
+
+fn occurrence(effects: &[Effect], index: usize) -> usize {
+    effects[..index].iter().filter(|effect| same_effect(effect, &effects[index])).count()
+}
+
+fn find_match(effects: &[Effect], effect: &Effect, occurrence: usize) -> Option<usize> {
+    effects
+        .iter()
+        .enumerate()
+        .filter(|(_, other)| same_effect(other, effect))
+        .nth(occurrence)
+        .map(|(index, _)| index)
+}
@@ -0,0 +1,124 @@
+// COMMENT: used in [Faction].
+use super::OptionRecordMergeLog;
+use crate::{Cfg, FactionRecordMap, PluginInfo};
+use anyhow::{Context, Result};
+
+// COMMENT: ranks are positional, so every index is merged on its own, the last distinct value wins
+#[derive(Default)]
+pub(crate) struct RankNamesHelper<'a> {
+    generated: bool,
+    lowercased: Vec<Vec<String>>,
+    edited: Vec<(usize, &'a str, &'a PluginInfo)>,
+    deleted: Vec<(usize, &'a PluginInfo)>,
+}
+
+impl<'a> RankNamesHelper<'a> {
+    pub(crate) fn make_fact(
+        &mut self,
+        map: &'a FactionRecordMap,
+        next_index: usize,
+        option_log: &mut OptionRecordMergeLog,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        if !self.generated {
+            self.lowercased = map
+                .records
+                .iter()
+                .map(|record| record.fact.rank_names.iter().map(|rank_name| rank_name.to_lowercase()).collect::<Vec<String>>())
+                .collect::<Vec<Vec<String>>>();
+            self.generated = true;
+        }
+        let record = map.record(next_index)?;
+        let (base, new) = (&self.lowercased[0], &self.lowercased[next_index]);
+        for subindex in new.len()..base.len() {
+            if !self.deleted.iter().any(|deleted| deleted.0 == subindex) {
+                self.deleted.push((subindex, record.plugin_info));
+                if !cfg.meta.silent {
+                    option_log.field_extend(
+                        "rank_names",
+                        true,
+                        format_args!("[{subindex}] {:?}", map.record(0)?.fact.rank_names[subindex]),
+                        &record.plugin_info.name,
+                        &map,
+                        cfg,
+                    )?;
+                }
+            }
+        }
+        for (subindex, rank_name) in new.iter().enumerate() {
+            if base.get(subindex) == Some(rank_name) {
+                continue;
+            }
+            let value = record.fact.rank_names[subindex].as_str();
+            match self.edited.iter_mut().find(|edited| edited.0 == subindex) {
+                Some(edited) => {
+                    if edited.1.to_lowercase() != *rank_name {
+                        if !cfg.meta.silent {
+                            option_log.field_changed_custom(
+                                "rank_names",
+                                format_args!("[{subindex}]: {:?} -> {:?}", edited.1, value),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?;
+                        }
+                        *edited = (subindex, value, record.plugin_info);
+                    }
+                }
+                None => {
+                    if !cfg.meta.silent {
+                        match map.record(0)?.fact.rank_names.get(subindex) {
+                            Some(base_value) => option_log.field_changed_custom(
+                                "rank_names",
+                                format_args!("[{subindex}]: {base_value:?} -> {value:?}"),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?,
+                            None => option_log.field_extend(
+                                "rank_names",
+                                false,
+                                format_args!("[{subindex}] {value:?}"),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?,
+                        }
+                    }
+                    self.edited.push((subindex, value, record.plugin_info));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // COMMENT: names can't be shifted to other ranks, so the list is cut at the first deleted rank that is not followed by edits
+    pub(crate) fn commit(&mut self, base: &[String]) -> Result<Vec<String>> {
+        let len = match self
+            .deleted
+            .iter()
+            .map(|deleted| deleted.0)
+            .filter(|index| !self.edited.iter().any(|edited| edited.0 >= *index))
+            .min()
+        {
+            Some(first_deleted) => first_deleted,
+            None => self.edited.iter().map(|edited| edited.0 + 1).max().unwrap_or_default().max(base.len()),
+        };
+        Ok((0..len)
+            .map_while(|index| match self.edited.iter().find(|edited| edited.0 == index) {
+                Some(edited) => Some(edited.1.to_owned()),
+                None => base.get(index).cloned(),
+            })
+            .collect::<Vec<String>>())
+    }
+
+    // COMMENT: order of ranks is meaningful, so the last list is not sorted
+    pub(crate) fn get_low_sorted_last(&self) -> Result<Vec<&String>> {
+        Ok(self
+            .lowercased
+            .last()
+            .with_context(|| "Bug: failed to get RankNamesHelper.lowercased.last()")?
+            .iter()
+            .collect::<Vec<&String>>())
+    }
+}
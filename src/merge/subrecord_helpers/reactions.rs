@@ -0,0 +1,171 @@
+// COMMENT: used in [Faction].
+use super::{get_low_sorted_last, OptionRecordMergeLog};
+use crate::{Cfg, FactionRecordMap, PluginInfo};
+use anyhow::{Context, Result};
+use tes3::esp::FactionReaction;
+
+macro_rules! log_field_extend {
+    ($option_log:ident, $shorten:expr, $element:expr, $plugin_info:expr, $map:ident, $cfg:ident) => {
+        $option_log.field_extend(
+            "reactions",
+            $shorten,
+            format_args!("\"{}\"({})", $element.faction, $element.reaction),
+            &$plugin_info.name,
+            &$map,
+            $cfg,
+        )?;
+    };
+}
+
+type ReactionRecordLow = (String, i32);
+type Added<'a> = (String, FactionReaction, &'a PluginInfo);
+
+// COMMENT: reactions are unique per faction, so value changes are tracked as edits instead of delete + add
+#[derive(Default)]
+pub(crate) struct ReactionsHelper<'a> {
+    generated: bool,
+    lowercased: Vec<Vec<ReactionRecordLow>>,
+    added: Vec<Added<'a>>,
+    deleted: Vec<(usize, &'a PluginInfo)>,
+    edited: Vec<(usize, i32)>,
+}
+
+impl<'a> ReactionsHelper<'a> {
+    pub(crate) fn make_fact(
+        &mut self,
+        map: &'a FactionRecordMap,
+        next_index: usize,
+        option_log: &mut OptionRecordMergeLog,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        if !self.generated {
+            self.lowercased = map
+                .records
+                .iter()
+                .map(|record| record.fact.reactions.iter().map(to_lowercase).collect::<Vec<ReactionRecordLow>>())
+                .collect::<Vec<Vec<ReactionRecordLow>>>();
+            self.generated = true;
+        }
+        if cfg.merge.plus_before_minus {
+            self.add(map, next_index, option_log, cfg)?;
+        };
+        self.delete(map, next_index, option_log, cfg)?;
+        self.edit(map, next_index, option_log, cfg)?;
+        if !cfg.merge.plus_before_minus {
+            self.add(map, next_index, option_log, cfg)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, map: &'a FactionRecordMap, next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+        let record = map.record(next_index)?;
+        for (subindex, (faction, _)) in self.lowercased[0].iter().enumerate() {
+            if !self.deleted.iter().any(|deleted| deleted.0 == subindex)
+                && !self.lowercased[next_index].iter().any(|(new_faction, _)| new_faction == faction)
+            {
+                self.deleted.push((subindex, record.plugin_info));
+                if !cfg.meta.silent {
+                    log_field_extend!(option_log, true, &map.record(0)?.fact.reactions[subindex], record.plugin_info, map, cfg);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn edit(&mut self, map: &'a FactionRecordMap, next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+        let record = map.record(next_index)?;
+        for (subindex, (faction, base_reaction)) in self.lowercased[0].iter().enumerate() {
+            if self.deleted.iter().any(|deleted| deleted.0 == subindex) {
+                continue;
+            }
+            if let Some((_, new_reaction)) = self.lowercased[next_index].iter().find(|(new_faction, _)| new_faction == faction) {
+                if new_reaction == base_reaction {
+                    continue;
+                }
+                let old_reaction = match self.edited.iter_mut().find(|edited| edited.0 == subindex) {
+                    Some(edited) => {
+                        let old_reaction = edited.1;
+                        edited.1 = *new_reaction;
+                        old_reaction
+                    }
+                    None => {
+                        self.edited.push((subindex, *new_reaction));
+                        *base_reaction
+                    }
+                };
+                if old_reaction != *new_reaction && !cfg.meta.silent {
+                    option_log.field_changed_custom(
+                        "reactions",
+                        format_args!(
+                            "\"{}\": {} -> {}",
+                            map.record(0)?.fact.reactions[subindex].faction,
+                            old_reaction,
+                            new_reaction
+                        ),
+                        &record.plugin_info.name,
+                        &map,
+                        cfg,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, map: &'a FactionRecordMap, next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+        let record = map.record(next_index)?;
+        for (subindex, (faction, reaction)) in self.lowercased[next_index].iter().enumerate() {
+            if self.lowercased[0].iter().any(|(base_faction, _)| base_faction == faction) {
+                continue;
+            }
+            let new = &record.fact.reactions[subindex];
+            match self.added.iter_mut().find(|added| &added.0 == faction) {
+                Some(added) => {
+                    if added.1.reaction != *reaction {
+                        if !cfg.meta.silent {
+                            option_log.field_changed_custom(
+                                "reactions",
+                                format_args!("\"{}\": {} -> {}", new.faction, added.1.reaction, reaction),
+                                &record.plugin_info.name,
+                                &map,
+                                cfg,
+                            )?;
+                        }
+                        added.1.reaction = *reaction;
+                        added.2 = record.plugin_info;
+                    }
+                }
+                None => {
+                    self.added.push((faction.to_owned(), new.clone(), record.plugin_info));
+                    if !cfg.meta.silent {
+                        log_field_extend!(option_log, false, new, record.plugin_info, map, cfg);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn commit(&mut self, base: &[FactionReaction]) -> Result<Vec<FactionReaction>> {
+        let mut res = base
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.deleted.iter().any(|deleted| &deleted.0 == index))
+            .map(|(index, kept)| match self.edited.iter().find(|edited| edited.0 == index) {
+                Some(edited) => FactionReaction {
+                    reaction: edited.1,
+                    ..kept.clone()
+                },
+                None => kept.clone(),
+            })
+            .collect::<Vec<FactionReaction>>();
+        res.extend(self.added.iter().map(|added| added.1.clone()));
+        Ok(res)
+    }
+
+    get_low_sorted_last!(ReactionRecordLow, Reactions);
+}
+
+pub(crate) fn to_lowercase(arg: &FactionReaction) -> ReactionRecordLow {
+    (arg.faction.to_lowercase(), arg.reaction)
+}
@@ -0,0 +1,119 @@
+// COMMENT: used in [Cell].
+use super::OptionRecordMergeLog;
+use crate::{CellRecordMap, Cfg};
+use anyhow::{Context, Result};
+use hashbrown::{hash_map::Entry, HashMap};
+use tes3::esp::Reference;
+
+type ReferenceKey = (u32, u32);
+
+macro_rules! edit_fields {
+    ($first:ident, $merged:ident, $new:ident, $key:ident, $plugin_info:expr, $option_log:ident, $map:ident, $cfg:ident, $($field:ident),+) => {
+        $(if $new.$field != $first.$field && $new.$field != $merged.$field {
+            if !$cfg.meta.silent {
+                $option_log.field_changed_custom(
+                    "references",
+                    format_args!(
+                        "\"{}\"({}:{}).{}: {:?} -> {:?}",
+                        $first.id,
+                        $key.0,
+                        $key.1,
+                        stringify!($field),
+                        $merged.$field,
+                        $new.$field
+                    ),
+                    &$plugin_info.name,
+                    &$map,
+                    $cfg,
+                )?;
+            }
+            $merged.$field = $new.$field.clone();
+        })+
+    };
+}
+
+// COMMENT: keys are rebased on the load order while reading plugins, so references from different plugins are comparable
+#[derive(Default)]
+pub(crate) struct ReferencesHelper<'a> {
+    generated: bool,
+    last: Option<&'a HashMap<ReferenceKey, Reference>>,
+    references: HashMap<ReferenceKey, (&'a Reference, Reference, &'a Reference)>,
+}
+
+impl<'a> ReferencesHelper<'a> {
+    pub(crate) fn make_cell(
+        &mut self,
+        map: &'a CellRecordMap,
+        next_index: usize,
+        option_log: &mut OptionRecordMergeLog,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        if !self.generated {
+            for (key, reference) in map.record(0)?.cell.references.iter() {
+                self.references.insert(*key, (reference, reference.clone(), reference));
+            }
+            self.last = Some(&map.last_record()?.cell.references);
+            self.generated = true;
+        }
+        let record = map.record(next_index)?;
+        for (key, new) in record.cell.references.iter() {
+            match self.references.entry(*key) {
+                Entry::Vacant(v) => {
+                    v.insert((new, new.clone(), new));
+                    if !cfg.meta.silent {
+                        option_log.field_extend(
+                            "references",
+                            false,
+                            format_args!("\"{}\"({}:{})", new.id, key.0, key.1),
+                            &record.plugin_info.name,
+                            &map,
+                            cfg,
+                        )?;
+                    }
+                }
+                Entry::Occupied(mut o) => {
+                    let (first, merged, last) = o.get_mut();
+                    *last = new;
+                    edit_fields!(
+                        first,
+                        merged,
+                        new,
+                        key,
+                        record.plugin_info,
+                        option_log,
+                        map,
+                        cfg,
+                        translation,
+                        rotation,
+                        scale,
+                        deleted
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // COMMENT: references of the last instance and added ones are kept, others are placed only if they differ from their last version
+    pub(crate) fn commit(&mut self, base: &HashMap<ReferenceKey, Reference>) -> Result<HashMap<ReferenceKey, Reference>> {
+        let last = self.last.with_context(|| "Bug: failed to get ReferencesHelper.last")?;
+        Ok(self
+            .references
+            .iter()
+            .filter(|(key, (_, merged, last_version))| {
+                last.contains_key(*key) || merged != *last_version || (!base.contains_key(*key) && merged.deleted.is_none())
+            })
+            .map(|(key, (_, merged, _))| (*key, merged.clone()))
+            .collect::<HashMap<ReferenceKey, Reference>>())
+    }
+
+    pub(crate) fn get_low_sorted_last(&self) -> Result<Vec<(&ReferenceKey, &Reference)>> {
+        let mut res = self
+            .last
+            .with_context(|| "Bug: failed to get ReferencesHelper.last")?
+            .iter()
+            .collect::<Vec<_>>();
+        res.sort_by_key(|(key, _)| **key);
+        Ok(res)
+    }
+}
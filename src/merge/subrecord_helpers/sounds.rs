@@ -0,0 +1,90 @@
+// COMMENT: used in [Region].
+use super::{
+    add_and_log_field_lengthen, added_to_owned, commit, get_low_sorted_last, get_vec_element, log_field_shorten, OptionRecordMergeLog,
+};
+use crate::{Cfg, PluginInfo, RegionRecordMap};
+use anyhow::{Context, Result};
+use paste::paste;
+use tes3::esp::FixedString;
+
+macro_rules! log_field_extend {
+    ($option_log:ident, $shorten:expr, $element:expr, $record:expr, $map:ident, $cfg:ident) => {
+        $option_log.field_extend(
+            "sounds",
+            $shorten,
+            format_args!("\"{}\"({})", $element.0.as_str(), $element.1),
+            &$record.plugin_info.name,
+            &$map,
+            $cfg,
+        )?;
+    };
+}
+
+type SoundRecord = (FixedString<32>, u8);
+type SoundRecordLow = (String, u8);
+type Added<'a> = (SoundRecordLow, &'a SoundRecord, &'a PluginInfo);
+
+#[derive(Default)]
+pub(crate) struct SoundsHelper<'a> {
+    generated: bool,
+    lowercased: Vec<Vec<SoundRecordLow>>,
+    added: Vec<Added<'a>>,
+    deleted: Vec<(usize, &'a PluginInfo)>,
+}
+
+impl<'a> SoundsHelper<'a> {
+    pub(crate) fn make_regn(
+        &mut self,
+        map: &'a RegionRecordMap,
+        next_index: usize,
+        option_log: &mut OptionRecordMergeLog,
+        cfg: &Cfg,
+    ) -> Result<()> {
+        if !self.generated {
+            self.lowercased = map
+                .records
+                .iter()
+                .map(|record| record.regn.sounds.iter().map(to_lowercase).collect::<Vec<SoundRecordLow>>())
+                .collect::<Vec<Vec<SoundRecordLow>>>();
+            self.generated = true;
+        }
+        if cfg.merge.plus_before_minus {
+            self.add_regn(map, next_index, option_log, cfg)?;
+        };
+        self.delete_regn(map, next_index, option_log, cfg)?;
+        if !cfg.merge.plus_before_minus {
+            self.add_regn(map, next_index, option_log, cfg)?;
+        }
+        Ok(())
+    }
+
+    fn delete_regn(&mut self, map: &'a RegionRecordMap, next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+        for (subindex, subrecord) in self.lowercased[0].iter().enumerate() {
+            if !self.deleted.iter().any(|deleted| deleted.0 == subindex) && !self.lowercased[next_index].contains(subrecord) {
+                let base_record = &map.record(0)?;
+                self.deleted.push((subindex, base_record.plugin_info));
+                log_field_shorten!(base_record, sounds, next_index, subindex, option_log, map, regn, cfg);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_regn(&mut self, map: &'a RegionRecordMap, next_index: usize, option_log: &mut OptionRecordMergeLog, cfg: &Cfg) -> Result<()> {
+        for (subindex, subrecord) in self.lowercased[next_index].iter().enumerate() {
+            if !self.lowercased[0].contains(subrecord) && !self.added.iter().any(|added| &added.0 == subrecord) {
+                add_and_log_field_lengthen!(self.added, subrecord, sounds, next_index, subindex, option_log, map, regn, cfg);
+            }
+        }
+        Ok(())
+    }
+
+    commit!(SoundRecord, Sounds, 0);
+    get_low_sorted_last!(SoundRecordLow, Sounds);
+    added_to_owned!(SoundRecord, Added);
+}
+
+pub(crate) fn to_lowercase(arg: &SoundRecord) -> SoundRecordLow {
+    (arg.0.to_lowercase(), arg.1)
+}
+
+get_vec_element!(sounds, (FixedString<32>, u8), (FixedString<32>, u8));
@@ -52,6 +52,13 @@ pub(super) fn compare_plugins(
             compare_plugin(new, old, old_name, &cfg.delev_output, counts, &mut merge_changed, &mut plugins_differ, cfg, log)
                 .with_context(|| plugin_compare_failure_msg(&cfg.delev_output.name, old_name))?;
         }
+        if cfg.relevel {
+            new = &raw.relevel.plugin;
+            (old, old_name) = (&cmp.relevel_previous, "");
+            #[rustfmt::skip]
+            compare_plugin(new, old, old_name, &cfg.relevel_output, counts, &mut merge_changed, &mut plugins_differ, cfg, log)
+                .with_context(|| plugin_compare_failure_msg(&cfg.relevel_output.name, old_name))?;
+        }
         if cfg.compare_only && plugins_differ != 0 {
             *exit_code = 3;
         }
@@ -189,10 +196,10 @@ fn is_empty(
     if prefix.is_empty() {
         Ok(false)
     } else {
-        let name = if matches!(output.kind, PluginKind::Delev) {
-            "delev"
-        } else {
-            "output"
+        let name = match output.kind {
+            PluginKind::Merge => "output",
+            PluginKind::Delev => "delev",
+            PluginKind::Relevel => "relevel",
         };
         let text = if old_name.is_empty() {
             format!("{prefix} {name} {infix} previous version")
@@ -229,7 +236,9 @@ fn compare_headers(
                     };
                     let mut new_header_masters_len = new_header.masters.len();
                     let mut cfg_output_name_low = String::new();
-                    let merge_was_written = if matches!(output.kind, PluginKind::Delev) && counts.delev.placed > 0 {
+                    let merge_was_written = if (matches!(output.kind, PluginKind::Delev) && counts.delev.placed > 0)
+                        || (matches!(output.kind, PluginKind::Relevel) && counts.relevel.placed > 0)
+                    {
                         cfg_output_name_low = cfg.output.name.to_lowercase();
                         new_header_masters_len += 1;
                         true
@@ -30,6 +30,9 @@ fn update_headers(raw: &mut RawPlugins, cfg: &Cfg) -> Result<()> {
     if cfg.delev_distinct {
         raw.delev.update_header(&cfg.guts.header_description_delev, cfg)?;
     }
+    if cfg.relevel {
+        raw.relevel.update_header(&cfg.guts.header_description_relevel, cfg)?;
+    }
     Ok(())
 }
 
@@ -38,5 +41,6 @@ fn select_placed(output: &OutputFile, counts: &ListCounts, cfg: &Cfg) -> usize {
         PluginKind::Merge if !(cfg.delev && cfg.delev_distinct) => counts.total.placed,
         PluginKind::Merge => counts.merge.placed,
         PluginKind::Delev => counts.delev.placed,
+        PluginKind::Relevel => counts.relevel.placed,
     }
 }
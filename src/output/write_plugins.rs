@@ -36,6 +36,17 @@ pub(super) fn write_plugins(
             log,
         )?;
     }
+    if cfg.relevel {
+        write_plugin(
+            &mut raw.relevel.plugin,
+            counts,
+            &cfg.relevel_output,
+            &mut cmp.relevel_previous,
+            &mut merge_was_written,
+            cfg,
+            log,
+        )?;
+    }
     Ok(())
 }
 
@@ -67,8 +78,8 @@ fn write_plugin(
         let text = format!("Directory \"{}\" was created", output.dir_path.display());
         msg(text, MsgTone::Good, 0, cfg, log)?;
     }
-    if matches!(output.kind, PluginKind::Delev) && *merge_was_written {
-        delev_append_master(plugin, counts, cfg, log)?;
+    if matches!(output.kind, PluginKind::Delev | PluginKind::Relevel) && *merge_was_written {
+        append_output_master(plugin, output, counts, cfg, log)?;
     }
     if previous.loaded {
         if previous.plugin.objects == plugin.objects {
@@ -100,13 +111,16 @@ fn write_plugin(
     Ok(())
 }
 
-fn delev_append_master(deleveled_plugin: &mut Plugin, counts: &mut ListCounts, cfg: &Cfg, log: &mut Log) -> Result<()> {
+fn append_output_master(plugin: &mut Plugin, output: &OutputFile, counts: &mut ListCounts, cfg: &Cfg, log: &mut Log) -> Result<()> {
     let size = get_plugin_size(&cfg.output.path, cfg, log)?;
-    match &mut deleveled_plugin.objects[0] {
+    match &mut plugin.objects[0] {
         TES3Object::Header(header) => header.masters.push((cfg.output.name.clone(), size)),
         _ => return Err(anyhow!("omg")),
     };
-    counts.delev.master += 1;
+    match output.kind {
+        PluginKind::Relevel => counts.relevel.master += 1,
+        _ => counts.delev.master += 1,
+    };
     Ok(())
 }
 
@@ -18,6 +18,14 @@ pub(super) fn show_result(timer: Instant, read_stats: ReadStats, counts: ListCou
         read_stats.plugins.speed,
         read_stats.records.speed,
     )?;
+    if read_stats.groundcover_plugins > 0 {
+        writeln!(
+            text,
+            "Groundcover: {} plugin{} read for cell multipatch only",
+            read_stats.groundcover_plugins,
+            plural("s", read_stats.groundcover_plugins as usize)?
+        )?;
+    }
     write!(text, "Lists stats: {} total, {} unique", counts.total.total, counts.total.unique)?;
     if counts.total.placed > 0 && !(cfg.delev && cfg.delev_distinct) {
         write!(text, ", {} placed", counts.total.placed)?;
@@ -35,6 +43,9 @@ pub(super) fn show_result(timer: Instant, read_stats: ReadStats, counts: ListCou
     if counts.merge.deleted_subrecord > 0 {
         write!(text, ", {} subrecords deleted", counts.merge.deleted_subrecord)?;
     }
+    if counts.merge.edited_subrecord > 0 {
+        write!(text, ", {} subrecords removed or replaced", counts.merge.edited_subrecord)?;
+    }
     if counts.merge.master > 0 && cfg.delev && cfg.delev_distinct {
         write!(text, ", {} masters", counts.merge.master)?;
     }
@@ -50,6 +61,18 @@ pub(super) fn show_result(timer: Instant, read_stats: ReadStats, counts: ListCou
             write!(text, ", {} masters", counts.delev.master)?;
         }
     }
+    if cfg.relevel {
+        write!(text, "\nRelevel stats: {} releveled", counts.relevel.releveled)?;
+        if counts.relevel.placed > 0 {
+            write!(text, ", {} placed", counts.relevel.placed)?;
+        }
+        if counts.relevel.releveled_subrecord > 0 {
+            write!(text, ", {} subrecords releveled", counts.relevel.releveled_subrecord)?;
+        }
+        if counts.relevel.master > 0 {
+            write!(text, ", {} masters", counts.relevel.master)?;
+        }
+    }
     let show = if cfg.no_summary { u8::MAX } else { 0 };
     msg(&text, MsgTone::Neutral, show, cfg, log)?;
     show_output_plugin_suggestion(text, counts, cfg, log)
@@ -61,6 +84,7 @@ fn show_output_plugin_suggestion(mut text: String, counts: ListCounts, cfg: &Cfg
         write!(text, "\nPlace ")?;
         let mut merge_placed = false;
         let mut delev_placed = false;
+        let mut relevel_placed = false;
         if counts.merge.placed > 0 {
             merge_placed = true;
             write!(text, "\"{}\"", cfg.output.name)?;
@@ -72,8 +96,15 @@ fn show_output_plugin_suggestion(mut text: String, counts: ListCounts, cfg: &Cfg
             delev_placed = true;
             write!(text, "\"{}\"", cfg.delev_output.name)?;
         }
+        if counts.relevel.placed > 0 && cfg.relevel {
+            if merge_placed {
+                write!(text, " and ")?;
+            }
+            relevel_placed = true;
+            write!(text, "\"{}\"", cfg.relevel_output.name)?;
+        }
         writeln!(text, " last in load order and activate")?;
-        if merge_placed || delev_placed {
+        if merge_placed || delev_placed || relevel_placed {
             msg(text, MsgTone::Warm, 0, cfg, log)?;
         }
     }
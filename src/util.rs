@@ -15,6 +15,7 @@ pub(crate) struct ListCounts {
     pub(crate) total: ListCountsTotal,
     pub(crate) merge: ListCountsMerge,
     pub(crate) delev: ListCountsDelev,
+    pub(crate) relevel: ListCountsRelevel,
 }
 
 #[derive(Default)]
@@ -32,6 +33,7 @@ pub(crate) struct ListCountsMerge {
     pub(crate) untouched: usize,
     pub(crate) master: usize,
     pub(crate) deleted_subrecord: usize,
+    pub(crate) edited_subrecord: usize,
 }
 
 #[derive(Default)]
@@ -42,6 +44,14 @@ pub(crate) struct ListCountsDelev {
     pub(crate) deleveled_subrecord: usize,
 }
 
+#[derive(Default)]
+pub(crate) struct ListCountsRelevel {
+    pub(crate) releveled: usize,
+    pub(crate) placed: usize,
+    pub(crate) master: usize,
+    pub(crate) releveled_subrecord: usize,
+}
+
 #[derive(Default)]
 pub(super) struct ComparePlugin {
     pub(super) plugin: Plugin,
@@ -59,6 +69,7 @@ pub(super) struct ComparePlugins {
     pub(super) compare_with: ComparePlugin,
     pub(super) delev_previous: ComparePlugin,
     pub(super) delev_compare_with: ComparePlugin,
+    pub(super) relevel_previous: ComparePlugin,
 }
 
 pub(crate) enum MsgTone {
@@ -288,6 +299,7 @@ pub(super) fn plural(word: &str, count: usize) -> Result<&str> {
         "have" => if_plural!(word, "has"),
         "are" => if_plural!(word, "is"),
         "these" => if_plural!(word, "this"),
+        "point" => if_plural!(word, "points"),
         _ => return Err(anyhow!("Bug: Failed to match plural word")),
     };
     Ok(res)